@@ -8,7 +8,8 @@ const BUILD_DIR = path.join(__dirname, '../build');
 
 // Circuits to compile
 const circuits = [
-  { name: 'withdraw', levels: 20 } // Assuming withdraw.circom is the main circuit
+  { name: 'withdraw', levels: 20 }, // Assuming withdraw.circom is the main circuit
+  { name: 'batch_update', levels: 20 }
 ];
 
 // Create build directory if it doesn't exist
@@ -6,11 +6,41 @@ const snarkjs = require('snarkjs'); // Use snarkjs library for vkey export
 // Configuration
 const BUILD_DIR = path.join(__dirname, '../build');
 
+// BN254 base field modulus, for negating G2 points
+const FIELD_MODULUS = 21888242871839275222246405745257275088696311157297823662689037894645226208583n;
+
 // Circuits to export (should match compile.js/setup.js)
 const circuits = [
-  { name: 'withdraw' }
+  { name: 'withdraw' },
+  { name: 'batch_update' }
 ];
 
+// 32-byte big-endian encoding of a decimal field element
+function u256be(value) {
+  return Buffer.from(BigInt(value).toString(16).padStart(64, '0'), 'hex');
+}
+
+function encodeG1([x, y]) {
+  return Buffer.concat([u256be(x), u256be(y)]);
+}
+
+// snarkjs lists Fq2 coordinates as [c0, c1]; the alt_bn128 syscalls take c1 first
+function encodeNegatedG2([[x0, x1], [y0, y1]]) {
+  const negate = (value) => (FIELD_MODULUS - BigInt(value)) % FIELD_MODULUS;
+  return Buffer.concat([u256be(x1), u256be(x0), u256be(negate(y1)), u256be(negate(y0))]);
+}
+
+// Verifying key as the program stores it: alpha, then beta, gamma and delta negated, then IC
+function encodeVerifyingKey(vKey) {
+  return Buffer.concat([
+    encodeG1(vKey.vk_alpha_1),
+    encodeNegatedG2(vKey.vk_beta_2),
+    encodeNegatedG2(vKey.vk_gamma_2),
+    encodeNegatedG2(vKey.vk_delta_2),
+    ...vKey.IC.map(encodeG1),
+  ]);
+}
+
 async function exportKeys() {
   try {
     console.log('Starting key export...');
@@ -21,6 +51,7 @@ async function exportKeys() {
 
       const zkeyPath = path.join(BUILD_DIR, `${name}.zkey`);
       const vkeyPath = path.join(BUILD_DIR, `${name}_verification_key.json`);
+      const solanaKeyPath = path.join(BUILD_DIR, `${name}_verifying_key.bin`);
       const solidityVerifierPath = path.join(BUILD_DIR, `${name}_verifier.sol`);
 
       // Check if ZKey file exists (generated by setup script)
@@ -34,7 +65,11 @@ async function exportKeys() {
       fs.writeFileSync(vkeyPath, JSON.stringify(vKey, null, 2));
       console.log('Verification key exported successfully.');
 
-      // 2. Export Solidity verifier contract using snarkjs CLI
+      // 2. Encode it for the program's `initialize_verifying_key`
+      console.log(`Encoding verifying key for the Solana program to ${solanaKeyPath}...`);
+      fs.writeFileSync(solanaKeyPath, encodeVerifyingKey(vKey));
+
+      // 3. Export Solidity verifier contract using snarkjs CLI
       console.log(`Exporting Solidity verifier to ${solidityVerifierPath}...`);
       const verifierCmd = `snarkjs zkey export solidityverifier ${zkeyPath} ${solidityVerifierPath}`;
       execSync(verifierCmd, { stdio: 'inherit' });
@@ -10,25 +10,28 @@ const PHASE1_FILENAME = 'powersOfTau28_hez_final_14.ptau';
 const PHASE1_PATH = path.join(PTAU_DIR, PHASE1_FILENAME);
 
 // Circuits to setup (should match compile.js)
+// Larger circuits need a larger Powers of Tau file
 const circuits = [
-  { name: 'withdraw' }
+  { name: 'withdraw' },
+  { name: 'batch_update', ptau: 'powersOfTau28_hez_final_18.ptau' }
 ];
 
 async function performSetup() {
   try {
     console.log('Starting Groth16 setup...');
 
-    // Check if Powers of Tau file exists
-    if (!fs.existsSync(PHASE1_PATH)) {
-      throw new Error(`Powers of Tau file not found at ${PHASE1_PATH}. Please download it to the 'ptau' directory.`);
-    } else {
-       console.log(`Using Powers of Tau file: ${PHASE1_PATH}`);
-    }
-
     for (const circuit of circuits) {
       const { name } = circuit;
+      const ptauPath = circuit.ptau ? path.join(PTAU_DIR, circuit.ptau) : PHASE1_PATH;
       console.log(`\nSetting up ${name} circuit...`);
 
+      // Check if Powers of Tau file exists
+      if (!fs.existsSync(ptauPath)) {
+        throw new Error(`Powers of Tau file not found at ${ptauPath}. Please download it to the 'ptau' directory.`);
+      } else {
+         console.log(`Using Powers of Tau file: ${ptauPath}`);
+      }
+
       const r1csPath = path.join(BUILD_DIR, `${name}.r1cs`);
       const zkeyPath = path.join(BUILD_DIR, `${name}.zkey`);
 
@@ -39,7 +42,7 @@ async function performSetup() {
 
       // Generate zkey using snarkjs groth16 setup
       console.log(`Generating ${name}.zkey...`);
-      const setupCmd = `snarkjs groth16 setup ${r1csPath} ${PHASE1_PATH} ${zkeyPath}`;
+      const setupCmd = `snarkjs groth16 setup ${r1csPath} ${ptauPath} ${zkeyPath}`;
       execSync(setupCmd, { stdio: 'inherit' });
 
       console.log(`Setup for ${name} completed successfully! ZKey generated at ${zkeyPath}`);
@@ -0,0 +1,89 @@
+pragma circom 2.0.0;
+
+include "../node_modules/circomlib/circuits/poseidon.circom";
+include "../node_modules/circomlib/circuits/bitify.circom";
+include "../node_modules/circomlib/circuits/comparators.circom";
+include "./merkle.circom";
+
+// Batch root update circuit for SolanaVeil
+// Proves:
+// 1. Appending `batchSize` leaves at positions startIndex..startIndex+batchSize
+//    to the tree with root `oldRoot` yields the tree with root `newRoot`
+// 2. Each of those positions was empty (zero leaf) before the update
+// 3. The appended leaves are exactly the commitments whose Poseidon hash chain
+//    the program recorded on the tree account: chain = H(...H(H(0, c0), c1)..., cn)
+template BatchUpdate(levels, maxBatchSize) {
+    // Private inputs
+    signal input leaves[maxBatchSize];                // Commitments being appended
+    signal input pathElements[maxBatchSize][levels];  // Sibling path of each insertion position
+
+    // Public inputs
+    signal input oldRoot;      // Root currently stored on the tree account
+    signal input newRoot;      // Root after appending the batch
+    signal input hashChain;    // Hash chain of the batch recorded on-chain
+    signal input startIndex;   // Leaf index of the first commitment in the batch
+    signal input batchSize;    // Number of commitments in the batch (1..maxBatchSize)
+
+    // 1. Check the batch size is within bounds
+    // (range-check it first so the comparators below are sound)
+    component sizeBits = Num2Bits(16);
+    sizeBits.in <== batchSize;
+    component sizeNonZero = IsZero();
+    sizeNonZero.in <== batchSize;
+    sizeNonZero.out === 0;
+    component sizeCheck = LessEqThan(16);
+    sizeCheck.in[0] <== batchSize;
+    sizeCheck.in[1] <== maxBatchSize;
+    sizeCheck.out === 1;
+
+    signal roots[maxBatchSize + 1];
+    signal chains[maxBatchSize + 1];
+    roots[0] <== oldRoot;
+    chains[0] <== 0;
+
+    component isActive[maxBatchSize];
+    component indexBits[maxBatchSize];
+    component emptyRoots[maxBatchSize];
+    component filledRoots[maxBatchSize];
+    component chainHashers[maxBatchSize];
+
+    for (var i = 0; i < maxBatchSize; i++) {
+        // Leaves past batchSize are padding and leave the root and chain unchanged
+        isActive[i] = LessThan(16);
+        isActive[i].in[0] <== i;
+        isActive[i].in[1] <== batchSize;
+
+        // Insertion position; padding positions collapse to 0 so they stay in range
+        indexBits[i] = Num2Bits(levels);
+        indexBits[i].in <== (startIndex + i) * isActive[i].out;
+
+        // 2. The position is empty under the current root
+        emptyRoots[i] = MerkleRoot(levels);
+        emptyRoots[i].leaf <== 0;
+        filledRoots[i] = MerkleRoot(levels);
+        filledRoots[i].leaf <== leaves[i];
+        for (var j = 0; j < levels; j++) {
+            emptyRoots[i].pathElements[j] <== pathElements[i][j];
+            emptyRoots[i].pathIndices[j] <== indexBits[i].out[j];
+            filledRoots[i].pathElements[j] <== pathElements[i][j];
+            filledRoots[i].pathIndices[j] <== indexBits[i].out[j];
+        }
+        isActive[i].out * (emptyRoots[i].root - roots[i]) === 0;
+
+        // 1. Inserting the leaf with the same path gives the next root
+        roots[i + 1] <== roots[i] + isActive[i].out * (filledRoots[i].root - roots[i]);
+
+        // 3. Extend the hash chain
+        chainHashers[i] = Poseidon(2);
+        chainHashers[i].inputs[0] <== chains[i];
+        chainHashers[i].inputs[1] <== leaves[i];
+        chains[i + 1] <== chains[i] + isActive[i].out * (chainHashers[i].out - chains[i]);
+    }
+
+    newRoot === roots[maxBatchSize];
+    hashChain === chains[maxBatchSize];
+}
+
+// 20 levels to match the withdraw circuit; batches of up to 8 commitments
+// (must match ROOT_UPDATE_BATCH_SIZE in the program)
+component main { public [oldRoot, newRoot, hashChain, startIndex, batchSize] } = BatchUpdate(20, 8);
@@ -52,4 +52,34 @@ template Selector() {
     // If index is 1, sibling is left (out[0]) and current is right (out[1])
     out[0] <== (in[0] - in[1]) * (1 - index) + in[1];
     out[1] <== (in[1] - in[0]) * (1 - index) + in[0];
-}
\ No newline at end of file
+}
+// Compute the merkle root for a leaf at the position given by pathIndices
+// Unlike MerkleProof this does not constrain the result, so callers can
+// conditionally compare or select it
+template MerkleRoot(levels) {
+    signal input leaf;
+    signal input pathElements[levels];
+    signal input pathIndices[levels];
+    signal output root;
+
+    component selectors[levels];
+    component hashers[levels];
+
+    signal computedPath[levels+1];
+    computedPath[0] <== leaf;
+
+    for (var i = 0; i < levels; i++) {
+        selectors[i] = Selector();
+        selectors[i].in[0] <== computedPath[i];
+        selectors[i].in[1] <== pathElements[i];
+        selectors[i].index <== pathIndices[i];
+
+        hashers[i] = Poseidon(2);
+        hashers[i].inputs[0] <== selectors[i].out[0];
+        hashers[i].inputs[1] <== selectors[i].out[1];
+
+        computedPath[i+1] <== hashers[i].out;
+    }
+
+    root <== computedPath[levels];
+}
@@ -10,6 +10,8 @@ include "./merkle.circom";
 // 1. The prover knows a valid nullifier and secret for a commitment in the tree
 // 2. The commitment is in the merkle tree at the specified position
 // 3. The nullifier hash is correctly computed to prevent double spending
+// 4. The commitment's leaf index is below a bound old enough to withdraw
+// 5. The withdrawal callback (if any) is the one the prover chose
 template Withdraw(levels) {
     // Private inputs
     signal input nullifier;      // Secret nullifier
@@ -24,6 +26,10 @@ template Withdraw(levels) {
     signal input relayer;        // Address of relayer (for fees)
     signal input fee;            // Fee paid to relayer
     signal input denomination;   // Pool denomination amount
+    signal input leafIndexBound; // Leaves below this index have reached the pool's minimum deposit age
+    signal input callbackHash;   // Keccak hash of the withdrawal callback, top byte cleared (0 = no callback):
+                                 // keccak(domain || program || account count u32 LE
+                                 //   || (key || is_writable u8) per account || data length u32 LE || data)
     
     // Public outputs
     signal output nullifierHash; // Nullifier hash to prevent double spending
@@ -64,7 +70,23 @@ template Withdraw(levels) {
     lte.in[0] <== fee;
     lte.in[1] <== denomination;
     lte.out === 1;
+    
+    // 6. Check the deposit is old enough
+    // The leaf index is the little-endian bit decomposition of the path indices
+    component leafIndex = Bits2Num(levels);
+    for (var i = 0; i < levels; i++) {
+        leafIndex.in[i] <== pathIndices[i];
+    }
+    component ageCheck = LessThan(levels + 1);
+    ageCheck.in[0] <== leafIndex.out;
+    ageCheck.in[1] <== leafIndexBound;
+    ageCheck.out === 1;
+    
+    // 7. Bind the callback hash to the proof
+    // Squaring adds a constraint on the otherwise unused public input so it cannot be altered
+    signal callbackHashSquare;
+    callbackHashSquare <== callbackHash * callbackHash;
 }
 
 // Create a component with 20 levels (supports ~1 million deposits)
-component main { public [root, poolId, recipient, relayer, fee, denomination] } = Withdraw(20);
\ No newline at end of file
+component main { public [root, poolId, recipient, relayer, fee, denomination, leafIndexBound, callbackHash] } = Withdraw(20);
\ No newline at end of file
@@ -22,6 +22,7 @@ import "@openzeppelin/contracts/token/ERC20/extensions/IERC20Metadata.sol"; // F
 import "./interfaces/IWormholeTokenBridge.sol";
 import "./interfaces/IVerifier.sol";
 import "./libraries/MerkleTree.sol"; // Assuming library exists
+import "./libraries/VeilTransferPayload.sol";
 
 /**
  * @title SolanaVeilBridge
@@ -520,9 +521,11 @@ contract SolanaVeilBridge is Ownable, ReentrancyGuard, Pausable, ILayerZeroRecei
         require(!processedWormholeMessages[deliveryHash], "Wormhole message already processed");
         processedWormholeMessages[deliveryHash] = true;
 
-        // Assuming the payload directly contains the commitment generated on Solana
-        // A real implementation might require more complex payload parsing (e.g., amount, token, etc.)
-        bytes32 commitment = bytes32(payload); // Adjust parsing based on actual Solana payload structure
+        // Decode the transfer emitted by the Solana program's `bridge::payload`
+        VeilTransferPayload.Payload memory transfer = VeilTransferPayload.decode(payload);
+        require(transfer.sourceChain == sourceChain, "Payload source chain mismatch");
+        require(transfer.destinationChain == wormhole.chainId(), "Payload not meant for this chain");
+        bytes32 commitment = transfer.commitment;
 
         // TODO: Add the commitment to an Ethereum-side Merkle tree or equivalent state
         // This commitment will be used later when the user withdraws privately on Ethereum using `receiveFromSolana`.
@@ -598,7 +601,8 @@ contract SolanaVeilBridge is Ownable, ReentrancyGuard, Pausable, ILayerZeroRecei
 
     /**
      * @notice Bridge tokens from EVM to Solana with privacy.
-     * @dev Locks tokens in this contract and emits a Wormhole message containing a commitment.
+     * @dev Locks tokens in this contract and emits a Wormhole message carrying a commitment,
+     *      encoded as a `VeilTransferPayload` the Solana program decodes.
      * @param token The address of the ERC20 token to bridge.
      * @param amount The amount of tokens to bridge.
      * @param solanaRecipientAddress The Solana recipient address (bytes32 format).
@@ -636,8 +640,17 @@ contract SolanaVeilBridge is Ownable, ReentrancyGuard, Pausable, ILayerZeroRecei
         uint256 cost = wormhole.messageFee();
         require(msg.value >= cost, "Insufficient fee for Wormhole message");
 
-        // The payload sent to Solana should contain the commitment.
-        bytes memory messagePayload = abi.encode(commitment);
+        // The payload is decoded by the Solana program's `bridge::payload`
+        require(amount <= type(uint64).max, "Amount exceeds payload range");
+        bytes memory messagePayload = VeilTransferPayload.encode(VeilTransferPayload.Payload({
+            amount: uint64(amount),
+            token: bytes32(uint256(uint160(token))), // Wormhole format
+            sourceChain: wormhole.chainId(),
+            destinationChain: WH_CHAIN_ID_SOLANA,
+            recipient: solanaRecipientAddress,
+            commitment: commitment,
+            nonce: nonce
+        }));
 
         uint64 sequence = wormhole.publishMessage{value: cost}(
             nonce, // Use provided nonce for Wormhole message
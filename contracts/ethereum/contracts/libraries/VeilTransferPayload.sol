@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import { BytesLib } from "@layerzerolabs/lz-evm-sdk-v2/contracts/vendor/BytesLib.sol";
+
+/**
+ * @title VeilTransferPayload
+ * @notice Decodes the Wormhole payload of a SolanaVeil cross-chain transfer
+ * @dev Mirrors `bridge::payload` in the Solana program. Version 1 is packed big-endian:
+ *      version uint8 | amount uint64 | token bytes32 | sourceChain uint16 | destinationChain uint16
+ *      | recipient bytes32 | commitment bytes32 | nonce uint32 (113 bytes)
+ */
+library VeilTransferPayload {
+    using BytesLib for bytes;
+
+    /// Version byte of the current layout (the bridge's original payload id)
+    uint8 internal constant VERSION_1 = 100;
+
+    /// Encoded length of a version 1 payload
+    uint256 internal constant V1_LENGTH = 113;
+
+    struct Payload {
+        uint64 amount; // Net amount in the token's base units
+        bytes32 token; // Token on the source chain, Wormhole format
+        uint16 sourceChain; // Wormhole chain IDs
+        uint16 destinationChain;
+        bytes32 recipient; // Bridge contract receiving the message
+        bytes32 commitment; // Privacy commitment for the destination tree
+        uint32 nonce;
+    }
+
+    /**
+     * @notice Encodes a payload as the current version.
+     * @param payload The transfer to encode.
+     * @return The packed payload bytes.
+     */
+    function encode(Payload memory payload) internal pure returns (bytes memory) {
+        return abi.encodePacked(
+            VERSION_1,
+            payload.amount,
+            payload.token,
+            payload.sourceChain,
+            payload.destinationChain,
+            payload.recipient,
+            payload.commitment,
+            payload.nonce
+        );
+    }
+
+    /**
+     * @notice Decodes a payload, rejecting unknown versions and any other length.
+     * @param data The payload bytes of a Wormhole message.
+     * @return payload The decoded transfer.
+     */
+    function decode(bytes memory data) internal pure returns (Payload memory payload) {
+        require(data.length > 0, "Invalid payload length");
+        require(data.toUint8(0) == VERSION_1, "Unsupported payload version");
+        require(data.length == V1_LENGTH, "Invalid payload length");
+
+        payload.amount = data.toUint64(1);
+        payload.token = data.toBytes32(9);
+        payload.sourceChain = data.toUint16(41);
+        payload.destinationChain = data.toUint16(43);
+        payload.recipient = data.toBytes32(45);
+        payload.commitment = data.toBytes32(77);
+        payload.nonce = data.toUint32(109);
+    }
+}
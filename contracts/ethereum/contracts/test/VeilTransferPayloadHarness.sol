@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import "../libraries/VeilTransferPayload.sol";
+
+/**
+ * @title VeilTransferPayloadHarness
+ * @notice Exposes `VeilTransferPayload` to tests. Not for deployment.
+ */
+contract VeilTransferPayloadHarness {
+    function encode(VeilTransferPayload.Payload memory payload) external pure returns (bytes memory) {
+        return VeilTransferPayload.encode(payload);
+    }
+
+    function decode(bytes memory data) external pure returns (VeilTransferPayload.Payload memory) {
+        return VeilTransferPayload.decode(data);
+    }
+}
@@ -0,0 +1,50 @@
+import { expect } from "chai";
+import { ethers } from "hardhat";
+import { readFileSync } from "fs";
+import { join } from "path";
+
+// EVM -> Solana transfer the Solana program's `bridge::payload` tests decode from the same fixture
+const FIXTURE = "0x" + readFileSync(join(__dirname, "fixtures", "evm-to-solana-payload.hex"), "utf8").trim();
+
+const WH_CHAIN_ID_SOLANA = 1;
+const WH_CHAIN_ID_ETHEREUM = 2;
+
+const transfer = {
+  amount: 1_500_000n,
+  token: ethers.zeroPadValue("0x" + "11".repeat(20), 32),
+  sourceChain: WH_CHAIN_ID_ETHEREUM,
+  destinationChain: WH_CHAIN_ID_SOLANA,
+  recipient: "0x" + "22".repeat(32),
+  commitment: "0x" + "33".repeat(32),
+  nonce: 7,
+};
+
+describe("VeilTransferPayload", () => {
+  async function harness() {
+    const factory = await ethers.getContractFactory("VeilTransferPayloadHarness");
+    return factory.deploy();
+  }
+
+  it("encodes EVM -> Solana transfers as the Solana program decodes them", async () => {
+    const payload = await harness();
+    expect(await payload.encode(transfer)).to.equal(FIXTURE);
+  });
+
+  it("decodes the fixture back into the transfer", async () => {
+    const payload = await harness();
+    const decoded = await payload.decode(FIXTURE);
+    expect(decoded.amount).to.equal(transfer.amount);
+    expect(decoded.token).to.equal(transfer.token);
+    expect(decoded.sourceChain).to.equal(BigInt(transfer.sourceChain));
+    expect(decoded.destinationChain).to.equal(BigInt(transfer.destinationChain));
+    expect(decoded.recipient).to.equal(transfer.recipient);
+    expect(decoded.commitment).to.equal(transfer.commitment);
+    expect(decoded.nonce).to.equal(BigInt(transfer.nonce));
+  });
+
+  it("rejects the legacy bare-commitment payload", async () => {
+    const payload = await harness();
+    const legacy = ethers.AbiCoder.defaultAbiCoder().encode(["bytes32"], [transfer.commitment]);
+    await expect(payload.decode(legacy)).to.be.revertedWith("Unsupported payload version");
+  });
+});
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+declare_id!("84nijRgFurbEUNQu994kBVeQi2Ww1nemRADwLtADAj5y");
+
+/// Size of the mocked price accounts; covers every field up to the aggregate price
+pub const PRICE_ACCOUNT_LEN: usize = 240;
+
+/// Mock Pyth oracle for SolanaVeil SOL relayer fees.
+///
+/// Writes Pyth v2 price accounts with whatever price, confidence and publish time a
+/// test asks for, so fee conversion can be exercised against fresh, stale and
+/// uncertain prices on a local validator. Price accounts are created by the test
+/// with this program as owner and `PRICE_ACCOUNT_LEN` bytes of space.
+#[program]
+pub mod mock_pyth {
+    use super::*;
+
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i64,
+        conf: u64,
+        exponent: i32,
+        publish_time: i64,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.price_account.try_borrow_mut_data()?;
+        require!(data.len() >= PRICE_ACCOUNT_LEN, MockPythError::AccountTooSmall);
+
+        let mut write = |at: usize, bytes: &[u8]| data[at..at + bytes.len()].copy_from_slice(bytes);
+        write(0, &0xa1b2c3d4u32.to_le_bytes()); // magic
+        write(4, &2u32.to_le_bytes()); // version
+        write(8, &3u32.to_le_bytes()); // account type: price
+        write(12, &(PRICE_ACCOUNT_LEN as u32).to_le_bytes()); // size
+        write(20, &exponent.to_le_bytes());
+        write(96, &publish_time.to_le_bytes());
+        write(208, &price.to_le_bytes()); // aggregate price
+        write(216, &conf.to_le_bytes()); // aggregate confidence
+        write(224, &1u32.to_le_bytes()); // aggregate status: trading
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    /// CHECK: Raw Pyth-layout price account owned by this program
+    #[account(mut, owner = crate::ID)]
+    pub price_account: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum MockPythError {
+    #[msg("Price account is too small for a Pyth price")]
+    AccountTooSmall,
+}
@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+
+declare_id!("FGG3HsXwJZAPaEH1RpK8kvg2ABqHhBGzAMTcA8rMCzKt");
+
+/// Seed prefix of the SolanaVeil PDA that signs callbacks for a pool
+pub const CALLBACK_AUTHORITY_SEED: &[u8] = b"callback_authority";
+
+/// Largest callback payload the receipt records
+pub const MAX_RECORDED_DATA_LEN: usize = 256;
+
+/// Mock recipient program for SolanaVeil withdrawal callbacks.
+///
+/// Whitelisted in tests, it records every `on_veil_withdrawal` call in a receipt
+/// account so tests can check what the pool forwarded, and can be told to reject
+/// calls to check that a failing callback aborts the withdrawal.
+#[program]
+pub mod mock_withdraw_receiver {
+    use super::*;
+
+    pub fn initialize_receipt(ctx: Context<InitializeReceipt>, veil_program: Pubkey) -> Result<()> {
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.authority = ctx.accounts.authority.key();
+        receipt.veil_program = veil_program;
+        receipt.reject = false;
+        receipt.calls = 0;
+        receipt.bump = ctx.bumps.receipt;
+
+        Ok(())
+    }
+
+    pub fn set_reject(ctx: Context<UpdateReceipt>, reject: bool) -> Result<()> {
+        ctx.accounts.receipt.reject = reject;
+
+        Ok(())
+    }
+
+    /// Callback entrypoint invoked by the SolanaVeil program via CPI
+    pub fn on_veil_withdrawal(
+        ctx: Context<OnVeilWithdrawal>,
+        nullifier_hash: [u8; 32],
+        mint: Pubkey,
+        amount: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let receipt = &mut ctx.accounts.receipt;
+
+        // Only pools of the configured SolanaVeil program may call in, through the
+        // callback authority the program derives for the pool
+        require_keys_eq!(
+            *ctx.accounts.pool.owner,
+            receipt.veil_program,
+            ReceiverError::UnknownCaller
+        );
+        let (callback_authority, _) = Pubkey::find_program_address(
+            &[CALLBACK_AUTHORITY_SEED, ctx.accounts.pool.key().as_ref()],
+            &receipt.veil_program,
+        );
+        require_keys_eq!(
+            ctx.accounts.callback_authority.key(),
+            callback_authority,
+            ReceiverError::UnknownCaller
+        );
+        require!(!receipt.reject, ReceiverError::Rejected);
+        require!(data.len() <= MAX_RECORDED_DATA_LEN, ReceiverError::DataTooLong);
+
+        receipt.calls += 1;
+        receipt.last_pool = ctx.accounts.pool.key();
+        receipt.last_recipient = ctx.accounts.recipient.key();
+        receipt.last_nullifier_hash = nullifier_hash;
+        receipt.last_mint = mint;
+        receipt.last_amount = amount;
+        receipt.last_data = data;
+
+        Ok(())
+    }
+}
+
+/// Record of the calls received, a singleton PDA
+#[account]
+pub struct Receipt {
+    /// Authority that can toggle `reject`
+    pub authority: Pubkey,
+
+    /// SolanaVeil program whose pools may call in
+    pub veil_program: Pubkey,
+
+    /// Fail every callback while set
+    pub reject: bool,
+
+    /// Number of callbacks received
+    pub calls: u64,
+
+    /// Pool that made the last call
+    pub last_pool: Pubkey,
+
+    /// Recipient of the last withdrawal
+    pub last_recipient: Pubkey,
+
+    /// Nullifier hash of the last withdrawal
+    pub last_nullifier_hash: [u8; 32],
+
+    /// Mint of the last withdrawal (zeros for native SOL pools)
+    pub last_mint: Pubkey,
+
+    /// Amount paid to the recipient by the last withdrawal
+    pub last_amount: u64,
+
+    /// Payload of the last callback
+    pub last_data: Vec<u8>,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Receipt {
+    /// Size of the Receipt struct for space allocation
+    pub const LEN: usize =
+        32 +                            // authority: Pubkey
+        32 +                            // veil_program: Pubkey
+        1 +                             // reject: bool
+        8 +                             // calls: u64
+        32 +                            // last_pool: Pubkey
+        32 +                            // last_recipient: Pubkey
+        32 +                            // last_nullifier_hash: [u8; 32]
+        32 +                            // last_mint: Pubkey
+        8 +                             // last_amount: u64
+        4 + MAX_RECORDED_DATA_LEN +     // last_data: Vec<u8>
+        1;                              // bump: u8
+}
+
+#[derive(Accounts)]
+pub struct InitializeReceipt<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Receipt::LEN,
+        seeds = [b"receipt"],
+        bump
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateReceipt<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt"],
+        bump = receipt.bump,
+        has_one = authority @ ReceiverError::Unauthorized,
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
+
+#[derive(Accounts)]
+pub struct OnVeilWithdrawal<'info> {
+    /// Callback authority PDA of `pool`, signing for the SolanaVeil program
+    pub callback_authority: Signer<'info>,
+
+    /// CHECK: Pool the withdrawal came from; checked against `callback_authority`
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Withdrawal recipient, only recorded
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt"],
+        bump = receipt.bump,
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
+
+#[error_code]
+pub enum ReceiverError {
+    #[msg("Caller is not a pool of the configured SolanaVeil program")]
+    UnknownCaller,
+    #[msg("Receiver is set to reject callbacks")]
+    Rejected,
+    #[msg("Callback data is too long to record")]
+    DataTooLong,
+    #[msg("Unauthorized access")]
+    Unauthorized,
+}
@@ -0,0 +1,5 @@
+//! Wire formats shared with the SolanaVeil bridge contracts on other chains, and the
+//! Wormhole Core Bridge interface that carries them.
+
+pub mod payload;
+pub mod wormhole;
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use crate::errors::ErrorCode;
+
+/// Version byte of the current payload layout. It keeps the custom Wormhole payload
+/// id (100) the bridge used before payloads were versioned, so messages already
+/// emitted still decode.
+pub const PAYLOAD_VERSION_1: u8 = 100;
+
+/// Encoded length of a version 1 payload
+pub const PAYLOAD_V1_LEN: usize = 113;
+
+// Offsets into a version 1 payload; `SolanaVeilBridge.sol` reads the same ones
+const AMOUNT_OFFSET: usize = 1;
+const TOKEN_OFFSET: usize = 9;
+const SOURCE_CHAIN_OFFSET: usize = 41;
+const DESTINATION_CHAIN_OFFSET: usize = 43;
+const RECIPIENT_OFFSET: usize = 45;
+const COMMITMENT_OFFSET: usize = 77;
+const NONCE_OFFSET: usize = 109;
+
+/// Wormhole message payload of a SolanaVeil cross-chain transfer.
+///
+/// Version 1 is packed big-endian, matching Solidity's `abi.encodePacked`:
+///
+/// ```text
+/// version u8 | amount u64 | token bytes32 | source chain u16 | destination chain u16
+///   | recipient bytes32 | commitment bytes32 | nonce u32
+/// ```
+///
+/// Addresses are in Wormhole's 32-byte format, so `token` is a mint on Solana and a
+/// left-padded ERC-20 address on EVM chains.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VeilTransferPayload {
+    /// Amount bridged after fees, in the token's base units
+    pub amount: u64,
+
+    /// Token on the source chain
+    pub token: [u8; 32],
+
+    /// Wormhole chain ids
+    pub source_chain: u16,
+    pub destination_chain: u16,
+
+    /// Bridge contract receiving the message on the destination chain
+    pub recipient: [u8; 32],
+
+    /// Privacy commitment inserted into the destination chain's tree
+    pub commitment: [u8; 32],
+
+    pub nonce: u32,
+}
+
+impl VeilTransferPayload {
+    /// Encode as the current payload version
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(PAYLOAD_V1_LEN);
+        data.push(PAYLOAD_VERSION_1);
+        data.extend_from_slice(&self.amount.to_be_bytes());
+        data.extend_from_slice(&self.token);
+        data.extend_from_slice(&self.source_chain.to_be_bytes());
+        data.extend_from_slice(&self.destination_chain.to_be_bytes());
+        data.extend_from_slice(&self.recipient);
+        data.extend_from_slice(&self.commitment);
+        data.extend_from_slice(&self.nonce.to_be_bytes());
+        data
+    }
+
+    /// Decode a payload, rejecting unknown versions and any length other than the
+    /// version's exact length
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let version = *data.first().ok_or(ErrorCode::InvalidPayloadLength)?;
+        if version != PAYLOAD_VERSION_1 {
+            return Err(ErrorCode::UnsupportedPayloadVersion.into());
+        }
+        if data.len() != PAYLOAD_V1_LEN {
+            return Err(ErrorCode::InvalidPayloadLength.into());
+        }
+
+        // In bounds: the length is checked above
+        let bytes = |at: usize| -> [u8; 32] { data[at..at + 32].try_into().unwrap() };
+        let read_u16 = |at: usize| u16::from_be_bytes(data[at..at + 2].try_into().unwrap());
+
+        Ok(Self {
+            amount: u64::from_be_bytes(data[AMOUNT_OFFSET..TOKEN_OFFSET].try_into().unwrap()),
+            token: bytes(TOKEN_OFFSET),
+            source_chain: read_u16(SOURCE_CHAIN_OFFSET),
+            destination_chain: read_u16(DESTINATION_CHAIN_OFFSET),
+            recipient: bytes(RECIPIENT_OFFSET),
+            commitment: bytes(COMMITMENT_OFFSET),
+            nonce: u32::from_be_bytes(data[NONCE_OFFSET..PAYLOAD_V1_LEN].try_into().unwrap()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> VeilTransferPayload {
+        VeilTransferPayload {
+            amount: 1_500_000,
+            token: [0x11; 32],
+            source_chain: 1,
+            destination_chain: 2,
+            recipient: [0x22; 32],
+            commitment: [0x33; 32],
+            nonce: 7,
+        }
+    }
+
+    fn error(result: Result<VeilTransferPayload>) -> Error {
+        result.unwrap_err()
+    }
+
+    #[test]
+    fn round_trips() {
+        let encoded = payload().encode();
+        assert_eq!(encoded.len(), PAYLOAD_V1_LEN);
+        assert_eq!(VeilTransferPayload::decode(&encoded).unwrap(), payload());
+
+        let extremes = VeilTransferPayload {
+            amount: u64::MAX,
+            source_chain: u16::MAX,
+            destination_chain: 0,
+            nonce: u32::MAX,
+            ..payload()
+        };
+        assert_eq!(VeilTransferPayload::decode(&extremes.encode()).unwrap(), extremes);
+    }
+
+    #[test]
+    fn matches_the_packed_layout() {
+        // What `abi.encodePacked(uint8, uint64, bytes32, uint16, uint16, bytes32, bytes32, uint32)`
+        // produces for the same fields
+        let mut expected = vec![100];
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0x16, 0xe3, 0x60]);
+        expected.extend_from_slice(&[0x11; 32]);
+        expected.extend_from_slice(&[0, 1, 0, 2]);
+        expected.extend_from_slice(&[0x22; 32]);
+        expected.extend_from_slice(&[0x33; 32]);
+        expected.extend_from_slice(&[0, 0, 0, 7]);
+
+        assert_eq!(payload().encode(), expected);
+    }
+
+    #[test]
+    fn decodes_evm_payloads() {
+        // Shared with the EVM bridge's `VeilTransferPayload` tests, which encode the same transfer
+        let fixture = include_str!("../../../../ethereum/test/fixtures/evm-to-solana-payload.hex").trim();
+        let data: Vec<u8> = (0..fixture.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&fixture[i..i + 2], 16).unwrap())
+            .collect();
+
+        let mut token = [0u8; 32];
+        token[12..].fill(0x11);
+        assert_eq!(
+            VeilTransferPayload::decode(&data).unwrap(),
+            VeilTransferPayload {
+                source_chain: 2,
+                destination_chain: crate::bridge::wormhole::CHAIN_ID_SOLANA,
+                token,
+                ..payload()
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_payloads() {
+        let encoded = payload().encode();
+
+        assert_eq!(
+            error(VeilTransferPayload::decode(&[])),
+            ErrorCode::InvalidPayloadLength.into()
+        );
+        assert_eq!(
+            error(VeilTransferPayload::decode(&encoded[..PAYLOAD_V1_LEN - 1])),
+            ErrorCode::InvalidPayloadLength.into()
+        );
+        assert_eq!(
+            error(VeilTransferPayload::decode(&[PAYLOAD_VERSION_1])),
+            ErrorCode::InvalidPayloadLength.into()
+        );
+
+        let mut trailing = encoded.clone();
+        trailing.push(0);
+        assert_eq!(
+            error(VeilTransferPayload::decode(&trailing)),
+            ErrorCode::InvalidPayloadLength.into()
+        );
+
+        let mut unknown_version = encoded;
+        unknown_version[0] = 1;
+        assert_eq!(
+            error(VeilTransferPayload::decode(&unknown_version)),
+            ErrorCode::UnsupportedPayloadVersion.into()
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+use crate::errors::ErrorCode;
+
+/// Wormhole Core Bridge program
+pub mod program {
+    use anchor_lang::prelude::*;
+
+    declare_id!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+}
+
+/// Wormhole chain id of Solana
+pub const CHAIN_ID_SOLANA: u16 = 1;
+
+/// Core Bridge instruction index of `post_message`
+const POST_MESSAGE_INSTRUCTION: u8 = 1;
+
+/// Account discriminator of a posted VAA
+const POSTED_VAA_MAGIC: &[u8; 3] = b"vaa";
+
+/// Offset of the message fee in the Core Bridge's `Bridge` account:
+/// guardian set index u32 | last lamports u64 | guardian set expiration u32 | fee u64
+const BRIDGE_FEE_OFFSET: usize = 16;
+
+/// Accounts of the Core Bridge `post_message` instruction, in instruction order
+#[derive(Accounts)]
+pub struct PostMessage<'info> {
+    /// CHECK: Core Bridge `Bridge` account
+    #[account(mut)]
+    pub config: AccountInfo<'info>,
+    /// CHECK: Message account written by the Core Bridge
+    #[account(mut, signer)]
+    pub message: AccountInfo<'info>,
+    /// CHECK: Emitter PDA of the calling program
+    #[account(signer)]
+    pub emitter: AccountInfo<'info>,
+    /// CHECK: Core Bridge sequence tracker of `emitter`
+    #[account(mut)]
+    pub sequence: AccountInfo<'info>,
+    /// CHECK: Pays for the message account and the message fee
+    #[account(mut, signer)]
+    pub payer: AccountInfo<'info>,
+    /// CHECK: Core Bridge fee collector
+    #[account(mut)]
+    pub fee_collector: AccountInfo<'info>,
+    /// CHECK: Clock sysvar
+    pub clock: AccountInfo<'info>,
+    /// CHECK: Rent sysvar
+    pub rent: AccountInfo<'info>,
+    /// CHECK: System program
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(AnchorSerialize)]
+struct PostMessageData {
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: u8,
+}
+
+/// Publish `payload` through the Core Bridge, paying its message fee from `payer`.
+/// Returns the sequence number the message was published with.
+pub fn post_message<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, PostMessage<'info>>,
+    nonce: u32,
+    payload: Vec<u8>,
+    consistency_level: u8,
+) -> Result<u64> {
+    // The tracker holds the next sequence; it does not exist before the emitter's first message
+    let sequence = {
+        let data = ctx.accounts.sequence.try_borrow_data()?;
+        match data.get(..8) {
+            Some(bytes) => u64::from_le_bytes(bytes.try_into().unwrap()),
+            None => 0,
+        }
+    };
+
+    let fee = {
+        let data = ctx.accounts.config.try_borrow_data()?;
+        let bytes = data.get(BRIDGE_FEE_OFFSET..BRIDGE_FEE_OFFSET + 8)
+            .ok_or(ErrorCode::InvalidWormholeMessage)?;
+        u64::from_le_bytes(bytes.try_into().unwrap())
+    };
+    if fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.clone(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.clone(),
+                    to: ctx.accounts.fee_collector.clone(),
+                },
+            ),
+            fee,
+        )?;
+    }
+
+    let mut data = vec![POST_MESSAGE_INSTRUCTION];
+    PostMessageData { nonce, payload, consistency_level }.serialize(&mut data)?;
+    let instruction = Instruction {
+        program_id: ctx.program.key(),
+        accounts: ctx.to_account_metas(None),
+        data,
+    };
+    invoke_signed(&instruction, &ctx.to_account_infos(), ctx.signer_seeds)?;
+
+    Ok(sequence)
+}
+
+/// Message of a VAA the Core Bridge verified and posted
+pub struct PostedVaa {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub payload: Vec<u8>,
+}
+
+/// Body of a posted VAA account after its magic
+#[derive(AnchorDeserialize)]
+struct PostedVaaData {
+    _vaa_version: u8,
+    _consistency_level: u8,
+    _vaa_time: u32,
+    _vaa_signature_account: Pubkey,
+    _submission_time: u32,
+    _nonce: u32,
+    sequence: u64,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+    payload: Vec<u8>,
+}
+
+/// Read a posted VAA account, which must be owned by `wormhole_program`
+pub fn parse_vaa(account: &AccountInfo, wormhole_program: &Pubkey) -> Result<PostedVaa> {
+    require_keys_eq!(*account.owner, *wormhole_program, ErrorCode::InvalidWormholeMessage);
+
+    let data = account.try_borrow_data()?;
+    let body = data.strip_prefix(POSTED_VAA_MAGIC.as_slice())
+        .ok_or(ErrorCode::InvalidWormholeMessage)?;
+    let vaa = PostedVaaData::deserialize(&mut &body[..])
+        .map_err(|_| ErrorCode::InvalidWormholeMessage)?;
+
+    Ok(PostedVaa {
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        payload: vaa.payload,
+    })
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::program::invoke_signed;
+
+/// Name of the instruction every recipient program must expose.
+/// It is called as `on_veil_withdrawal(nullifier_hash: [u8; 32], mint: Pubkey, amount: u64, data: Vec<u8>)`
+/// with the pool's callback authority (as a signer), the pool and the recipient as its first
+/// three accounts, followed by the forwarded callback accounts. Recipient programs check the
+/// call really comes from a SolanaVeil pool by deriving `[CALLBACK_AUTHORITY_SEED, pool]`
+/// under the SolanaVeil program id and comparing it with the signer.
+const ON_WITHDRAWAL_INSTRUCTION: &[u8] = b"global:on_veil_withdrawal";
+
+/// Seed prefix of the data-less PDA `[b"callback_authority", pool]` that signs withdrawal
+/// callbacks. It is used for nothing else, so a recipient program never receives the
+/// signer privilege of the pool, which owns the pool's funds.
+pub const CALLBACK_AUTHORITY_SEED: &[u8] = b"callback_authority";
+
+/// Largest memo / instruction payload a withdrawal callback may carry
+pub const MAX_CALLBACK_DATA_LEN: usize = 256;
+
+/// Most accounts a withdrawal callback may forward to the recipient program
+pub const MAX_CALLBACK_ACCOUNTS: usize = 16;
+
+/// Domain tag hashed ahead of every callback so the hash cannot collide with other keccak preimages
+pub const CALLBACK_HASH_DOMAIN: &[u8] = b"solana-veil:withdraw-callback:v1";
+
+/// `callbackHash` public input for withdrawals without a callback
+pub const NO_CALLBACK_HASH: [u8; 32] = [0; 32];
+
+/// Callback to run on a whitelisted recipient program after a withdrawal pays out
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawCallback {
+    /// Memo or instruction payload passed through to the recipient program
+    pub data: Vec<u8>,
+
+    /// Number of trailing remaining accounts forwarded to the recipient program;
+    /// any accounts before them are screening policy accounts
+    pub account_count: u8,
+}
+
+/// Hash of a callback, bound into the withdrawal proof as the `callbackHash` public input
+/// so a relayer cannot change the target program, forwarded accounts, their writability
+/// or the payload.
+///
+/// The preimage is length-prefixed so no two callbacks share one:
+///
+/// ```text
+/// CALLBACK_HASH_DOMAIN | program | account count u32 LE
+///   | (account key | is_writable u8) per account | data length u32 LE | data
+/// ```
+///
+/// The top byte is cleared so the hash is always a canonical BN254 field element.
+pub fn callback_hash(
+    callback_program: &Pubkey,
+    callback_accounts: &[AccountInfo],
+    data: &[u8],
+) -> [u8; 32] {
+    let account_count = (callback_accounts.len() as u32).to_le_bytes();
+    let data_len = (data.len() as u32).to_le_bytes();
+
+    let mut preimage: Vec<&[u8]> = Vec::with_capacity(2 * callback_accounts.len() + 5);
+    preimage.push(CALLBACK_HASH_DOMAIN);
+    preimage.push(callback_program.as_ref());
+    preimage.push(&account_count);
+    for account in callback_accounts {
+        preimage.push(account.key.as_ref());
+        preimage.push(if account.is_writable { &[1] } else { &[0] });
+    }
+    preimage.push(&data_len);
+    preimage.push(data);
+
+    let mut callback_hash = keccak::hashv(&preimage).to_bytes();
+    callback_hash[0] = 0;
+    callback_hash
+}
+
+/// CPI into a whitelisted recipient program once a withdrawal has paid out.
+///
+/// Forwarded accounts keep their writability but never their signer privilege, so the
+/// transaction's signers (e.g. the relayer) cannot be used by the recipient program.
+/// Any error returned by the recipient program aborts the withdrawal.
+#[allow(clippy::too_many_arguments)]
+pub fn invoke_withdraw_callback<'info>(
+    callback_program: &AccountInfo<'info>,
+    callback_authority: &AccountInfo<'info>,
+    callback_authority_seeds: &[&[u8]],
+    pool: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    callback_accounts: &[AccountInfo<'info>],
+    nullifier_hash: [u8; 32],
+    mint: Pubkey,
+    amount: u64,
+    data: &[u8],
+) -> Result<()> {
+    let mut instruction_data = Vec::with_capacity(8 + 32 + 32 + 8 + 4 + data.len());
+    instruction_data.extend_from_slice(&hash(ON_WITHDRAWAL_INSTRUCTION).to_bytes()[..8]);
+    instruction_data.extend_from_slice(&nullifier_hash);
+    instruction_data.extend_from_slice(mint.as_ref());
+    instruction_data.extend_from_slice(&amount.to_le_bytes());
+    instruction_data.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    instruction_data.extend_from_slice(data);
+
+    let mut accounts = Vec::with_capacity(callback_accounts.len() + 3);
+    accounts.push(AccountMeta::new_readonly(callback_authority.key(), true));
+    accounts.push(AccountMeta::new_readonly(pool.key(), false));
+    accounts.push(AccountMeta::new(recipient.key(), false));
+    for account in callback_accounts {
+        if account.is_writable {
+            accounts.push(AccountMeta::new(account.key(), false));
+        } else {
+            accounts.push(AccountMeta::new_readonly(account.key(), false));
+        }
+    }
+
+    let instruction = Instruction {
+        program_id: callback_program.key(),
+        accounts,
+        data: instruction_data,
+    };
+
+    let mut account_infos = Vec::with_capacity(callback_accounts.len() + 4);
+    account_infos.push(callback_authority.clone());
+    account_infos.push(pool.clone());
+    account_infos.push(recipient.clone());
+    account_infos.extend_from_slice(callback_accounts);
+    account_infos.push(callback_program.clone());
+
+    msg!("Withdrawal callback into {} for {}", callback_program.key(), recipient.key());
+    invoke_signed(&instruction, &account_infos, &[callback_authority_seeds])?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binds_the_split_between_accounts_and_data() {
+        let program = Pubkey::new_unique();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut first_lamports, mut second_lamports) = (0, 0);
+        let (mut first_data, mut second_data) = ([], []);
+        let owner = Pubkey::default();
+        let accounts = [
+            AccountInfo::new(&first, false, false, &mut first_lamports, &mut first_data, &owner, false, 0),
+            AccountInfo::new(&second, false, false, &mut second_lamports, &mut second_data, &owner, false, 0),
+        ];
+        let data = b"memo";
+
+        // Moving the second account's key (and its flag) into the payload keeps the bytes
+        // after the program the same, but not the hash
+        let mut shifted_data = second.to_bytes().to_vec();
+        shifted_data.push(0);
+        shifted_data.extend_from_slice(data);
+
+        assert_ne!(
+            callback_hash(&program, &accounts, data),
+            callback_hash(&program, &accounts[..1], &shifted_data)
+        );
+    }
+
+    #[test]
+    fn binds_account_writability() {
+        let program = Pubkey::new_unique();
+        let key = Pubkey::new_unique();
+        let (mut readonly_lamports, mut writable_lamports) = (0, 0);
+        let (mut readonly_data, mut writable_data) = ([], []);
+        let owner = Pubkey::default();
+        let readonly = AccountInfo::new(&key, false, false, &mut readonly_lamports, &mut readonly_data, &owner, false, 0);
+        let writable = AccountInfo::new(&key, false, true, &mut writable_lamports, &mut writable_data, &owner, false, 0);
+
+        assert_ne!(
+            callback_hash(&program, &[readonly], b"memo"),
+            callback_hash(&program, &[writable], b"memo")
+        );
+    }
+}
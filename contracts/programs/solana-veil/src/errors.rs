@@ -64,6 +64,168 @@ pub enum SolanaVeilError {
     
     #[msg("Zero-knowledge proof verification failed")]
     ZkProofVerificationFailed,
+    
+    #[msg("Screening program does not match pool configuration")]
+    InvalidScreeningProgram,
+    
+    #[msg("Deposit has not reached the pool's minimum age")]
+    DepositTooRecent,
+    
+    #[msg("Invalid treasury vault")]
+    InvalidTreasuryVault,
+    
+    #[msg("Mint uses a token extension that is not supported by pools")]
+    UnsupportedMintExtension,
+    
+    #[msg("Pool vault did not receive the exact denomination")]
+    DepositAmountMismatch,
+    
+    #[msg("Invalid pool vault")]
+    InvalidVault,
+    
+    #[msg("Pool registry page is full")]
+    RegistryPageFull,
+    
+    #[msg("Registry page does not list this pool")]
+    InvalidRegistryPage,
+    
+    #[msg("Deposit would exceed the pool's deposit cap")]
+    DepositCapExceeded,
+    
+    #[msg("Withdrawal would exceed the pool's rate limit for this window")]
+    WithdrawalLimitExceeded,
+    
+    #[msg("Invalid pool configuration")]
+    InvalidPoolConfig,
+    
+    #[msg("Deposits are paused for this pool")]
+    DepositsPaused,
+    
+    #[msg("Withdrawals are paused for this pool")]
+    WithdrawalsPaused,
+    
+    #[msg("Pause flags contain bits not valid for this account")]
+    InvalidPauseFlags,
+    
+    #[msg("Invalid admin timelock")]
+    InvalidTimelock,
+    
+    #[msg("Admin action timelock has not elapsed")]
+    TimelockNotElapsed,
+    
+    #[msg("Pending action does not match the provided accounts")]
+    InvalidPendingAction,
+    
+    #[msg("Verifying key does not match the queued hash")]
+    VerifyingKeyHashMismatch,
+    
+    #[msg("Verifying key exceeds the maximum size")]
+    VerifyingKeyTooLarge,
+    
+    #[msg("Signer does not hold the required role")]
+    MissingRole,
+    
+    #[msg("Too many keys hold roles")]
+    TooManyRoleMembers,
+    
+    #[msg("Cannot revoke the last Admin")]
+    LastAdmin,
+    
+    #[msg("No commitments are waiting for a root update")]
+    NoPendingCommitments,
+    
+    #[msg("Too many commitment batches are waiting for a root update")]
+    RootUpdateBacklogFull,
+    
+    #[msg("Commitment is not a valid field element")]
+    InvalidCommitment,
+    
+    #[msg("Merkle tree is full")]
+    MerkleTreeFull,
+    
+    #[msg("Invalid number of commitments in batch")]
+    InvalidBatchSize,
+    
+    #[msg("Commitment is not a canonical field element (must be less than the BN254 scalar modulus)")]
+    NonCanonicalCommitment,
+    
+    #[msg("Commitment has already been deposited")]
+    DuplicateCommitment,
+    
+    #[msg("Commitment record account does not match the commitment")]
+    InvalidCommitmentRecord,
+    
+    #[msg("Callback program is not whitelisted as a recipient program")]
+    InvalidCallbackProgram,
+    
+    #[msg("Invalid withdrawal callback data or accounts")]
+    InvalidCallback,
+    
+    #[msg("Relayer stake is below the required stake")]
+    InsufficientStake,
+    
+    #[msg("Relayer has an unstake pending")]
+    UnstakePending,
+    
+    #[msg("Relayer has not requested to unstake")]
+    UnstakeNotRequested,
+    
+    #[msg("Unstake cooldown has not elapsed")]
+    UnstakeCooldownNotElapsed,
+    
+    #[msg("Invalid slash amount or evidence")]
+    InvalidSlash,
+    
+    #[msg("Stake mint does not match the relayer config")]
+    InvalidStakeMint,
+    
+    #[msg("Relayer endpoint must be an https URL within the length limit")]
+    InvalidRelayerEndpoint,
+    
+    #[msg("Invalid relayer metadata")]
+    InvalidRelayerMetadata,
+    
+    #[msg("Relayed withdrawals require a signed relayer fee quote")]
+    MissingRelayerQuote,
+    
+    #[msg("Relayer fee quote is not signed by the relayer's quote key")]
+    InvalidQuoteSignature,
+    
+    #[msg("Relayer fee quote has expired")]
+    QuoteExpired,
+    
+    #[msg("Relayer fee quote is for another pool")]
+    QuotePoolMismatch,
+    
+    #[msg("Fee exceeds the relayer's signed quote")]
+    FeeExceedsQuote,
+    
+    #[msg("Relayer fee quote nonce has already been used")]
+    QuoteNonceAlreadyUsed,
+    
+    #[msg("Only the bound relayer can submit this withdrawal during its exclusivity window")]
+    RelayerExclusivityActive,
+    
+    #[msg("Price account is not a trading Pyth price feed")]
+    InvalidPriceFeed,
+    
+    #[msg("Oracle price is too old")]
+    StalePrice,
+    
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    
+    #[msg("Invalid SOL fee configuration or amount")]
+    InvalidSolFee,
+    
+    #[msg("SOL fee reserve cannot cover the SOL fee")]
+    InsufficientSolFeeReserve,
+    
+    #[msg("Verifying key is not a serialized Groth16 key")]
+    InvalidVerifyingKey,
+    
+    #[msg("Claim exceeds the pool's unclaimed protocol fees")]
+    ProtocolFeeClaimExceeded,
 }
 
 #[error_code]
@@ -126,4 +288,16 @@ pub enum ErrorCode {
     TransferAlreadyProcessed,
     #[msg("The provided commitment is invalid or failed validation")]
     InvalidCommitment,
+    #[msg("Outbound bridge transfers are paused")]
+    BridgeOutboundPaused,
+    #[msg("Inbound bridge transfers are paused")]
+    BridgeInboundPaused,
+    #[msg("Pause flags contain bits not valid for the bridge")]
+    InvalidPauseFlags,
+    #[msg("Signer is not allowed to perform this bridge action")]
+    UnauthorizedBridgeAction,
+    #[msg("Bridge payload version is not supported")]
+    UnsupportedPayloadVersion,
+    #[msg("Bridge payload length does not match its version")]
+    InvalidPayloadLength,
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::{AdminAction, Role};
 
 // === Pool Events ===
 
@@ -9,6 +10,15 @@ pub struct PoolInitializedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PoolRegisteredEvent {
+    pub pool: Pubkey,
+    pub mint: Pubkey,
+    pub denomination: u64,
+    pub registry_index: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct PoolUpdatedEvent {
     pub authority: Pubkey,
@@ -16,8 +26,42 @@ pub struct PoolUpdatedEvent {
     pub timestamp: i64,
 }
 
+// === Protocol Config Events ===
+
+#[event]
+pub struct ConfigInitializedEvent {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_basis_points: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdatedEvent {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_basis_points: u16,
+    pub pools_enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeesClaimedEvent {
+    pub pool: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
 // === Deposit Events ===
 
+#[event]
+pub struct DepositCapReachedEvent {
+    pub pool: Pubkey,
+    pub max_total_deposits: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct DepositEvent {
     pub user: Pubkey,
@@ -36,6 +80,15 @@ pub struct WithdrawEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct WithdrawalLimitReachedEvent {
+    pub pool: Pubkey,
+    pub withdrawal_limit_amount: u64,
+    pub window_start_slot: u64,
+    pub window_slots: u64,
+    pub timestamp: i64,
+}
+
 // === Tree Events ===
 
 #[event]
@@ -115,9 +168,48 @@ pub struct IncomingTransferEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct BridgeConfigUpdatedEvent {
+    pub authority: Pubkey,
+    pub new_fee_basis_points: u16,
+    pub new_wormhole_finality: u8,
+    pub new_pauser: Pubkey,
+    pub new_treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExternalEmitterRegisteredEvent {
+    pub chain_id: u16,
+    pub emitter_address: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrossChainTransferInitiatedEvent {
+    pub sender: Pubkey,
+    pub dest_chain_id: u16,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub commitment: [u8; 32],
+    pub wormhole_sequence: u64,
+    pub nonce: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct IncomingTransferProcessedEvent {
+    pub vaa_emitter_chain: u16,
+    pub vaa_emitter_address: [u8; 32],
+    pub vaa_sequence: u64,
+    pub commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct BridgePausedEvent {
-    pub paused: bool,
+    pub pause_flags: u8,
+    pub updated_by: Pubkey,
     pub timestamp: i64,
 }
 
@@ -133,4 +225,191 @@ pub struct RelayerConfigInitializedEvent {
     pub authority: Pubkey,
     pub required_stake: u64,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct PoolPauseFlagsUpdatedEvent {
+    pub pool: Pubkey,
+    pub pause_flags: u8,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferProposedEvent {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuthorityTransferAcceptedEvent {
+    pub account: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionQueuedEvent {
+    pub pending_action: Pubkey,
+    pub target: Pubkey,
+    pub action: AdminAction,
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionExecutedEvent {
+    pub pending_action: Pubkey,
+    pub target: Pubkey,
+    pub action: AdminAction,
+    pub executed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminActionCancelledEvent {
+    pub pending_action: Pubkey,
+    pub target: Pubkey,
+    pub action: AdminAction,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleGrantedEvent {
+    pub member: Pubkey,
+    pub role: Role,
+    pub granted_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleRevokedEvent {
+    pub member: Pubkey,
+    pub role: Role,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchRootUpdatedEvent {
+    pub pool: Pubkey,
+    pub tree: Pubkey,
+    pub old_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub start_index: u64,
+    pub batch_size: u64,
+    pub updater: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecipientProgramAddedEvent {
+    pub program_id: Pubkey,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecipientProgramRemovedEvent {
+    pub program_id: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerStakedEvent {
+    pub relayer: Pubkey,
+    pub amount: u64,
+    pub stake_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerUnstakeRequestedEvent {
+    pub relayer: Pubkey,
+    pub stake_amount: u64,
+    pub requested_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerUnstakedEvent {
+    pub relayer: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerSlashedEvent {
+    pub relayer: Pubkey,
+    pub amount: u64,
+    pub evidence_hash: [u8; 32],
+    pub slashed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerListedEvent {
+    pub relayer: Pubkey,
+    pub metadata: Pubkey,
+    pub directory_index: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerMetadataUpdatedEvent {
+    pub relayer: Pubkey,
+    pub endpoint: String,
+    pub quote_signer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolFeeConfiguredEvent {
+    pub pool: Pubkey,
+    pub pyth_program: Pubkey,
+    pub token_price_feed: Pubkey,
+    pub sol_price_feed: Pubkey,
+    pub max_price_age_seconds: i64,
+    pub max_confidence_basis_points: u16,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolFeePaidEvent {
+    pub pool: Pubkey,
+    pub relayer: Pubkey,
+    pub token_amount: u64,
+    pub lamports: u64,
+    pub token_price: i64,
+    pub sol_price: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolFeeReserveWithdrawnEvent {
+    pub pool: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RelayerStatsEvent {
+    pub relayer: Pubkey,
+    pub mint: Pubkey,
+    pub successful_withdrawals: u64,
+    pub total_relayed: u64,
+    pub total_fees: u64,
+    pub mint_withdrawals: u64,
+    pub mint_total_relayed: u64,
+    pub mint_total_fees: u64,
+    pub last_active_slot: u64,
+    pub slash_count: u64,
+    pub timestamp: i64,
+}
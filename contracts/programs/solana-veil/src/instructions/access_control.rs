@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+// Create the role registry; the protocol config authority becomes the first Admin
+pub fn initialize_access_control(ctx: Context<InitializeAccessControl>) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    let admin = ctx.accounts.authority.key();
+
+    access_control.members = Vec::new();
+    access_control.grant(admin, Role::Admin)?;
+    access_control.bump = ctx.bumps.access_control;
+
+    emit!(RoleGrantedEvent {
+        member: admin,
+        role: Role::Admin,
+        granted_by: admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Grant a role to a key (Admin only)
+pub fn grant_role(
+    ctx: Context<ManageRoles>,
+    member: Pubkey,
+    role: Role,
+) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    access_control.grant(member, role)?;
+
+    emit!(RoleGrantedEvent {
+        member,
+        role,
+        granted_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Revoke a role from a key (Admin only)
+pub fn revoke_role(
+    ctx: Context<ManageRoles>,
+    member: Pubkey,
+    role: Role,
+) -> Result<()> {
+    let access_control = &mut ctx.accounts.access_control;
+    access_control.revoke(&member, role)?;
+
+    emit!(RoleRevokedEvent {
+        member,
+        role,
+        revoked_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeAccessControl<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AccessControl::LEN,
+        seeds = [b"access_control".as_ref()],
+        bump
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageRoles<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&admin.key(), Role::Admin) @ SolanaVeilError::MissingRole
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+}
@@ -1,17 +1,16 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use solana_program::program::invoke_signed;
-use solana_program::system_instruction;
-// Import Wormhole related items
-use wormhole_anchor_sdk::wormhole; // Assuming wormhole_anchor_sdk crate
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
-use crate::errors::ErrorCode;
+use crate::bridge::payload::VeilTransferPayload;
+use crate::bridge::wormhole;
+use crate::errors::{ErrorCode, SolanaVeilError};
 use crate::events::*;
+use crate::instructions::deposit::{append_commitments, record_commitments};
 use crate::state::bridge::*;
-use crate::state::pool::Pool; // Keep if pool interaction is needed
-use crate::state::tree::MerkleTree; // Keep for commitment insertion
-// Remove local verifier import if using Wormhole VAA verification
-// use crate::verifier::verify_bridge_proof;
+use crate::state::{DepositCheckpoints, MerkleTree, Pool};
+use crate::state::pause::pause_flags;
+use crate::state::access_control::{AccessControl, Role};
+use crate::token_extensions;
 
 /// Initialize a new bridge configuration
 pub fn initialize_bridge(
@@ -20,15 +19,16 @@ pub fn initialize_bridge(
     wormhole_finality: u8,
 ) -> Result<()> {
     let bridge_config = &mut ctx.accounts.bridge_config;
-    let wormhole_sequence = &ctx.accounts.wormhole_sequence;
 
     bridge_config.authority = ctx.accounts.authority.key();
+    bridge_config.pending_authority = Pubkey::default();
     bridge_config.treasury = ctx.accounts.treasury.key();
     bridge_config.wormhole_program_id = ctx.accounts.wormhole_program.key();
     bridge_config.wormhole_token_bridge_program_id = ctx.accounts.wormhole_token_bridge.key();
     bridge_config.fee_basis_points = fee_basis_points;
     bridge_config.bump = ctx.bumps.bridge_config;
-    bridge_config.paused = false;
+    bridge_config.pause_flags = 0;
+    bridge_config.pauser = Pubkey::default();
     bridge_config.chain_count = 0;
     bridge_config.wormhole_sequence_bump = ctx.bumps.wormhole_sequence;
     bridge_config.wormhole_finality = wormhole_finality;
@@ -38,27 +38,6 @@ pub fn initialize_bridge(
         bridge_config.supported_chains[i] = ChainConfig::default();
     }
 
-    // Initialize Wormhole sequence tracker
-    // This requires CPI to Wormhole Core Bridge to initialize the sequence PDA
-    // Example using wormhole_anchor_sdk:
-    // wormhole::initialize(
-    //     CpiContext::new_with_signer(
-    //         ctx.accounts.wormhole_program.to_account_info(),
-    //         wormhole::Initialize {
-    //             bridge: ctx.accounts.wormhole_bridge.to_account_info(), // Wormhole's bridge state
-    //             emitter: ctx.accounts.wormhole_emitter.to_account_info(), // Our emitter PDA
-    //             sequence: wormhole_sequence.to_account_info(),
-    //             payer: ctx.accounts.authority.to_account_info(),
-    //             system_program: ctx.accounts.system_program.to_account_info(),
-    //             clock: ctx.accounts.wormhole_clock.to_account_info(), // Wormhole needs clock sysvar
-    //             rent: ctx.accounts.wormhole_rent.to_account_info(),   // Wormhole needs rent sysvar
-    //         },
-    //         &[&[b"emitter".as_ref(), &[ctx.bumps.wormhole_emitter]]] // Signer seeds for our emitter PDA
-    //     ),
-    //     // guardian_set_index, fee
-    // )?;
-    // Note: Actual initialization might differ based on Wormhole SDK specifics.
-
     emit!(BridgeInitializedEvent {
         authority: bridge_config.authority,
         treasury: bridge_config.treasury,
@@ -69,27 +48,20 @@ pub fn initialize_bridge(
     Ok(())
 }
 
-/// Update bridge configuration settings
+/// Update bridge configuration settings.
+/// Fee and treasury changes go through the admin timelock (`queue_admin_action`).
 pub fn update_bridge_config(
     ctx: Context<UpdateBridgeConfig>,
-    new_fee_basis_points: Option<u16>,
     new_wormhole_finality: Option<u8>,
-    new_paused_state: Option<bool>,
-    new_treasury: Option<Pubkey>,
+    new_pauser: Option<Pubkey>,
 ) -> Result<()> {
     let bridge_config = &mut ctx.accounts.bridge_config;
 
-    if let Some(fee) = new_fee_basis_points {
-        bridge_config.fee_basis_points = fee;
-    }
     if let Some(finality) = new_wormhole_finality {
         bridge_config.wormhole_finality = finality;
     }
-    if let Some(paused) = new_paused_state {
-        bridge_config.paused = paused;
-    }
-    if let Some(treasury) = new_treasury {
-        bridge_config.treasury = treasury;
+    if let Some(pauser) = new_pauser {
+        bridge_config.pauser = pauser;
     }
 
     // Emit event
@@ -97,7 +69,7 @@ pub fn update_bridge_config(
         authority: bridge_config.authority,
         new_fee_basis_points: bridge_config.fee_basis_points,
         new_wormhole_finality: bridge_config.wormhole_finality,
-        new_paused_state: bridge_config.paused,
+        new_pauser: bridge_config.pauser,
         new_treasury: bridge_config.treasury,
         timestamp: Clock::get()?.unix_timestamp,
     });
@@ -105,6 +77,93 @@ pub fn update_bridge_config(
     Ok(())
 }
 
+/// Pause or resume bridge directions, callable by the authority, the designated pauser or a Guardian
+pub fn set_bridge_pause_flags(
+    ctx: Context<SetBridgePauseFlags>,
+    pause_flags: u8,
+) -> Result<()> {
+    require!(pause_flags & !pause_flags::BRIDGE_FLAGS == 0, ErrorCode::InvalidPauseFlags);
+
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    bridge_config.pause_flags = pause_flags;
+
+    emit!(BridgePausedEvent {
+        pause_flags,
+        updated_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+
+/// Add a destination chain with no supported tokens yet
+pub fn add_destination_chain(
+    ctx: Context<UpdateBridge>,
+    chain_id: u16,
+) -> Result<()> {
+    let bridge_config = &mut ctx.accounts.bridge_config;
+
+    require!(
+        !bridge_config.supported_chains[..bridge_config.chain_count as usize]
+            .iter()
+            .any(|c| c.chain_id == chain_id),
+        ErrorCode::ChainAlreadySupported
+    );
+    require!((bridge_config.chain_count as usize) < MAX_SUPPORTED_CHAINS, ErrorCode::TooManyChains);
+
+    let index = bridge_config.chain_count as usize;
+    bridge_config.supported_chains[index] = ChainConfig {
+        chain_id,
+        ..ChainConfig::default()
+    };
+    bridge_config.chain_count += 1;
+
+    Ok(())
+}
+
+/// Enable bridging of a Solana mint to an already supported destination chain
+pub fn add_supported_token(
+    ctx: Context<AddSupportedToken>,
+    chain_id: u16,
+    dest_token_id: u64,
+    min_amount: u64,
+    max_amount: u64,
+) -> Result<()> {
+    require!(min_amount <= max_amount, ErrorCode::InvalidAmount);
+
+    // The bridge vault holds this mint like a pool vault does, so the same
+    // Token-2022 extensions are refused
+    token_extensions::validate_pool_mint(&ctx.accounts.mint.to_account_info())?;
+    let mint = ctx.accounts.mint.key();
+
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    let chain_count = bridge_config.chain_count as usize;
+    let chain_config = bridge_config.supported_chains[..chain_count]
+        .iter_mut()
+        .find(|c| c.chain_id == chain_id)
+        .ok_or(ErrorCode::ChainNotSupported)?;
+
+    require!(
+        !chain_config.tokens[..chain_config.token_count as usize]
+            .iter()
+            .any(|t| t.mint == mint),
+        ErrorCode::TokenAlreadySupported
+    );
+    require!((chain_config.token_count as usize) < MAX_SUPPORTED_TOKENS, ErrorCode::TooManyTokens);
+
+    let index = chain_config.token_count as usize;
+    chain_config.tokens[index] = TokenConfig {
+        mint,
+        dest_token_id,
+        min_amount,
+        max_amount,
+        enabled: true,
+    };
+    chain_config.token_count += 1;
+
+    Ok(())
+}
 
 /// Register a trusted bridge emitter from another chain
 pub fn register_external_emitter(
@@ -141,9 +200,12 @@ pub fn initiate_cross_chain_transfer(
     let bridge_config = &ctx.accounts.bridge_config;
     let bridge_transfer = &mut ctx.accounts.bridge_transfer;
 
-    require!(!bridge_config.paused, ErrorCode::BridgePaused);
+    require!(
+        !pause_flags::is_paused(bridge_config.pause_flags, pause_flags::BRIDGE_OUTBOUND),
+        ErrorCode::BridgeOutboundPaused
+    );
 
-    let (chain_config, token_config) = find_token_config(
+    let (_, token_config) = find_token_config(
         bridge_config,
         destination_chain_id,
         ctx.accounts.mint.key(),
@@ -161,52 +223,53 @@ pub fn initiate_cross_chain_transfer(
         .unwrap() as u64;
     let transfer_amount = amount.checked_sub(fee_amount).ok_or(ErrorCode::ArithmeticOverflow)?;
 
-    // Transfer tokens from user to vault/bridge account
-    // Option 1: Transfer to a bridge-controlled vault account
+    // Transfer tokens from the user to the bridge-controlled vault
+    let decimals = ctx.accounts.mint.decimals;
+    let vault_balance_before = ctx.accounts.vault_token_account.amount;
     let transfer_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.vault_token_account.to_account_info(), // Bridge vault
             authority: ctx.accounts.user.to_account_info(),
         },
     );
-    token::transfer(transfer_ctx, amount)?;
+    token_interface::transfer_checked(transfer_ctx, amount, decimals)?;
 
-    // Option 2: Use Wormhole Token Bridge `transfer_tokens_with_payload`
-    // This locks tokens directly in the Token Bridge and emits a message.
-    // Requires different accounts and CPI structure. Let's stick with Option 1 for now.
+    // Token-2022 transfer fees would leave the vault short of the bridged amount
+    ctx.accounts.vault_token_account.reload()?;
+    require!(
+        ctx.accounts.vault_token_account.amount.checked_sub(vault_balance_before) == Some(amount),
+        ErrorCode::InvalidAmount
+    );
 
     // Transfer fee to treasury
     if fee_amount > 0 {
         let fee_transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.treasury_token_account.to_account_info(),
                 authority: ctx.accounts.vault_authority.to_account_info(), // Vault PDA
             },
         );
-        let vault_seeds = &[
-            b"vault_authority", // Make sure seeds match vault PDA derivation
-            &[ctx.accounts.vault_authority_bump], // Pass bump if needed
-        ];
-        token::transfer(fee_transfer_ctx.with_signer(&[&vault_seeds[..]]), fee_amount)?;
+        let vault_seeds: &[&[u8]] = &[b"vault_authority", &[ctx.bumps.vault_authority]];
+        token_interface::transfer_checked(fee_transfer_ctx.with_signer(&[vault_seeds]), fee_amount, decimals)?;
     }
 
     // Construct Wormhole message payload
-    // Payload ID (1 for standard transfer, 3 for transfer with payload)
-    // Let's use a custom payload ID, e.g., 100, for SolanaVeil commitment transfer
-    let payload_id: u8 = 100;
-    let mut message_payload: Vec<u8> = Vec::new();
-    message_payload.push(payload_id);
-    message_payload.extend_from_slice(&transfer_amount.to_be_bytes()); // Amount (net)
-    message_payload.extend_from_slice(&token_config.mint.to_bytes()); // Token address (Solana mint)
-    message_payload.extend_from_slice(&wormhole::CHAIN_ID_SOLANA.to_be_bytes()); // Source Chain ID (Solana)
-    message_payload.extend_from_slice(&destination_chain_id.to_be_bytes()); // Destination Chain ID
-    message_payload.extend_from_slice(&destination_address); // Recipient (Bridge contract on dest chain)
-    message_payload.extend_from_slice(&commitment); // Privacy commitment
-    message_payload.extend_from_slice(&nonce.to_be_bytes()); // Nonce
+    let message_payload = VeilTransferPayload {
+        amount: transfer_amount, // Net amount
+        token: token_config.mint.to_bytes(),
+        source_chain: wormhole::CHAIN_ID_SOLANA,
+        destination_chain: destination_chain_id,
+        recipient: destination_address, // Bridge contract on dest chain
+        commitment,
+        nonce,
+    }
+    .encode();
 
     // Post message to Wormhole
     let wormhole_accounts = wormhole::PostMessage {
@@ -220,15 +283,12 @@ pub fn initiate_cross_chain_transfer(
         rent: ctx.accounts.wormhole_rent.to_account_info(),
         system_program: ctx.accounts.system_program.to_account_info(),
     };
-    let emitter_signer_seeds = &[
-        b"emitter".as_ref(), // Seed used in InitializeBridge
-        &[ctx.bumps.wormhole_emitter],
-    ];
+    let emitter_signer_seeds: &[&[u8]] = &[b"emitter", &[ctx.bumps.wormhole_emitter]];
     let sequence = wormhole::post_message(
         CpiContext::new_with_signer(
             ctx.accounts.wormhole_program.to_account_info(),
             wormhole_accounts,
-            &[&emitter_signer_seeds[..]],
+            &[emitter_signer_seeds],
         ),
         nonce,
         message_payload,
@@ -247,22 +307,17 @@ pub fn initiate_cross_chain_transfer(
     bridge_transfer.status = TransferStatus::Pending;
     bridge_transfer.bump = ctx.bumps.bridge_transfer;
 
-    // Add commitment to Merkle tree (using existing pool/tree logic)
-    // This assumes the commitment needs to be added to the *local* Solana tree as well.
-    // If the commitment is only relevant on the destination chain, this step might be removed.
-    crate::instructions::tree::add_leaf(
-        &ctx.accounts.merkle_tree,
-        commitment,
-    )?;
+    // The commitment is only spendable on the destination chain: the tokens sit in the
+    // bridge vault, not in a pool, so it must not become a leaf of any local tree
 
     emit!(CrossChainTransferInitiatedEvent {
         sender: ctx.accounts.user.key(),
         dest_chain_id: destination_chain_id,
         token_mint: ctx.accounts.mint.key(),
         amount: transfer_amount, // Net amount
-        commitment: commitment,
+        commitment,
         wormhole_sequence: sequence,
-        nonce: nonce,
+        nonce,
         timestamp: bridge_transfer.timestamp,
     });
 
@@ -273,169 +328,107 @@ pub fn initiate_cross_chain_transfer(
 /// Process an incoming transfer VAA from Wormhole
 pub fn process_incoming_transfer(
     ctx: Context<ProcessIncomingTransfer>,
-    vaa_hash: [u8; 32], // Pass the VAA hash for verification
+    vaa_hash: [u8; 32],
 ) -> Result<()> {
     let bridge_config = &ctx.accounts.bridge_config;
-    require!(!bridge_config.paused, ErrorCode::BridgePaused);
-
-    // Verify the VAA using Wormhole Core Bridge CPI
-    let posted_vaa = &ctx.accounts.posted_vaa; // Account containing the VAA data posted by relayers
-    let vaa = wormhole::parse_vaa(posted_vaa.as_ref())?; // Use Wormhole SDK to parse
+    require!(
+        !pause_flags::is_paused(bridge_config.pause_flags, pause_flags::BRIDGE_INBOUND),
+        ErrorCode::BridgeInboundPaused
+    );
 
-    // Check VAA hash matches the one passed in (ensures correct VAA is used)
-    // require!(vaa.digest().hash == vaa_hash, ErrorCode::InvalidWormholeMessage);
+    // The Core Bridge only posts VAAs whose guardian signatures it verified, and the
+    // posted VAA's address is derived from `vaa_hash`, so it is the VAA the caller named
+    let vaa = wormhole::parse_vaa(&ctx.accounts.posted_vaa, &bridge_config.wormhole_program_id)?;
 
     // Verify the emitter chain and address are registered/trusted
-    let external_emitter_key = Pubkey::create_program_address(
-        &[
-            b"external_emitter",
-            &vaa.emitter_chain.to_be_bytes(),
-            &vaa.emitter_address, // Wormhole emitter address is already bytes32
-        ],
-        ctx.program_id,
-    ).map_err(|_| ProgramError::InvalidSeeds)?;
-
-    require!(external_emitter_key == ctx.accounts.external_emitter.key(), ErrorCode::InvalidExternalEmitter);
-    require!(ctx.accounts.external_emitter.is_active, ErrorCode::InvalidExternalEmitter);
+    let external_emitter = &ctx.accounts.external_emitter;
+    require!(
+        external_emitter.chain_id == vaa.emitter_chain
+            && external_emitter.emitter_address == vaa.emitter_address,
+        ErrorCode::InvalidExternalEmitter
+    );
+    require!(external_emitter.is_active, ErrorCode::InvalidExternalEmitter);
 
     // Decode the payload from the VAA
-    // Assuming the payload format defined in the documentation
-    let payload = vaa.payload;
-    require!(payload.len() > 1 + 8 + 32 + 2 + 2 + 32 + 32 + 4, ErrorCode::InvalidWormholeMessage); // Basic length check
-
-    let payload_id = payload[0];
-    require!(payload_id == 100, ErrorCode::InvalidWormholeMessage); // Check for our custom payload ID
-
-    let amount = u64::from_be_bytes(payload[1..9].try_into().unwrap());
-    let token_address_bytes: [u8; 32] = payload[9..41].try_into().unwrap(); // Origin token address bytes
-    let source_chain_id = u16::from_be_bytes(payload[41..43].try_into().unwrap());
-    let target_chain_id = u16::from_be_bytes(payload[43..45].try_into().unwrap());
-    let recipient_bytes: [u8; 32] = payload[45..77].try_into().unwrap(); // Should be this bridge program ID in Wormhole format
-    let commitment: [u8; 32] = payload[77..109].try_into().unwrap();
-    // let nonce = u32::from_be_bytes(payload[109..113].try_into().unwrap()); // Nonce might be useful
-
-    require!(source_chain_id == vaa.emitter_chain, ErrorCode::InvalidWormholeMessage);
-    require!(target_chain_id == wormhole::CHAIN_ID_SOLANA, ErrorCode::InvalidWormholeMessage);
-    // Verify recipient is this program?
-
-    // Find corresponding Solana mint for the incoming token
-    // This requires looking up based on source_chain_id and token_address_bytes
-    // Need a reverse mapping in BridgeConfig or a separate lookup mechanism.
-    // Placeholder: Assume we found the mint.
-    let local_mint_pubkey = ctx.accounts.mint.key(); // Use the mint passed in context for now
-
-    // Add the commitment to the local Merkle tree
-    crate::instructions::tree::add_leaf(
-        &ctx.accounts.merkle_tree,
-        commitment,
+    let payload = VeilTransferPayload::decode(&vaa.payload)?;
+    let commitment = payload.commitment;
+
+    require!(payload.source_chain == vaa.emitter_chain, ErrorCode::InvalidWormholeMessage);
+    require!(payload.destination_chain == wormhole::CHAIN_ID_SOLANA, ErrorCode::InvalidWormholeMessage);
+
+    // The note must be for the pool's token and denomination, as a deposit would be
+    let pool = &mut ctx.accounts.pool;
+    require!(
+        !pause_flags::is_paused(pool.pause_flags, pause_flags::DEPOSITS),
+        SolanaVeilError::DepositsPaused
+    );
+    let (_, token_config) = find_token_config(bridge_config, payload.source_chain, pool.mint)?;
+    require!(token_config.enabled, ErrorCode::TokenNotSupported);
+    require!(payload.token == remote_token_id(token_config.dest_token_id), ErrorCode::TokenNotSupported);
+    require!(payload.amount == pool.denomination, ErrorCode::InvalidAmount);
+    pool.check_deposit_cap(payload.amount)?;
+
+    record_commitments(
+        &pool.key(),
+        &[ctx.accounts.commitment_record.to_account_info()],
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &[commitment],
     )?;
 
-    // Mark the VAA as processed to prevent replay
-    // This usually involves storing the VAA hash or emitter/sequence in an account.
-    // Let's use a simple PDA based on the VAA hash.
+    // Release the bridged value from the bridge vault into the pool's vault, so the
+    // note is backed like any deposited one
+    let mint = &ctx.accounts.mint;
+    let transfer_amount = token_extensions::gross_amount_for(&mint.to_account_info(), payload.amount)?;
+    let pool_balance_before = ctx.accounts.pool_token_account.amount;
+    let vault_seeds: &[&[u8]] = &[b"vault_authority", &[ctx.bumps.vault_authority]];
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            to: ctx.accounts.pool_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        },
+    );
+    token_interface::transfer_checked(transfer_ctx.with_signer(&[vault_seeds]), transfer_amount, mint.decimals)?;
+
+    ctx.accounts.pool_token_account.reload()?;
+    require!(
+        ctx.accounts.pool_token_account.amount.checked_sub(pool_balance_before) == Some(payload.amount),
+        SolanaVeilError::DepositAmountMismatch
+    );
+
+    pool.total_deposited = pool.total_deposited.checked_add(payload.amount)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    append_commitments(pool, &mut ctx.accounts.tree, &mut ctx.accounts.checkpoints, &[commitment])?;
+
+    // Both the posted VAA and this replay marker are derived from `vaa_hash`, so the
+    // same message can only be processed once
     let processed_vaa = &mut ctx.accounts.processed_vaa;
+    processed_vaa.vaa_hash = vaa_hash;
     processed_vaa.timestamp = Clock::get()?.unix_timestamp;
     processed_vaa.bump = ctx.bumps.processed_vaa;
 
-
-    // Note: Tokens are NOT released here. They are made available for withdrawal
-    // via the `complete_bridge_withdrawal` instruction using the commitment.
-    // The actual tokens should have been transferred to a vault via Wormhole Token Bridge's
-    // `complete_transfer` mechanism before this instruction is called, or managed by this program.
-
     emit!(IncomingTransferProcessedEvent {
         vaa_emitter_chain: vaa.emitter_chain,
         vaa_emitter_address: vaa.emitter_address,
         vaa_sequence: vaa.sequence,
-        commitment: commitment,
+        commitment,
         timestamp: processed_vaa.timestamp,
     });
 
     Ok(())
 }
 
-/// Complete a withdrawal initiated from another chain (verifies ZK proof)
-pub fn complete_bridge_withdrawal(
-    ctx: Context<CompleteBridgeWithdrawal>,
-    proof_data: Vec<u8>,
-    root: [u8; 32],
-    nullifier_hash: [u8; 32],
-    recipient: Pubkey, // Solana recipient address
-    relayer: Pubkey,   // Relayer submitting the transaction (can be recipient)
-    fee: u64,          // Fee paid to relayer in token units
-    refund: u64,       // Refund amount in SOL (unused here?)
-) -> Result<()> {
-    // This instruction is essentially the same as the standard `withdraw` instruction
-    // but might use context derived from the cross-chain flow (e.g., commitment added by `process_incoming_transfer`).
-
-    // 1. Verify Merkle Root is known (using MerkleTree state)
-    require!(ctx.accounts.merkle_tree.is_known_root(root), ErrorCode::InvalidRoot);
-
-    // 2. Verify Nullifier is not used (using NullifierSet state)
-    require!(!ctx.accounts.nullifier_set.contains(nullifier_hash), ErrorCode::NullifierAlreadyUsed);
-
-    // 3. Verify ZK Proof
-    //    The public inputs should include: root, nullifier_hash, recipient, relayer, fee
-    //    Need to fetch the verification key (e.g., from an account)
-    //    verify_zk_proof(proof_data, vk_data, public_inputs)?; // Placeholder
-
-    // 4. Mark Nullifier as used
-    ctx.accounts.nullifier_set.insert(nullifier_hash)?;
-
-    // 5. Calculate amounts
-    let withdraw_amount = ctx.accounts.pool.get_deposit_amount(); // Get amount associated with commitment/proof
-    let amount_to_recipient = withdraw_amount.checked_sub(fee).ok_or(ErrorCode::InvalidFee)?;
-
-    // 6. Transfer tokens to recipient
-    let transfer_recipient_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.vault_token_account.to_account_info(),
-            to: ctx.accounts.recipient_token_account.to_account_info(),
-            authority: ctx.accounts.vault_authority.to_account_info(),
-        },
-    );
-    let vault_seeds = &[
-        b"vault_authority", // Make sure seeds match vault PDA derivation
-        &[ctx.accounts.vault_authority_bump], // Pass bump if needed
-    ];
-    token::transfer(transfer_recipient_ctx.with_signer(&[&vault_seeds[..]]), amount_to_recipient)?;
-
-    // 7. Transfer fee to relayer
-    if fee > 0 && relayer != Pubkey::default() {
-        let transfer_relayer_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.vault_token_account.to_account_info(),
-                to: ctx.accounts.relayer_token_account.to_account_info(),
-                authority: ctx.accounts.vault_authority.to_account_info(),
-            },
-        );
-        token::transfer(transfer_relayer_ctx.with_signer(&[&vault_seeds[..]]), fee)?;
-    }
-
-    // 8. Handle SOL refund (if applicable, though less common in token withdrawals)
-    // if refund > 0 && relayer != Pubkey::default() { ... transfer SOL ... }
-
-    emit!(WithdrawalEvent {
-        to: recipient,
-        nullifier_hash: nullifier_hash,
-        amount: amount_to_recipient,
-        fee: fee,
-    });
-
-    Ok(())
-}
-
-
 // === Helper Functions ===
 
 /// Helper function to find token configuration for a chain and mint
-fn find_token_config<'a>(
-    bridge_config: &'a BridgeConfig,
+fn find_token_config(
+    bridge_config: &BridgeConfig,
     chain_id: u16,
     mint: Pubkey,
-) -> Result<(&'a ChainConfig, &'a TokenConfig)> {
+) -> Result<(&ChainConfig, &TokenConfig)> {
     let chain_config = bridge_config.supported_chains.iter().find(|c| c.chain_id == chain_id)
         .ok_or(ErrorCode::ChainNotSupported)?;
 
@@ -445,12 +438,13 @@ fn find_token_config<'a>(
     Ok((chain_config, token_config))
 }
 
-// Remove old helper functions related to local ZK proof verification if handled differently
-// fn add_commitment_to_tree(...) -> Result<()> { ... }
-// fn verify_nullifier_unused(...) -> Result<()> { ... }
-// fn add_nullifier_to_compressed_set(...) -> Result<()> { ... }
-// fn keccak256(...) -> [u8; 32] { ... }
-
+/// A remote token id in the payload's 32-byte token field, big-endian like the
+/// `uint256` token ids of `SolanaVeilBridge.sol`
+fn remote_token_id(dest_token_id: u64) -> [u8; 32] {
+    let mut token = [0u8; 32];
+    token[24..].copy_from_slice(&dest_token_id.to_be_bytes());
+    token
+}
 
 // === Context Structs ===
 
@@ -491,10 +485,10 @@ pub struct InitializeBridge<'info> {
     #[account(mut, seeds = [b"fee_collector"], bump, seeds::program = wormhole_program.key())]
     pub wormhole_fee_collector: AccountInfo<'info>,
     /// CHECK: Clock sysvar.
-    #[account(address = solana_program::sysvar::clock::ID)]
+    #[account(address = anchor_lang::solana_program::sysvar::clock::ID)]
     pub wormhole_clock: AccountInfo<'info>,
     /// CHECK: Rent sysvar.
-    #[account(address = solana_program::sysvar::rent::ID)]
+    #[account(address = anchor_lang::solana_program::sysvar::rent::ID)]
     pub wormhole_rent: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
@@ -506,10 +500,71 @@ pub struct UpdateBridgeConfig<'info> {
         mut,
         seeds = [b"bridge_config"],
         bump = bridge_config.bump,
-        has_one = authority,
     )]
     pub bridge_config: Account<'info, BridgeConfig>,
-    pub authority: Signer<'info>,
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&operator.key(), Role::BridgeOperator) @ SolanaVeilError::MissingRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateBridge<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_config"],
+        bump = bridge_config.bump,
+    )]
+    pub bridge_config: Box<Account<'info, BridgeConfig>>,
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&operator.key(), Role::BridgeOperator) @ SolanaVeilError::MissingRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+    pub operator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddSupportedToken<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_config"],
+        bump = bridge_config.bump,
+    )]
+    pub bridge_config: Box<Account<'info, BridgeConfig>>,
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&operator.key(), Role::BridgeOperator) @ SolanaVeilError::MissingRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+    pub operator: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(Accounts)]
+pub struct SetBridgePauseFlags<'info> {
+    #[account(
+        mut,
+        seeds = [b"bridge_config"],
+        bump = bridge_config.bump,
+        constraint = signer.key() == bridge_config.authority
+            || (bridge_config.pauser != Pubkey::default() && signer.key() == bridge_config.pauser)
+            || access_control.has_role(&signer.key(), Role::Guardian)
+            @ ErrorCode::UnauthorizedBridgeAction,
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+    /// Bridge authority, designated pauser or Guardian
+    pub signer: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -518,21 +573,27 @@ pub struct RegisterExternalEmitter<'info> {
     #[account(
         seeds = [b"bridge_config"],
         bump = bridge_config.bump,
-        has_one = authority,
     )]
-    pub bridge_config: Account<'info, BridgeConfig>, // Need config to check authority
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    #[account(
+        seeds = [b"access_control"],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&operator.key(), Role::BridgeOperator) @ SolanaVeilError::MissingRole,
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
 
     #[account(
         init,
-        payer = authority,
+        payer = operator,
         space = 8 + std::mem::size_of::<ExternalBridgeEmitter>(),
-        seeds = [b"external_emitter", &chain_id.to_be_bytes(), &emitter_address],
+        seeds = [b"external_emitter".as_ref(), &chain_id.to_be_bytes(), &emitter_address],
         bump,
     )]
     pub external_emitter: Account<'info, ExternalBridgeEmitter>,
 
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub operator: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
@@ -553,33 +614,26 @@ pub struct InitiateCrossChainTransfer<'info> {
         init,
         payer = user,
         space = 8 + std::mem::size_of::<BridgeTransfer>(),
-        // Use commitment or wormhole sequence for PDA uniqueness? Sequence is better.
-        seeds = [b"bridge_transfer", bridge_config.wormhole_emitter.key().as_ref(), &destination_chain_id.to_be_bytes(), &nonce.to_be_bytes()], // Placeholder seeds
+        seeds = [b"bridge_transfer".as_ref(), wormhole_emitter.key().as_ref(), &destination_chain_id.to_be_bytes(), &nonce.to_be_bytes()],
         bump,
     )]
     pub bridge_transfer: Account<'info, BridgeTransfer>,
 
-    pub mint: Account<'info, Mint>,
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(mut, constraint = user_token_account.owner == user.key(), constraint = user_token_account.mint == mint.key())]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
     // Bridge Vault Account (holds tokens before Wormhole transfer or if not using Token Bridge directly)
     #[account(mut, constraint = vault_token_account.mint == mint.key())]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
     /// CHECK: PDA authority for the vault. Seeds: ["vault_authority"]
-    #[account(seeds = [b"vault_authority"], bump)] // Add bump if needed
+    #[account(seeds = [b"vault_authority"], bump)]
     pub vault_authority: AccountInfo<'info>,
-    // Add vault_authority_bump if needed
-    pub vault_authority_bump: u8,
-
 
     #[account(mut, constraint = treasury_token_account.mint == mint.key(), constraint = treasury_token_account.owner == bridge_config.treasury)]
-    pub treasury_token_account: Account<'info, TokenAccount>,
-
-    // Merkle Tree Account (for local commitment insertion)
-    #[account(mut)]
-    pub merkle_tree: Account<'info, MerkleTree>,
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
 
     // Wormhole Accounts
     /// CHECK: Wormhole Core Bridge Program ID.
@@ -589,7 +643,7 @@ pub struct InitiateCrossChainTransfer<'info> {
     #[account(mut, seeds = [b"Bridge"], bump, seeds::program = wormhole_program.key())]
     pub wormhole_bridge: AccountInfo<'info>,
     /// CHECK: PDA signer for Wormhole messages. Seeds: ["emitter"]
-    #[account(seeds = [b"emitter"], bump)] // Use bump from bridge_config?
+    #[account(seeds = [b"emitter"], bump)]
     pub wormhole_emitter: AccountInfo<'info>,
     /// CHECK: Wormhole sequence tracking PDA. Seeds: ["Sequence", wormhole_emitter.key().as_ref()]
     #[account(mut, seeds = [b"Sequence", wormhole_emitter.key().as_ref()], bump = bridge_config.wormhole_sequence_bump, seeds::program = wormhole_program.key())]
@@ -597,17 +651,17 @@ pub struct InitiateCrossChainTransfer<'info> {
     /// CHECK: Wormhole fee collector account.
     #[account(mut, seeds = [b"fee_collector"], bump, seeds::program = wormhole_program.key())]
     pub wormhole_fee_collector: AccountInfo<'info>,
-    /// CHECK: Account to store the Wormhole message data. Needs to be initialized.
-    #[account(mut)] // Should be initialized by payer before calling post_message
-    pub wormhole_message: Signer<'info>, // Message account needs to sign? Or is it written to? Check Wormhole docs. Often written to.
+    /// CHECK: Fresh keypair account the Core Bridge writes the message to
+    #[account(mut)]
+    pub wormhole_message: Signer<'info>,
     /// CHECK: Clock sysvar.
-    #[account(address = solana_program::sysvar::clock::ID)]
+    #[account(address = anchor_lang::solana_program::sysvar::clock::ID)]
     pub wormhole_clock: AccountInfo<'info>,
     /// CHECK: Rent sysvar.
-    #[account(address = solana_program::sysvar::rent::ID)]
+    #[account(address = anchor_lang::solana_program::sysvar::rent::ID)]
     pub wormhole_rent: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -630,100 +684,79 @@ pub struct ProcessIncomingTransfer<'info> {
     #[account(address = bridge_config.wormhole_program_id)]
     pub wormhole_program: AccountInfo<'info>,
     /// CHECK: Account holding the posted VAA data. Seeds: ["PostedVAA", &vaa_hash]
-    #[account(seeds = [b"PostedVAA", &vaa_hash], bump, seeds::program = wormhole_program.key())]
-    pub posted_vaa: AccountInfo<'info>, // This needs to be the account structure defined by Wormhole Core
+    #[account(seeds = [b"PostedVAA".as_ref(), &vaa_hash], bump, seeds::program = wormhole_program.key())]
+    pub posted_vaa: AccountInfo<'info>,
 
-    // External Emitter Account (derived from VAA)
-    /// CHECK: Derived based on VAA emitter_chain and emitter_address. Seeds: ["external_emitter", chain_id_bytes, emitter_address_bytes]
-    #[account(seeds = [b"external_emitter", &posted_vaa.emitter_chain.to_be_bytes(), &posted_vaa.emitter_address], bump = external_emitter.bump)] // Assuming posted_vaa has these fields after parsing
+    // External Emitter Account, checked against the VAA's emitter in the handler
     pub external_emitter: Account<'info, ExternalBridgeEmitter>,
 
-    // Merkle Tree Account (to add commitment)
-    #[account(mut)]
-    pub merkle_tree: Account<'info, MerkleTree>,
-
-    // Mint account (needed to associate commitment with token type?)
-    // How do we know which mint this corresponds to without parsing payload first?
-    // Maybe commitment insertion doesn't need mint context directly.
-    /// CHECK: Mint associated with the transfer (needs lookup based on VAA payload).
-    pub mint: Account<'info, Mint>,
-
-    // Processed VAA tracking account
+    // Pool credited with the bridged note
     #[account(
-        init,
-        payer = payer,
-        space = 8 + 8 + 1, // timestamp + bump
-        seeds = [b"processed_vaa", &vaa_hash],
-        bump
+        mut,
+        constraint = pool.is_spl_token @ SolanaVeilError::InvalidTokenAccount
     )]
-    pub processed_vaa: Account<'info, ProcessedVaa>,
-
-    pub system_program: Program<'info, System>,
-}
-
-// Simple account to track processed VAAs
-#[account]
-pub struct ProcessedVaa {
-    pub timestamp: i64,
-    pub bump: u8,
-}
-
+    pub pool: Box<Account<'info, Pool>>,
 
-#[derive(Accounts)]
-#[instruction(proof_data: Vec<u8>, root: [u8; 32], nullifier_hash: [u8; 32], recipient: Pubkey, relayer: Pubkey, fee: u64, refund: u64)]
-pub struct CompleteBridgeWithdrawal<'info> {
-    // Pool account (if amount is derived from pool state)
-    // pub pool: Account<'info, Pool>,
+    #[account(
+        mut,
+        constraint = tree.key() == pool.tree @ SolanaVeilError::InvalidMerkleTree,
+        constraint = tree.pool == pool.key() @ SolanaVeilError::InvalidMerkleTree
+    )]
+    pub tree: Box<Account<'info, MerkleTree>>,
 
-    // Merkle Tree account (to verify root)
-    #[account()]
-    pub merkle_tree: Account<'info, MerkleTree>,
+    #[account(
+        mut,
+        seeds = [b"checkpoints".as_ref(), pool.key().as_ref()],
+        bump = checkpoints.bump
+    )]
+    pub checkpoints: Box<Account<'info, DepositCheckpoints>>,
 
-    // Nullifier set account (to check and insert nullifier)
+    /// CHECK: The commitment's record PDA, checked and created by `CommitmentRecord::create`
     #[account(mut)]
-    pub nullifier_set: Account<'info, crate::state::nullifier::NullifierSet>, // Assuming path
+    pub commitment_record: AccountInfo<'info>,
 
-    // Verification Key Account (needed for ZK proof verification)
-    /// CHECK: Account holding the ZK verification key.
-    pub verification_key: AccountInfo<'info>,
+    #[account(
+        constraint = mint.key() == pool.mint @ SolanaVeilError::TokenAccountMintMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
 
-    // Vault token account holding the funds
-    #[account(mut)]
-    pub vault_token_account: Account<'info, TokenAccount>,
+    // Bridge vault releasing the bridged tokens
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vault_authority,
+        token::token_program = token_program
+    )]
+    pub vault_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     /// CHECK: PDA authority for the vault. Seeds: ["vault_authority"]
-    #[account(seeds = [b"vault_authority"], bump)] // Add bump if needed
+    #[account(seeds = [b"vault_authority"], bump)]
     pub vault_authority: AccountInfo<'info>,
-    // Add vault_authority_bump if needed
-    pub vault_authority_bump: u8,
-
 
-    // Recipient's token account
     #[account(
         mut,
-        constraint = recipient_token_account.owner == recipient @ ErrorCode::InvalidProof, // Recipient must match proof/input
-        constraint = recipient_token_account.mint == vault_token_account.mint @ ErrorCode::InvalidProof, // Mints must match
+        constraint = pool_token_account.key() == pool.vault @ SolanaVeilError::InvalidVault
     )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    // Relayer's token account (optional, for fee)
+    // Replay marker of the VAA
     #[account(
-        mut,
-        constraint = relayer_token_account.owner == relayer @ ErrorCode::InvalidProof, // Relayer must match input
-        constraint = relayer_token_account.mint == vault_token_account.mint @ ErrorCode::InvalidProof, // Mints must match
+        init,
+        payer = payer,
+        space = 8 + 32 + 8 + 1, // vaa_hash + timestamp + bump
+        seeds = [b"processed_vaa".as_ref(), &vaa_hash],
+        bump
     )]
-    pub relayer_token_account: Account<'info, TokenAccount>, // Use Box if truly optional based on fee > 0
-
-    // Token program
-    pub token_program: Program<'info, Token>,
+    pub processed_vaa: Account<'info, ProcessedVaa>,
 
-    // System program (potentially for SOL refund)
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-
-// Remove old contexts if they are fully replaced
-// #[derive(Accounts)] ... InitializeNullifier ...
-// #[derive(Accounts)] ... InitializeRelayer ...
-// #[derive(Accounts)] ... RegisterRelayer ...
-// #[derive(Accounts)] ... LockTokensForBridge (replaced by InitiateCrossChainTransfer) ...
-// #[derive(Accounts)] ... ProcessIncomingTransfer (old version) ...
\ No newline at end of file
+// Simple account to track processed VAAs
+#[account]
+pub struct ProcessedVaa {
+    pub vaa_hash: [u8; 32],
+    pub timestamp: i64,
+    pub bump: u8,
+}
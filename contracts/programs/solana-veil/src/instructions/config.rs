@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+/// Maximum protocol fee in basis points (5%)
+pub const MAX_PROTOCOL_FEE_BASIS_POINTS: u16 = 500;
+
+/// Compute the protocol fee skimmed from a withdrawal of `amount`
+pub fn calculate_protocol_fee(amount: u64, protocol_fee_basis_points: u16) -> Result<u64> {
+    let protocol_fee = (amount as u128)
+        .checked_mul(protocol_fee_basis_points as u128)
+        .ok_or(SolanaVeilError::CalculationError)?
+        .checked_div(10000)
+        .ok_or(SolanaVeilError::CalculationError)? as u64;
+    Ok(protocol_fee)
+}
+
+// Initialize the protocol configuration
+pub fn initialize_config(
+    ctx: Context<InitializeConfig>,
+    protocol_fee_basis_points: u16,
+    treasury: Pubkey,
+    admin_timelock_seconds: i64,
+) -> Result<()> {
+    if protocol_fee_basis_points > MAX_PROTOCOL_FEE_BASIS_POINTS {
+        return Err(SolanaVeilError::FeeTooHigh.into());
+    }
+    
+    if !(0..=MAX_ADMIN_TIMELOCK_SECONDS).contains(&admin_timelock_seconds) {
+        return Err(SolanaVeilError::InvalidTimelock.into());
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.authority = ctx.accounts.authority.key();
+    config.protocol_fee_basis_points = protocol_fee_basis_points;
+    config.treasury = treasury;
+    config.pools_enabled = true;
+    config.admin_timelock_seconds = admin_timelock_seconds;
+    config.bump = ctx.bumps.config;
+
+    emit!(ConfigInitializedEvent {
+        authority: config.authority,
+        treasury,
+        protocol_fee_basis_points,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Update the protocol configuration. Fee and treasury changes go through
+// the admin timelock (`queue_admin_action`).
+pub fn update_config(
+    ctx: Context<UpdateConfig>,
+    pools_enabled: Option<bool>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(enabled) = pools_enabled {
+        config.pools_enabled = enabled;
+    }
+
+    emit!(ConfigUpdatedEvent {
+        authority: config.authority,
+        treasury: config.treasury,
+        protocol_fee_basis_points: config.protocol_fee_basis_points,
+        pools_enabled: config.pools_enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Create the treasury vault that collects a pool's protocol fees
+pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    if !pool.is_spl_token {
+        // Native SOL vault is a data-less system account, funded up to rent exemption
+        // so any fee amount can be credited to it
+        let treasury_vault = ctx.accounts.treasury_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidTreasuryVault)?;
+
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let top_up = rent_exempt_minimum.saturating_sub(treasury_vault.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.authority.key,
+                    treasury_vault.key,
+                    top_up,
+                ),
+                &[
+                    ctx.accounts.authority.to_account_info(),
+                    treasury_vault.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+    }
+
+    msg!("Initialized treasury vault for pool: {}", pool.key());
+
+    Ok(())
+}
+
+// Claim accumulated protocol fees from a pool's treasury vault
+pub fn claim_protocol_fees(
+    ctx: Context<ClaimProtocolFees>,
+    amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let pool_key = pool.key();
+
+    // Only accrued protocol fees can be claimed; for SPL pools the treasury vault
+    // also holds the tokens relayers were paid SOL for
+    pool.record_protocol_fee_claim(amount)?;
+
+    if pool.is_spl_token {
+        let treasury_token_account = ctx.accounts.treasury_token_account.as_ref()
+            .ok_or(SolanaVeilError::InvalidTreasuryVault)?;
+        let destination_token_account = ctx.accounts.destination_token_account.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+        let token_program = ctx.accounts.token_program.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+        let mint = ctx.accounts.mint.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+
+        if amount > treasury_token_account.amount {
+            return Err(SolanaVeilError::InsufficientFunds.into());
+        }
+
+        let config_seeds = &[b"config".as_ref(), &[ctx.accounts.config.bump]];
+        let config_signer = &[&config_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: treasury_token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                to: destination_token_account.to_account_info(),
+                authority: ctx.accounts.config.to_account_info(),
+            },
+            config_signer,
+        );
+
+        token_interface::transfer_checked(transfer_ctx, amount, mint.decimals)?;
+    } else {
+        let treasury_vault = ctx.accounts.treasury_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidTreasuryVault)?;
+
+        // Never dip into the vault's rent reserve
+        let claimable = treasury_vault.lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        if amount > claimable {
+            return Err(SolanaVeilError::InsufficientFunds.into());
+        }
+
+        let vault_seeds = &[
+            b"treasury_vault".as_ref(),
+            pool_key.as_ref(),
+            &[ctx.bumps.treasury_vault],
+        ];
+        let vault_signer = &[&vault_seeds[..]];
+
+        invoke_signed(
+            &system_instruction::transfer(
+                treasury_vault.key,
+                ctx.accounts.destination.key,
+                amount,
+            ),
+            &[
+                treasury_vault.to_account_info(),
+                ctx.accounts.destination.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            vault_signer,
+        )?;
+    }
+
+    emit!(ProtocolFeesClaimedEvent {
+        pool: pool_key,
+        treasury: ctx.accounts.treasury.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + size_of::<Config>(),
+        seeds = [b"config".as_ref()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    /// Only required for native SOL pools
+    #[account(
+        mut,
+        seeds = [b"treasury_vault".as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pub treasury_vault: Option<SystemAccount<'info>>,
+
+    /// Only required for SPL token pools
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"treasury_vault".as_ref(), pool.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = config,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Only required for SPL token pools
+    #[account(
+        constraint = mint.key() == pool.mint @ SolanaVeilError::TokenAccountMintMismatch
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    /// Treasury authority recorded in the protocol config
+    pub treasury: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        has_one = treasury @ SolanaVeilError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub pool: Account<'info, Pool>,
+
+    /// Only required for native SOL pools
+    #[account(
+        mut,
+        seeds = [b"treasury_vault".as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pub treasury_vault: Option<SystemAccount<'info>>,
+
+    /// Only required for SPL token pools
+    #[account(
+        mut,
+        seeds = [b"treasury_vault".as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives claimed SOL
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+
+    /// Receives claimed tokens for SPL token pools
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == pool.mint @ SolanaVeilError::TokenAccountMintMismatch
+    )]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for SPL token pools
+    #[account(
+        constraint = mint.key() == pool.mint @ SolanaVeilError::TokenAccountMintMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
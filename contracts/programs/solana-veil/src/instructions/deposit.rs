@@ -1,25 +1,86 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::system_instruction;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
-use crate::error::*;
+use crate::errors::SolanaVeilError;
+use crate::screening;
+use crate::token_extensions;
+use crate::vault::SOL_VAULT_SEED;
+use crate::verifier;
+use crate::events::DepositCapReachedEvent;
+
+/// Most commitments a single `deposit_batch` call may insert
+pub const MAX_DEPOSIT_BATCH_SIZE: usize = 32;
 
 // Deposit funds into a pool
-pub fn deposit(
-    ctx: Context<Deposit>,
+pub fn deposit<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
     commitment: [u8; 32],
+) -> Result<()> {
+    deposit_commitments(ctx, &[commitment])
+}
+
+// Deposit several notes at once, pulling `commitments.len() * denomination` in one transfer
+pub fn deposit_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+    commitments: Vec<[u8; 32]>,
+) -> Result<()> {
+    if commitments.is_empty() || commitments.len() > MAX_DEPOSIT_BATCH_SIZE {
+        return Err(SolanaVeilError::InvalidBatchSize.into());
+    }
+    
+    deposit_commitments(ctx, &commitments)
+}
+
+// Shared deposit path: one transfer covering every commitment, then one leaf per commitment
+fn deposit_commitments<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+    commitments: &[[u8; 32]],
 ) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let tree = &mut ctx.accounts.tree;
     
-    // Check that the pool is active
-    if !pool.is_active {
-        return Err(SolanaVeilError::PoolInactive.into());
+    // Check that deposits are not paused
+    if pause_flags::is_paused(pool.pause_flags, pause_flags::DEPOSITS) {
+        return Err(SolanaVeilError::DepositsPaused.into());
+    }
+    
+    // Leading remaining accounts are the commitments' records, the rest belong to the
+    // screening policy
+    if commitments.len() > ctx.remaining_accounts.len() {
+        return Err(SolanaVeilError::InvalidCommitmentRecord.into());
+    }
+    let (commitment_records, policy_accounts) = ctx.remaining_accounts.split_at(commitments.len());
+    
+    // Run the depositor through the pool's screening policy, if any
+    if screening::is_screening_enabled(&pool.screening_program) {
+        let screening_program = ctx.accounts.screening_program.as_ref()
+            .ok_or(SolanaVeilError::InvalidScreeningProgram)?;
+        
+        screening::screen_address(
+            screening_program,
+            pool.key(),
+            ctx.accounts.user.key(),
+            policy_accounts,
+        )?;
     }
     
-    // Get the denomination amount
+    // Get the denomination amount and the total owed for all notes
     let denomination = pool.denomination;
+    let amount = denomination.checked_mul(commitments.len() as u64)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    
+    record_commitments(
+        &pool.key(),
+        commitment_records,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        commitments,
+    )?;
+    
+    // Enforce the pool's TVL cap
+    pool.check_deposit_cap(amount)?;
     
     // Handle deposit based on token type
     if pool.is_spl_token {
@@ -30,85 +91,147 @@ pub fn deposit(
         let user_token_account = ctx.accounts.user_token_account.as_ref()
             .ok_or(SolanaVeilError::InvalidTokenAccount)?;
         
-        let pool_token_account = ctx.accounts.pool_token_account.as_ref()
+        let pool_token_account = ctx.accounts.pool_token_account.as_mut()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+        
+        let mint = ctx.accounts.mint.as_ref()
             .ok_or(SolanaVeilError::InvalidTokenAccount)?;
         
+        // Gross up for Token-2022 transfer fees so the vault receives exactly the amount owed
+        let transfer_amount = token_extensions::gross_amount_for(&mint.to_account_info(), amount)?;
+        
         // Check if the user has enough tokens
-        if user_token_account.amount < denomination {
+        if user_token_account.amount < transfer_amount {
             return Err(SolanaVeilError::InsufficientFunds.into());
         }
         
+        let vault_balance_before = pool_token_account.amount;
+        
         // Transfer tokens to the pool
         let transfer_ctx = CpiContext::new(
             token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: user_token_account.to_account_info(),
+                mint: mint.to_account_info(),
                 to: pool_token_account.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         );
         
-        token::transfer(transfer_ctx, denomination)?;
+        token_interface::transfer_checked(transfer_ctx, transfer_amount, mint.decimals)?;
+        
+        // Reject the deposit unless the vault was credited with exactly the amount owed
+        pool_token_account.reload()?;
+        let received = pool_token_account.amount.checked_sub(vault_balance_before)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        if received != amount {
+            return Err(SolanaVeilError::DepositAmountMismatch.into());
+        }
     } else {
         // Native SOL deposit
         // Check if the user is sending enough SOL
-        if ctx.accounts.user.lamports() < denomination {
+        if ctx.accounts.user.lamports() < amount {
             return Err(SolanaVeilError::InsufficientFunds.into());
         }
         
-        // Transfer SOL to the pool account
+        let sol_vault = ctx.accounts.sol_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidVault)?;
+        
+        // Transfer SOL to the pool's vault; the pool data account never holds deposits
         invoke(
             &system_instruction::transfer(
                 ctx.accounts.user.key,
-                ctx.accounts.pool.to_account_info().key,
-                denomination,
+                sol_vault.key,
+                amount,
             ),
             &[
                 ctx.accounts.user.to_account_info(),
-                ctx.accounts.pool.to_account_info(),
+                sol_vault.to_account_info(),
                 ctx.accounts.system_program.to_account_info(),
             ],
         )?;
     }
     
     // Update pool statistics
-    pool.total_deposited = pool.total_deposited.checked_add(denomination)
+    pool.total_deposited = pool.total_deposited.checked_add(amount)
         .ok_or(SolanaVeilError::CalculationError)?;
     
-    // Insert the commitment into the merkle tree
-    let leaf_index = pool.next_index;
+    // Announce the cap once it leaves no room for another note
+    if pool.max_total_deposits > 0
+        && pool.max_total_deposits.saturating_sub(pool.outstanding_deposits()) < denomination
+    {
+        emit!(DepositCapReachedEvent {
+            pool: pool.key(),
+            max_total_deposits: pool.max_total_deposits,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
     
-    // Update pool's next index
-    pool.next_index = pool.next_index.checked_add(1)
-        .ok_or(SolanaVeilError::CalculationError)?;
+    append_commitments(pool, tree, &mut ctx.accounts.checkpoints, commitments)
+}
+
+// Reject commitments the circuits could not represent, and any already deposited
+// (including repeats within one call): creating each commitment's record fails if
+// the record already exists
+pub(crate) fn record_commitments<'info>(
+    pool: &Pubkey,
+    commitment_records: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    commitments: &[[u8; 32]],
+) -> Result<()> {
+    for (commitment, record) in commitments.iter().zip(commitment_records) {
+        if !verifier::is_canonical_field_element(commitment) {
+            return Err(SolanaVeilError::NonCanonicalCommitment.into());
+        }
+        CommitmentRecord::create(record, payer, system_program, pool, commitment)?;
+    }
     
-    // Update tree's leaf count
-    tree.num_leaves = tree.num_leaves.checked_add(1)
-        .ok_or(SolanaVeilError::CalculationError)?;
+    Ok(())
+}
+
+// Append already recorded commitments to the pool's tree, one leaf each.
+// Shared by deposits and inbound bridge transfers so every leaf goes through the
+// same batch hash chain, checkpoints and events.
+pub(crate) fn append_commitments<'info>(
+    pool: &Account<'info, Pool>,
+    tree: &mut Account<'info, MerkleTree>,
+    checkpoints: &mut DepositCheckpoints,
+    commitments: &[[u8; 32]],
+) -> Result<()> {
+    // Every leaf must fit in the tree
+    if tree.remaining_capacity() < commitments.len() as u64 {
+        return Err(SolanaVeilError::MerkleTreeFull.into());
+    }
     
-    // Emit an event for the ZK Compression system to insert the leaf
-    // This log will be parsed by the ZK Compression indexer
-    msg!("insert_compressed_leaf:{{\"tree_id\":\"{}\",\"leaf_index\":{},\"leaf\":\"{}\"}}",
-        tree.key().to_string(),
-        leaf_index,
-        format!("{:?}", commitment)
-    );
-    
-    // For now, we're not updating the merkle root on-chain since we're using ZK Compression
-    // In a real implementation, we would get the updated root from the ZK Compression system
-    
-    msg!("Deposit successful with commitment: {:?}", commitment);
-    msg!("Leaf index: {}", leaf_index);
-    
-    // Emit a Deposit event
-    emit!(DepositEvent {
-        pool: pool.key(),
-        tree: tree.key(),
-        commitment,
-        leaf_index,
-        amount: denomination,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
+    // Every leaf must also fit in the backlog of batches awaiting a root update
+    if tree.backlog_capacity() < commitments.len() as u64 {
+        return Err(SolanaVeilError::RootUpdateBacklogFull.into());
+    }
+    
+    let slot = Clock::get()?.slot;
+    let timestamp = Clock::get()?.unix_timestamp;
+    
+    // Checkpoint the leaf range so withdrawals can prove their deposit is old enough
+    let checkpoint_interval = pool.min_deposit_age_slots / CHECKPOINTS_PER_AGE_WINDOW;
+    checkpoints.record(slot, tree.num_leaves, checkpoint_interval);
+    
+    // Insert each commitment into the merkle tree
+    for commitment in commitments {
+        // Record the commitment in the tree's pending batch hash chain; the root is
+        // advanced later by `batch_update_root` with a proof over this chain
+        let leaf_index = tree.append_commitment(*commitment)?;
+        
+        // Indexers insert the leaf from this event
+        emit!(DepositEvent {
+            pool: pool.key(),
+            tree: tree.key(),
+            commitment: *commitment,
+            leaf_index,
+            amount: pool.denomination,
+            timestamp,
+        });
+    }
     
     Ok(())
 }
@@ -126,30 +249,58 @@ pub struct Deposit<'info> {
         constraint = tree.key() == pool.tree @ SolanaVeilError::InvalidMerkleTree,
         constraint = tree.pool == pool.key() @ SolanaVeilError::InvalidMerkleTree
     )]
-    pub tree: Account<'info, MerkleTree>,
+    pub tree: Box<Account<'info, MerkleTree>>,
+    
+    #[account(
+        mut,
+        seeds = [b"checkpoints".as_ref(), pool.key().as_ref()],
+        bump = checkpoints.bump
+    )]
+    pub checkpoints: Box<Account<'info, DepositCheckpoints>>,
     
     /// Only required for SPL token deposits
     #[account(
         mut,
-        constraint = pool.is_spl_token || user_token_account.is_none() @ SolanaVeilError::InvalidTokenAccount
+        constraint = pool.is_spl_token @ SolanaVeilError::InvalidTokenAccount
     )]
-    pub user_token_account: Option<Account<'info, TokenAccount>>,
+    pub user_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     
     /// Only required for SPL token deposits
     #[account(
         mut,
-        constraint = pool.is_spl_token || pool_token_account.is_none() @ SolanaVeilError::InvalidTokenAccount,
-        constraint = !pool.is_spl_token || (
-            pool_token_account.is_some() && 
-            pool_token_account.as_ref().unwrap().key() == pool.token_vault
-        ) @ SolanaVeilError::InvalidTokenAccount
+        constraint = pool.is_spl_token @ SolanaVeilError::InvalidTokenAccount,
+        constraint = pool_token_account.key() == pool.vault @ SolanaVeilError::InvalidTokenAccount
     )]
-    pub pool_token_account: Option<Account<'info, TokenAccount>>,
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     
-    pub system_program: Program<'info, System>,
+    /// Only required for native SOL deposits
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, pool.key().as_ref()],
+        bump = pool.vault_bump,
+        constraint = pool.is_spl_token || sol_vault.key() == pool.vault @ SolanaVeilError::InvalidVault
+    )]
+    pub sol_vault: Option<SystemAccount<'info>>,
     
     /// Only required for SPL token deposits
-    pub token_program: Option<Program<'info, Token>>,
+    #[account(
+        constraint = mint.key() == pool.mint @ SolanaVeilError::TokenAccountMintMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Only required if the pool has a screening program configured
+    /// CHECK: Must match `pool.screening_program`; policy accounts follow the commitment records in the remaining accounts
+    #[account(
+        executable,
+        constraint = screening_program.key() == pool.screening_program @ SolanaVeilError::InvalidScreeningProgram
+    )]
+    pub screening_program: Option<AccountInfo<'info>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Only required for SPL token deposits (SPL Token or Token-2022)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[event]
@@ -0,0 +1,607 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::instructions::config::MAX_PROTOCOL_FEE_BASIS_POINTS;
+use crate::verifier::verifying_key_input_count;
+
+/// Maximum pool relayer fee in basis points (5%)
+pub const MAX_POOL_FEE_BASIS_POINTS: u16 = 500;
+
+// === Authority transfers ===
+
+// Propose a new pool authority; takes effect once the new authority accepts
+pub fn propose_pool_authority(
+    ctx: Context<ProposePoolAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    pool.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposedEvent {
+        account: pool.key(),
+        authority: pool.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Accept a proposed pool authority transfer
+pub fn accept_pool_authority(ctx: Context<AcceptPoolAuthority>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let previous_authority = pool.authority;
+    pool.authority = pool.pending_authority;
+    pool.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferAcceptedEvent {
+        account: pool.key(),
+        previous_authority,
+        new_authority: pool.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Propose a new merkle tree authority; takes effect once the new authority accepts
+pub fn propose_tree_authority(
+    ctx: Context<ProposeTreeAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let tree = &mut ctx.accounts.tree;
+    tree.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposedEvent {
+        account: tree.key(),
+        authority: tree.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Accept a proposed merkle tree authority transfer
+pub fn accept_tree_authority(ctx: Context<AcceptTreeAuthority>) -> Result<()> {
+    let tree = &mut ctx.accounts.tree;
+    let previous_authority = tree.authority;
+    tree.authority = tree.pending_authority;
+    tree.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferAcceptedEvent {
+        account: tree.key(),
+        previous_authority,
+        new_authority: tree.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Propose a new bridge authority; takes effect once the new authority accepts
+pub fn propose_bridge_authority(
+    ctx: Context<ProposeBridgeAuthority>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    bridge_config.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposedEvent {
+        account: bridge_config.key(),
+        authority: bridge_config.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Accept a proposed bridge authority transfer
+pub fn accept_bridge_authority(ctx: Context<AcceptBridgeAuthority>) -> Result<()> {
+    let bridge_config = &mut ctx.accounts.bridge_config;
+    let previous_authority = bridge_config.authority;
+    bridge_config.authority = bridge_config.pending_authority;
+    bridge_config.pending_authority = Pubkey::default();
+
+    emit!(AuthorityTransferAcceptedEvent {
+        account: bridge_config.key(),
+        previous_authority,
+        new_authority: bridge_config.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// === Timelocked admin actions ===
+
+/// Account an action applies to and the role allowed to queue or cancel it
+fn action_target(
+    action: &AdminAction,
+    config: &Account<Config>,
+    bridge_config: Option<&Account<BridgeConfig>>,
+    pool: Option<&Account<Pool>>,
+) -> Result<(Pubkey, Role)> {
+    match action {
+        AdminAction::ProtocolFee { .. } | AdminAction::ProtocolTreasury { .. } => {
+            Ok((config.key(), Role::FeeManager))
+        }
+        AdminAction::AdminTimelock { .. } => Ok((config.key(), Role::Admin)),
+        AdminAction::BridgeFee { .. } | AdminAction::BridgeTreasury { .. } => {
+            let bridge_config = bridge_config.ok_or(SolanaVeilError::InvalidPendingAction)?;
+            Ok((bridge_config.key(), Role::FeeManager))
+        }
+        AdminAction::PoolMaxFee { .. } => {
+            let pool = pool.ok_or(SolanaVeilError::InvalidPendingAction)?;
+            Ok((pool.key(), Role::FeeManager))
+        }
+        AdminAction::VerifyingKey { .. } => {
+            let pool = pool.ok_or(SolanaVeilError::InvalidPendingAction)?;
+            Ok((pool.key(), Role::KeyManager))
+        }
+    }
+}
+
+/// Reject actions whose values could never be applied
+fn validate_action(action: &AdminAction) -> Result<()> {
+    match *action {
+        AdminAction::ProtocolFee { fee_basis_points } => {
+            if fee_basis_points > MAX_PROTOCOL_FEE_BASIS_POINTS {
+                return Err(SolanaVeilError::FeeTooHigh.into());
+            }
+        }
+        AdminAction::AdminTimelock { seconds } => {
+            if !(0..=MAX_ADMIN_TIMELOCK_SECONDS).contains(&seconds) {
+                return Err(SolanaVeilError::InvalidTimelock.into());
+            }
+        }
+        AdminAction::BridgeFee { fee_basis_points } => {
+            if fee_basis_points > 10000 {
+                return Err(SolanaVeilError::FeeTooHigh.into());
+            }
+        }
+        AdminAction::PoolMaxFee { max_fee_basis_points } => {
+            if max_fee_basis_points > MAX_POOL_FEE_BASIS_POINTS {
+                return Err(SolanaVeilError::FeeTooHigh.into());
+            }
+        }
+        AdminAction::ProtocolTreasury { .. }
+        | AdminAction::BridgeTreasury { .. }
+        | AdminAction::VerifyingKey { .. } => {}
+    }
+    Ok(())
+}
+
+// Queue a fee, treasury, timelock or verifying key change behind the admin timelock
+pub fn queue_admin_action(
+    ctx: Context<QueueAdminAction>,
+    action: AdminAction,
+) -> Result<()> {
+    validate_action(&action)?;
+
+    let (target, role) = action_target(
+        &action,
+        &ctx.accounts.config,
+        ctx.accounts.bridge_config.as_ref(),
+        ctx.accounts.pool.as_ref(),
+    )?;
+    if target != ctx.accounts.target.key() {
+        return Err(SolanaVeilError::InvalidPendingAction.into());
+    }
+    let proposer = ctx.accounts.authority.key();
+    ctx.accounts.access_control.require_role(&proposer, role)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let executable_at = now.checked_add(ctx.accounts.config.admin_timelock_seconds)
+        .ok_or(SolanaVeilError::CalculationError)?;
+
+    let pending_action = &mut ctx.accounts.pending_action;
+    pending_action.target = target;
+    pending_action.proposer = proposer;
+    pending_action.action = action;
+    pending_action.queued_at = now;
+    pending_action.executable_at = executable_at;
+    pending_action.bump = ctx.bumps.pending_action;
+
+    emit!(AdminActionQueuedEvent {
+        pending_action: pending_action.key(),
+        target,
+        action,
+        executable_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// Drop a queued action before it is executed
+pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+    let pending_action = &ctx.accounts.pending_action;
+
+    let (target, role) = action_target(
+        &pending_action.action,
+        &ctx.accounts.config,
+        ctx.accounts.bridge_config.as_ref(),
+        ctx.accounts.pool.as_ref(),
+    )?;
+    if target != pending_action.target {
+        return Err(SolanaVeilError::InvalidPendingAction.into());
+    }
+    let authority = ctx.accounts.authority.key();
+    ctx.accounts.access_control.require_role(&authority, role)?;
+
+    emit!(AdminActionCancelledEvent {
+        pending_action: pending_action.key(),
+        target,
+        action: pending_action.action,
+        cancelled_by: authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Apply a queued action once its timelock has elapsed. Anyone can execute;
+// the change itself was already authorized when it was queued.
+pub fn execute_admin_action(
+    ctx: Context<ExecuteAdminAction>,
+    verifying_key_data: Option<Vec<u8>>,
+) -> Result<()> {
+    let pending_action = &ctx.accounts.pending_action;
+    let target = pending_action.target;
+    let action = pending_action.action;
+
+    let now = Clock::get()?.unix_timestamp;
+    if now < pending_action.executable_at {
+        return Err(SolanaVeilError::TimelockNotElapsed.into());
+    }
+
+    // Bounds may have been tightened since the action was queued
+    validate_action(&action)?;
+
+    match action {
+        AdminAction::ProtocolFee { fee_basis_points } => {
+            let config = &mut ctx.accounts.config;
+            if config.key() != target {
+                return Err(SolanaVeilError::InvalidPendingAction.into());
+            }
+            config.protocol_fee_basis_points = fee_basis_points;
+        }
+        AdminAction::ProtocolTreasury { treasury } => {
+            let config = &mut ctx.accounts.config;
+            if config.key() != target {
+                return Err(SolanaVeilError::InvalidPendingAction.into());
+            }
+            config.treasury = treasury;
+        }
+        AdminAction::AdminTimelock { seconds } => {
+            let config = &mut ctx.accounts.config;
+            if config.key() != target {
+                return Err(SolanaVeilError::InvalidPendingAction.into());
+            }
+            config.admin_timelock_seconds = seconds;
+        }
+        AdminAction::BridgeFee { fee_basis_points } => {
+            let bridge_config = ctx.accounts.bridge_config.as_mut()
+                .filter(|bridge_config| bridge_config.key() == target)
+                .ok_or(SolanaVeilError::InvalidPendingAction)?;
+            bridge_config.fee_basis_points = fee_basis_points;
+        }
+        AdminAction::BridgeTreasury { treasury } => {
+            let bridge_config = ctx.accounts.bridge_config.as_mut()
+                .filter(|bridge_config| bridge_config.key() == target)
+                .ok_or(SolanaVeilError::InvalidPendingAction)?;
+            bridge_config.treasury = treasury;
+        }
+        AdminAction::PoolMaxFee { max_fee_basis_points } => {
+            let pool = ctx.accounts.pool.as_mut()
+                .filter(|pool| pool.key() == target)
+                .ok_or(SolanaVeilError::InvalidPendingAction)?;
+            pool.max_fee_basis_points = max_fee_basis_points;
+        }
+        AdminAction::VerifyingKey { circuit, key_hash } => {
+            let verifying_key = ctx.accounts.verifying_key.as_mut()
+                .filter(|verifying_key| verifying_key.pool == target && verifying_key.circuit == circuit)
+                .ok_or(SolanaVeilError::InvalidPendingAction)?;
+            let data = verifying_key_data.ok_or(SolanaVeilError::VerifyingKeyHashMismatch)?;
+
+            if data.len() > MAX_VERIFYING_KEY_LEN {
+                return Err(SolanaVeilError::VerifyingKeyTooLarge.into());
+            }
+            if verifying_key_input_count(&data).is_none() {
+                return Err(SolanaVeilError::InvalidVerifyingKey.into());
+            }
+            if keccak::hash(&data).to_bytes() != key_hash {
+                return Err(SolanaVeilError::VerifyingKeyHashMismatch.into());
+            }
+
+            verifying_key.key_hash = key_hash;
+            verifying_key.data = data;
+            verifying_key.updated_at = now;
+        }
+    }
+
+    emit!(AdminActionExecutedEvent {
+        pending_action: pending_action.key(),
+        target,
+        action,
+        executed_by: ctx.accounts.executor.key(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// Store a pool's initial verifying key (KeyManager only); later changes go through the admin timelock
+pub fn initialize_verifying_key(
+    ctx: Context<InitializeVerifyingKey>,
+    circuit: VerifierCircuit,
+    data: Vec<u8>,
+) -> Result<()> {
+    if data.len() > MAX_VERIFYING_KEY_LEN {
+        return Err(SolanaVeilError::VerifyingKeyTooLarge.into());
+    }
+    if verifying_key_input_count(&data).is_none() {
+        return Err(SolanaVeilError::InvalidVerifyingKey.into());
+    }
+
+    let verifying_key = &mut ctx.accounts.verifying_key;
+    verifying_key.pool = ctx.accounts.pool.key();
+    verifying_key.circuit = circuit;
+    verifying_key.key_hash = keccak::hash(&data).to_bytes();
+    verifying_key.data = data;
+    verifying_key.updated_at = Clock::get()?.unix_timestamp;
+    verifying_key.bump = ctx.bumps.verifying_key;
+
+    msg!("Initialized verifying key for pool: {}", verifying_key.pool);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposePoolAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptPoolAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = pool.pending_authority == new_authority.key() @ SolanaVeilError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeTreeAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub tree: Box<Account<'info, MerkleTree>>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptTreeAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = tree.pending_authority == new_authority.key() @ SolanaVeilError::Unauthorized
+    )]
+    pub tree: Box<Account<'info, MerkleTree>>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeBridgeAuthority<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bridge_config".as_ref()],
+        bump = bridge_config.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptBridgeAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"bridge_config".as_ref()],
+        bump = bridge_config.bump,
+        constraint = bridge_config.pending_authority == new_authority.key() @ SolanaVeilError::Unauthorized
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(action: AdminAction)]
+pub struct QueueAdminAction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// CHECK: Config, bridge config or pool the action applies to; matched against the action in the handler
+    pub target: UncheckedAccount<'info>,
+
+    /// Only required for bridge actions
+    #[account(
+        seeds = [b"bridge_config".as_ref()],
+        bump = bridge_config.bump
+    )]
+    pub bridge_config: Option<Account<'info, BridgeConfig>>,
+
+    /// Only required for pool actions
+    pub pool: Option<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PendingAction::LEN,
+        seeds = [
+            b"pending_action".as_ref(),
+            target.key().as_ref(),
+            &[action.kind()],
+        ],
+        bump
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminAction<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// Only required for bridge actions
+    #[account(
+        seeds = [b"bridge_config".as_ref()],
+        bump = bridge_config.bump
+    )]
+    pub bridge_config: Option<Account<'info, BridgeConfig>>,
+
+    /// Only required for pool actions
+    pub pool: Option<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pending_action".as_ref(),
+            pending_action.target.as_ref(),
+            &[pending_action.action.kind()],
+        ],
+        bump = pending_action.bump,
+        has_one = proposer @ SolanaVeilError::InvalidPendingAction,
+        close = proposer
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    /// CHECK: Receives the pending action's rent; must match `pending_action.proposer`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Only required for bridge actions
+    #[account(
+        mut,
+        seeds = [b"bridge_config".as_ref()],
+        bump = bridge_config.bump
+    )]
+    pub bridge_config: Option<Account<'info, BridgeConfig>>,
+
+    /// Only required for pool fee actions
+    #[account(mut)]
+    pub pool: Option<Account<'info, Pool>>,
+
+    /// Only required for verifying key actions
+    #[account(
+        mut,
+        seeds = [
+            b"verifying_key".as_ref(),
+            verifying_key.pool.as_ref(),
+            &[verifying_key.circuit as u8],
+        ],
+        bump = verifying_key.bump
+    )]
+    pub verifying_key: Option<Box<Account<'info, VerifyingKeyAccount>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"pending_action".as_ref(),
+            pending_action.target.as_ref(),
+            &[pending_action.action.kind()],
+        ],
+        bump = pending_action.bump,
+        has_one = proposer @ SolanaVeilError::InvalidPendingAction,
+        close = proposer
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    /// CHECK: Receives the pending action's rent; must match `pending_action.proposer`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(circuit: VerifierCircuit)]
+pub struct InitializeVerifyingKey<'info> {
+    #[account(mut)]
+    pub key_manager: Signer<'info>,
+
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&key_manager.key(), Role::KeyManager) @ SolanaVeilError::MissingRole
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = key_manager,
+        space = 8 + VerifyingKeyAccount::LEN,
+        seeds = [
+            b"verifying_key".as_ref(),
+            pool.key().as_ref(),
+            &[circuit as u8],
+        ],
+        bump
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
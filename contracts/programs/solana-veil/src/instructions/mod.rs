@@ -1,19 +1,25 @@
-pub mod pool;
 pub mod deposit;
 pub mod withdraw;
 pub mod tree;
 pub mod relayer;
 pub mod bridge;
-pub mod deposit;
-pub mod withdraw;
-pub mod pool;
-pub mod tree;
-pub mod relayer;
-pub mod bridge; // Add the new bridge module
+pub mod config;
+pub mod registry;
+pub mod governance;
+pub mod access_control;
+pub mod recipient_program;
+pub mod relayer_directory;
+pub mod sol_fee;
 
 pub use deposit::*;
 pub use withdraw::*;
-pub use pool::*;
 pub use tree::*;
 pub use relayer::*;
-pub use bridge::*; // Export bridge instructions
\ No newline at end of file
+pub use bridge::*; // Export bridge instructions
+pub use config::*;
+pub use registry::*;
+pub use governance::*;
+pub use access_control::*;
+pub use recipient_program::*;
+pub use relayer_directory::*;
+pub use sol_fee::*;
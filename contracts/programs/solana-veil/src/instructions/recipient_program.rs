@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+// Whitelist a program that withdrawals may call back into (Admin only)
+pub fn add_recipient_program(ctx: Context<AddRecipientProgram>) -> Result<()> {
+    let recipient_program = &mut ctx.accounts.recipient_program;
+    let program_id = ctx.accounts.program.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    recipient_program.program_id = program_id;
+    recipient_program.added_by = ctx.accounts.admin.key();
+    recipient_program.added_at = timestamp;
+    recipient_program.bump = ctx.bumps.recipient_program;
+
+    emit!(RecipientProgramAddedEvent {
+        program_id,
+        added_by: ctx.accounts.admin.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// Remove a program from the withdrawal callback whitelist (Admin only)
+pub fn remove_recipient_program(ctx: Context<RemoveRecipientProgram>) -> Result<()> {
+    emit!(RecipientProgramRemovedEvent {
+        program_id: ctx.accounts.recipient_program.program_id,
+        removed_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddRecipientProgram<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&admin.key(), Role::Admin) @ SolanaVeilError::MissingRole
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    /// CHECK: Only its address is recorded; it must be a deployed program
+    #[account(executable)]
+    pub program: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RecipientProgram::LEN,
+        seeds = [b"recipient_program".as_ref(), program.key().as_ref()],
+        bump
+    )]
+    pub recipient_program: Account<'info, RecipientProgram>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveRecipientProgram<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&admin.key(), Role::Admin) @ SolanaVeilError::MissingRole
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"recipient_program".as_ref(), recipient_program.program_id.as_ref()],
+        bump = recipient_program.bump
+    )]
+    pub recipient_program: Account<'info, RecipientProgram>,
+}
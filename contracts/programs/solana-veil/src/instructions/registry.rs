@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+// Create the pool registry header (one per program)
+pub fn initialize_pool_registry(ctx: Context<InitializePoolRegistry>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry;
+    registry.pool_count = 0;
+    registry.bump = ctx.bumps.registry;
+
+    msg!("Initialized pool registry");
+
+    Ok(())
+}
+
+/// Append a newly created pool to the registry, returning its registry index
+pub fn register_pool(
+    registry: &mut PoolRegistry,
+    page: &mut PoolRegistryPage,
+    page_bump: u8,
+    entry: PoolRegistryEntry,
+) -> Result<u64> {
+    let registry_index = registry.pool_count;
+
+    // A fresh page is created by `init_if_needed` whenever the previous one filled up
+    if page.entries.is_empty() {
+        page.page_index = registry.next_page_index();
+        page.bump = page_bump;
+    }
+    if page.entries.len() >= MAX_POOLS_PER_REGISTRY_PAGE {
+        return Err(SolanaVeilError::RegistryPageFull.into());
+    }
+    page.entries.push(entry);
+
+    registry.pool_count = registry.pool_count.checked_add(1)
+        .ok_or(SolanaVeilError::CalculationError)?;
+
+    emit!(PoolRegisteredEvent {
+        pool: entry.pool,
+        mint: entry.mint,
+        denomination: entry.denomination,
+        registry_index,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(registry_index)
+}
+
+/// Locate a pool's entry on its registry page
+pub fn registry_entry_mut<'a>(
+    page: &'a mut PoolRegistryPage,
+    pool: &Pool,
+) -> Result<&'a mut PoolRegistryEntry> {
+    let offset = (pool.registry_index % MAX_POOLS_PER_REGISTRY_PAGE as u64) as usize;
+    page.entries.get_mut(offset)
+        .ok_or(SolanaVeilError::InvalidRegistryPage.into())
+}
+
+#[derive(Accounts)]
+pub struct InitializePoolRegistry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PoolRegistry::LEN,
+        seeds = [b"pool_registry".as_ref()],
+        bump
+    )]
+    pub registry: Account<'info, PoolRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,6 +1,40 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, set_return_data};
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::state::*;
-use crate::error::*;
+use crate::errors::SolanaVeilError;
+use crate::events::*;
+use crate::vault::{sol_vault_rent_reserve, PoolVault};
+
+/// Seed prefix of a relayer's stake escrow: a data-less PDA for SOL stakes,
+/// or a token account owned by the relayer config for SPL stakes
+pub const RELAYER_STAKE_SEED: &[u8] = b"relayer_stake";
+
+// Create the relayer staking config (protocol authority only)
+pub fn initialize_relayer_config(
+    ctx: Context<InitializeRelayerConfig>,
+    required_stake: u64,
+    unstake_cooldown_slots: u64,
+) -> Result<()> {
+    let relayer_config = &mut ctx.accounts.relayer_config;
+    relayer_config.authority = ctx.accounts.authority.key();
+    relayer_config.required_stake = required_stake;
+    relayer_config.stake_mint = ctx.accounts.stake_mint.as_ref()
+        .map(|mint| mint.key())
+        .unwrap_or_default();
+    relayer_config.unstake_cooldown_slots = unstake_cooldown_slots;
+    relayer_config.total_slashed = 0;
+    relayer_config.bump = ctx.bumps.relayer_config;
+    
+    emit!(RelayerConfigInitializedEvent {
+        authority: relayer_config.authority,
+        required_stake,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
 
 // Register or update a relayer
 pub fn set_relayer(
@@ -13,8 +47,22 @@ pub fn set_relayer(
         return Err(SolanaVeilError::FeeTooHigh.into());
     }
     
-    // Update relayer account
     let relayer = &mut ctx.accounts.relayer;
+    
+    // Only a fully staked relayer that is not on its way out can be activated
+    if is_active {
+        if relayer.unstake_requested_slot != 0 {
+            return Err(SolanaVeilError::UnstakePending.into());
+        }
+    
+        if relayer.stake_amount < ctx.accounts.relayer_config.required_stake {
+            return Err(SolanaVeilError::InsufficientStake.into());
+        }
+    }
+    
+    let was_active = relayer.is_active;
+    
+    // Update relayer account
     relayer.authority = ctx.accounts.authority.key();
     relayer.is_active = is_active;
     relayer.fee_basis_points = fee_basis_points;
@@ -25,7 +73,15 @@ pub fn set_relayer(
         relayer.total_fees = 0;
     }
     
-    relayer.bump = *ctx.bumps.get("relayer").unwrap();
+    relayer.bump = ctx.bumps.relayer;
+    
+    if is_active && !was_active {
+        emit!(RelayerRegisteredEvent {
+            relayer: relayer.key(),
+            stake_amount: relayer.stake_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
     
     msg!("Relayer {} set to {} with fee basis points: {}",
         relayer.key(),
@@ -36,12 +92,364 @@ pub fn set_relayer(
     Ok(())
 }
 
+// Add stake to the relayer's escrow
+pub fn stake_relayer(
+    ctx: Context<StakeRelayer>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Err(SolanaVeilError::InsufficientStake.into());
+    }
+    
+    if ctx.accounts.relayer.unstake_requested_slot != 0 {
+        return Err(SolanaVeilError::UnstakePending.into());
+    }
+    
+    let credited = if ctx.accounts.relayer_config.is_native_stake() {
+        let stake_vault = ctx.accounts.stake_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidVault)?;
+    
+        // The first stake also funds the escrow's rent reserve, which is never paid out
+        let top_up = sol_vault_rent_reserve()?.saturating_sub(stake_vault.lamports());
+        let total = amount.checked_add(top_up)
+            .ok_or(SolanaVeilError::CalculationError)?;
+    
+        if ctx.accounts.authority.lamports() < total {
+            return Err(SolanaVeilError::InsufficientFunds.into());
+        }
+    
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.authority.key,
+                stake_vault.key,
+                total,
+            ),
+            &[
+                ctx.accounts.authority.to_account_info(),
+                stake_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    
+        amount
+    } else {
+        let token_program = ctx.accounts.token_program.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+        let authority_token_account = ctx.accounts.authority_token_account.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+        let stake_token_account = ctx.accounts.stake_token_account.as_mut()
+            .ok_or(SolanaVeilError::InvalidVault)?;
+        let stake_mint = ctx.accounts.stake_mint.as_ref()
+            .ok_or(SolanaVeilError::InvalidStakeMint)?;
+    
+        let escrow_balance_before = stake_token_account.amount;
+    
+        let transfer_ctx = CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: authority_token_account.to_account_info(),
+                mint: stake_mint.to_account_info(),
+                to: stake_token_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        );
+        token_interface::transfer_checked(transfer_ctx, amount, stake_mint.decimals)?;
+    
+        // Credit what the escrow actually received (Token-2022 transfer fees are withheld)
+        stake_token_account.reload()?;
+        stake_token_account.amount.checked_sub(escrow_balance_before)
+            .ok_or(SolanaVeilError::CalculationError)?
+    };
+    
+    let relayer = &mut ctx.accounts.relayer;
+    relayer.stake_amount = relayer.stake_amount.checked_add(credited)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    
+    emit!(RelayerStakedEvent {
+        relayer: relayer.key(),
+        amount: credited,
+        stake_amount: relayer.stake_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// Deactivate the relayer and start the unstake cooldown
+pub fn request_unstake(ctx: Context<RequestUnstake>) -> Result<()> {
+    let relayer = &mut ctx.accounts.relayer;
+    
+    if relayer.unstake_requested_slot != 0 {
+        return Err(SolanaVeilError::UnstakePending.into());
+    }
+    
+    if relayer.stake_amount == 0 {
+        return Err(SolanaVeilError::InsufficientStake.into());
+    }
+    
+    let requested_slot = Clock::get()?.slot;
+    relayer.is_active = false;
+    relayer.unstake_requested_slot = requested_slot;
+    
+    emit!(RelayerUnstakeRequestedEvent {
+        relayer: relayer.key(),
+        stake_amount: relayer.stake_amount,
+        requested_slot,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// Release the relayer's stake once the cooldown has passed without a slash
+pub fn complete_unstake(ctx: Context<CompleteUnstake>) -> Result<()> {
+    let relayer = &ctx.accounts.relayer;
+    let relayer_config = &ctx.accounts.relayer_config;
+    
+    if relayer.unstake_requested_slot == 0 {
+        return Err(SolanaVeilError::UnstakeNotRequested.into());
+    }
+    
+    // A slash during the cooldown restarts it
+    let cooldown_start = relayer.unstake_requested_slot.max(relayer.last_slashed_slot);
+    let current_slot = Clock::get()?.slot;
+    if current_slot < cooldown_start.saturating_add(relayer_config.unstake_cooldown_slots) {
+        return Err(SolanaVeilError::UnstakeCooldownNotElapsed.into());
+    }
+    
+    let amount = relayer.stake_amount;
+    let authority_key = ctx.accounts.authority.key();
+    let stake_vault_bump = [ctx.bumps.stake_vault];
+    let stake_vault_seeds: &[&[u8]] = &[
+        RELAYER_STAKE_SEED,
+        authority_key.as_ref(),
+        &stake_vault_bump,
+    ];
+    let relayer_config_bump = [relayer_config.bump];
+    let relayer_config_seeds: &[&[u8]] = &[
+        b"relayer_config".as_ref(),
+        &relayer_config_bump,
+    ];
+    
+    let escrow = stake_escrow(
+        relayer_config,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.stake_token_account,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        stake_vault_seeds,
+        relayer_config_seeds,
+    )?;
+    escrow.ensure_available(amount)?;
+    
+    let destination = if relayer_config.is_native_stake() {
+        Some(ctx.accounts.authority.to_account_info())
+    } else {
+        ctx.accounts.authority_token_account.as_ref().map(|account| account.to_account_info())
+    }.ok_or(SolanaVeilError::InvalidTokenAccount)?;
+    
+    escrow.pay(&destination, amount)?;
+    
+    let relayer = &mut ctx.accounts.relayer;
+    relayer.stake_amount = 0;
+    relayer.unstake_requested_slot = 0;
+    
+    emit!(RelayerUnstakedEvent {
+        relayer: relayer.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// Slash a misbehaving relayer's stake to the protocol treasury (Admin only).
+// `evidence_hash` commits to the off-chain evidence (e.g. the offending transactions).
+pub fn slash_relayer(
+    ctx: Context<SlashRelayer>,
+    amount: u64,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    if evidence_hash == [0u8; 32] {
+        return Err(SolanaVeilError::InvalidSlash.into());
+    }
+    
+    let relayer = &ctx.accounts.relayer;
+    let relayer_config = &ctx.accounts.relayer_config;
+    
+    let amount = amount.min(relayer.stake_amount);
+    if amount == 0 {
+        return Err(SolanaVeilError::InvalidSlash.into());
+    }
+    
+    let relayer_authority = relayer.authority;
+    let stake_vault_bump = [ctx.bumps.stake_vault];
+    let stake_vault_seeds: &[&[u8]] = &[
+        RELAYER_STAKE_SEED,
+        relayer_authority.as_ref(),
+        &stake_vault_bump,
+    ];
+    let relayer_config_bump = [relayer_config.bump];
+    let relayer_config_seeds: &[&[u8]] = &[
+        b"relayer_config".as_ref(),
+        &relayer_config_bump,
+    ];
+    
+    let escrow = stake_escrow(
+        relayer_config,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.stake_token_account,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        stake_vault_seeds,
+        relayer_config_seeds,
+    )?;
+    escrow.ensure_available(amount)?;
+    
+    let treasury_destination = if relayer_config.is_native_stake() {
+        ctx.accounts.treasury.as_ref().map(|account| account.to_account_info())
+    } else {
+        ctx.accounts.treasury_token_account.as_ref().map(|account| account.to_account_info())
+    }.ok_or(SolanaVeilError::InvalidTreasuryVault)?;
+    
+    escrow.pay(&treasury_destination, amount)?;
+    
+    let required_stake = relayer_config.required_stake;
+    let relayer = &mut ctx.accounts.relayer;
+    relayer.stake_amount -= amount;
+    relayer.last_slashed_slot = Clock::get()?.slot;
+    relayer.slash_count = relayer.slash_count.checked_add(1)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    
+    // A relayer left under the required stake must top up before relaying again
+    if relayer.stake_amount < required_stake {
+        relayer.is_active = false;
+    }
+    
+    let relayer_config = &mut ctx.accounts.relayer_config;
+    relayer_config.total_slashed = relayer_config.total_slashed.checked_add(amount)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    
+    emit!(RelayerSlashedEvent {
+        relayer: relayer.key(),
+        amount,
+        evidence_hash,
+        slashed_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// Return a relayer's counters, and optionally its counters for one mint, as return data
+pub fn get_relayer_stats(ctx: Context<GetRelayerStats>) -> Result<()> {
+    let relayer = &ctx.accounts.relayer;
+    let mut view = RelayerStatsView {
+        relayer: relayer.key(),
+        is_active: relayer.is_active,
+        stake_amount: relayer.stake_amount,
+        successful_withdrawals: relayer.successful_withdrawals,
+        total_relayed: relayer.total_relayed,
+        total_fees: relayer.total_fees,
+        last_active_slot: relayer.last_active_slot,
+        slash_count: relayer.slash_count,
+        last_slashed_slot: relayer.last_slashed_slot,
+        ..RelayerStatsView::default()
+    };
+    
+    if let Some(mint_stats) = &ctx.accounts.relayer_mint_stats {
+        view.mint = mint_stats.mint;
+        view.mint_withdrawals = mint_stats.withdrawals;
+        view.mint_total_relayed = mint_stats.total_relayed;
+        view.mint_total_fees = mint_stats.total_fees;
+    }
+    
+    set_return_data(&view.try_to_vec()?);
+    
+    Ok(())
+}
+
+// Payout source for a relayer's stake escrow
+#[allow(clippy::too_many_arguments)]
+fn stake_escrow<'a, 'info>(
+    relayer_config: &Account<'info, RelayerConfig>,
+    stake_vault: &Option<SystemAccount<'info>>,
+    stake_token_account: &Option<InterfaceAccount<'info, TokenAccount>>,
+    stake_mint: &Option<InterfaceAccount<'info, Mint>>,
+    token_program: &Option<Interface<'info, TokenInterface>>,
+    system_program: &Program<'info, System>,
+    stake_vault_seeds: &'a [&'a [u8]],
+    relayer_config_seeds: &'a [&'a [u8]],
+) -> Result<PoolVault<'a, 'info>> {
+    if relayer_config.is_native_stake() {
+        let stake_vault = stake_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidVault)?;
+    
+        Ok(PoolVault::Native {
+            vault: stake_vault.to_account_info(),
+            system_program: system_program.to_account_info(),
+            signer_seeds: stake_vault_seeds,
+        })
+    } else {
+        let stake_token_account = stake_token_account.as_ref()
+            .ok_or(SolanaVeilError::InvalidVault)?;
+        let stake_mint = stake_mint.as_ref()
+            .ok_or(SolanaVeilError::InvalidStakeMint)?;
+        let token_program = token_program.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+    
+        Ok(PoolVault::Token {
+            vault: stake_token_account.to_account_info(),
+            mint: stake_mint.to_account_info(),
+            decimals: stake_mint.decimals,
+            authority: relayer_config.to_account_info(),
+            token_program: token_program.to_account_info(),
+            signer_seeds: relayer_config_seeds,
+        })
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeRelayerConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RelayerConfig::LEN,
+        seeds = [b"relayer_config".as_ref()],
+        bump
+    )]
+    pub relayer_config: Account<'info, RelayerConfig>,
+    
+    /// Only required when stakes are held in an SPL token
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 #[instruction(is_active: bool, fee_basis_points: u16)]
 pub struct SetRelayer<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [b"relayer_config".as_ref()],
+        bump = relayer_config.bump
+    )]
+    pub relayer_config: Account<'info, RelayerConfig>,
+    
     #[account(
         init_if_needed,
         payer = authority,
@@ -55,4 +463,226 @@ pub struct SetRelayer<'info> {
     pub relayer: Account<'info, Relayer>,
     
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct StakeRelayer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"relayer_config".as_ref()],
+        bump = relayer_config.bump
+    )]
+    pub relayer_config: Account<'info, RelayerConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer".as_ref(), authority.key().as_ref()],
+        bump = relayer.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub relayer: Account<'info, Relayer>,
+    
+    /// Only required for SOL stakes
+    #[account(
+        mut,
+        seeds = [RELAYER_STAKE_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Option<SystemAccount<'info>>,
+    
+    /// Only required for SPL stakes
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [RELAYER_STAKE_SEED, authority.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = relayer_config,
+        token::token_program = token_program
+    )]
+    pub stake_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for SPL stakes
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ SolanaVeilError::InvalidTokenAccount
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for SPL stakes
+    #[account(
+        constraint = stake_mint.key() == relayer_config.stake_mint @ SolanaVeilError::InvalidStakeMint,
+        mint::token_program = token_program
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Only required for SPL stakes (SPL Token or Token-2022)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer".as_ref(), authority.key().as_ref()],
+        bump = relayer.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub relayer: Account<'info, Relayer>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteUnstake<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"relayer_config".as_ref()],
+        bump = relayer_config.bump
+    )]
+    pub relayer_config: Account<'info, RelayerConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer".as_ref(), authority.key().as_ref()],
+        bump = relayer.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub relayer: Account<'info, Relayer>,
+    
+    /// Only required for SOL stakes
+    #[account(
+        mut,
+        seeds = [RELAYER_STAKE_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Option<SystemAccount<'info>>,
+    
+    /// Only required for SPL stakes
+    #[account(
+        mut,
+        seeds = [RELAYER_STAKE_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub stake_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for SPL stakes; receives the released stake
+    #[account(
+        mut,
+        constraint = authority_token_account.owner == authority.key() @ SolanaVeilError::InvalidTokenAccount
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for SPL stakes
+    #[account(
+        constraint = stake_mint.key() == relayer_config.stake_mint @ SolanaVeilError::InvalidStakeMint,
+        mint::token_program = token_program
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Only required for SPL stakes (SPL Token or Token-2022)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct SlashRelayer<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&admin.key(), Role::Admin) @ SolanaVeilError::MissingRole
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+    
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer_config".as_ref()],
+        bump = relayer_config.bump
+    )]
+    pub relayer_config: Account<'info, RelayerConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"relayer".as_ref(), relayer.authority.as_ref()],
+        bump = relayer.bump
+    )]
+    pub relayer: Account<'info, Relayer>,
+    
+    /// Only required for SOL stakes
+    #[account(
+        mut,
+        seeds = [RELAYER_STAKE_SEED, relayer.authority.as_ref()],
+        bump
+    )]
+    pub stake_vault: Option<SystemAccount<'info>>,
+    
+    /// Only required for SPL stakes
+    #[account(
+        mut,
+        seeds = [RELAYER_STAKE_SEED, relayer.authority.as_ref()],
+        bump
+    )]
+    pub stake_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Protocol treasury receiving slashed SOL stakes
+    #[account(
+        mut,
+        address = config.treasury @ SolanaVeilError::InvalidTreasuryVault
+    )]
+    pub treasury: Option<SystemAccount<'info>>,
+    
+    /// Treasury token account receiving slashed SPL stakes
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ SolanaVeilError::InvalidTreasuryVault,
+        constraint = treasury_token_account.mint == relayer_config.stake_mint @ SolanaVeilError::InvalidStakeMint
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for SPL stakes
+    #[account(
+        constraint = stake_mint.key() == relayer_config.stake_mint @ SolanaVeilError::InvalidStakeMint,
+        mint::token_program = token_program
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Only required for SPL stakes (SPL Token or Token-2022)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct GetRelayerStats<'info> {
+    #[account(
+        seeds = [b"relayer".as_ref(), relayer.authority.as_ref()],
+        bump = relayer.bump
+    )]
+    pub relayer: Account<'info, Relayer>,
+    
+    /// Counters for one mint; omit for the relayer-wide counters only
+    #[account(
+        seeds = [
+            b"relayer_mint_stats".as_ref(),
+            relayer.key().as_ref(),
+            relayer_mint_stats.mint.as_ref(),
+        ],
+        bump = relayer_mint_stats.bump
+    )]
+    pub relayer_mint_stats: Option<Account<'info, RelayerMintStats>>,
+}
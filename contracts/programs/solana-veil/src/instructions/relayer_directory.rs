@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+// Create the relayer directory header (one per program)
+pub fn initialize_relayer_directory(ctx: Context<InitializeRelayerDirectory>) -> Result<()> {
+    let directory = &mut ctx.accounts.directory;
+    directory.relayer_count = 0;
+    directory.bump = ctx.bumps.directory;
+
+    msg!("Initialized relayer directory");
+
+    Ok(())
+}
+
+// Publish a relayer's service metadata and list it in the directory
+pub fn register_relayer_metadata(
+    ctx: Context<RegisterRelayerMetadata>,
+    params: RelayerMetadataParams,
+) -> Result<()> {
+    let directory = &mut ctx.accounts.directory;
+    let page = &mut ctx.accounts.directory_page;
+    let directory_index = directory.relayer_count;
+
+    // A fresh page is created by `init_if_needed` whenever the previous one filled up
+    if page.entries.is_empty() {
+        page.page_index = directory.next_page_index();
+        page.bump = ctx.bumps.directory_page;
+    }
+    if page.entries.len() >= MAX_RELAYERS_PER_DIRECTORY_PAGE {
+        return Err(SolanaVeilError::RegistryPageFull.into());
+    }
+    page.entries.push(RelayerDirectoryEntry {
+        relayer: ctx.accounts.relayer.key(),
+        authority: ctx.accounts.authority.key(),
+        metadata: ctx.accounts.metadata.key(),
+    });
+
+    directory.relayer_count = directory.relayer_count.checked_add(1)
+        .ok_or(SolanaVeilError::CalculationError)?;
+
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.relayer = ctx.accounts.relayer.key();
+    metadata.authority = ctx.accounts.authority.key();
+    metadata.directory_index = directory_index;
+    metadata.bump = ctx.bumps.metadata;
+    apply_metadata(metadata, &mut ctx.accounts.relayer, params)?;
+
+    emit!(RelayerListedEvent {
+        relayer: metadata.relayer,
+        metadata: metadata.key(),
+        directory_index,
+        timestamp: metadata.updated_at,
+    });
+
+    Ok(())
+}
+
+// Replace a listed relayer's service metadata
+pub fn update_relayer_metadata(
+    ctx: Context<UpdateRelayerMetadata>,
+    params: RelayerMetadataParams,
+) -> Result<()> {
+    apply_metadata(&mut ctx.accounts.metadata, &mut ctx.accounts.relayer, params)
+}
+
+// Validate and store metadata; the endpoint must be an https URL of bounded length.
+// The quote signing key lives on the `Relayer` account, where withdrawals read it.
+fn apply_metadata(
+    metadata: &mut RelayerMetadata,
+    relayer: &mut Relayer,
+    params: RelayerMetadataParams,
+) -> Result<()> {
+    let host = params.endpoint.strip_prefix("https://")
+        .ok_or(SolanaVeilError::InvalidRelayerEndpoint)?;
+    if params.endpoint.len() > MAX_RELAYER_URL_LEN
+        || host.is_empty()
+        || host.starts_with('/')
+        || params.endpoint.chars().any(|c| c.is_whitespace() || c.is_control())
+    {
+        return Err(SolanaVeilError::InvalidRelayerEndpoint.into());
+    }
+
+    if params.supported_pools.len() > MAX_RELAYER_POOLS
+        || params.supported_mints.len() > MAX_RELAYER_MINTS
+        || params.fee_schedule.len() > MAX_RELAYER_FEE_TIERS
+        || params.quote_signer == Pubkey::default()
+    {
+        return Err(SolanaVeilError::InvalidRelayerMetadata.into());
+    }
+
+    // Max fee is 5%, as for the relayer's base fee
+    if params.fee_schedule.iter().any(|tier| tier.fee_basis_points > 500) {
+        return Err(SolanaVeilError::FeeTooHigh.into());
+    }
+
+    metadata.endpoint = params.endpoint;
+    metadata.supported_pools = params.supported_pools;
+    metadata.supported_mints = params.supported_mints;
+    metadata.fee_schedule = params.fee_schedule;
+    metadata.updated_at = Clock::get()?.unix_timestamp;
+    relayer.quote_signer = params.quote_signer;
+
+    emit!(RelayerMetadataUpdatedEvent {
+        relayer: metadata.relayer,
+        endpoint: metadata.endpoint.clone(),
+        quote_signer: relayer.quote_signer,
+        timestamp: metadata.updated_at,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeRelayerDirectory<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RelayerDirectory::LEN,
+        seeds = [b"relayer_directory".as_ref()],
+        bump
+    )]
+    pub directory: Account<'info, RelayerDirectory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterRelayerMetadata<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"relayer".as_ref(), authority.key().as_ref()],
+        bump = relayer.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RelayerMetadata::LEN,
+        seeds = [b"relayer_metadata".as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub metadata: Box<Account<'info, RelayerMetadata>>,
+
+    #[account(
+        mut,
+        seeds = [b"relayer_directory".as_ref()],
+        bump = directory.bump
+    )]
+    pub directory: Account<'info, RelayerDirectory>,
+
+    /// Directory page the relayer is appended to, created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RelayerDirectoryPage::LEN,
+        seeds = [
+            b"relayer_directory_page".as_ref(),
+            &directory.next_page_index().to_le_bytes(),
+        ],
+        bump
+    )]
+    pub directory_page: Box<Account<'info, RelayerDirectoryPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRelayerMetadata<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"relayer".as_ref(), authority.key().as_ref()],
+        bump = relayer.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub relayer: Account<'info, Relayer>,
+
+    #[account(
+        mut,
+        seeds = [b"relayer_metadata".as_ref(), authority.key().as_ref()],
+        bump = metadata.bump,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
+    pub metadata: Box<Account<'info, RelayerMetadata>>,
+}
@@ -0,0 +1,175 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+use crate::vault::sol_vault_rent_reserve;
+
+// Configure SOL fee payouts for an SPL pool (Admin only). The reserve is funded with
+// plain SOL transfers to `[b"sol_fee_reserve", pool]`.
+pub fn configure_sol_fee(ctx: Context<ConfigureSolFee>, params: SolFeeParams) -> Result<()> {
+    if params.max_price_age_seconds <= 0
+        || params.max_price_age_seconds > MAX_PRICE_AGE_SECONDS
+        || params.max_confidence_basis_points > MAX_PRICE_CONFIDENCE_BASIS_POINTS
+        || params.pyth_program == Pubkey::default()
+        || params.token_price_feed == Pubkey::default()
+        || params.sol_price_feed == Pubkey::default()
+    {
+        return Err(SolanaVeilError::InvalidSolFee.into());
+    }
+
+    let pool_key = ctx.accounts.pool.key();
+    let sol_fee_config = &mut ctx.accounts.sol_fee_config;
+    sol_fee_config.pool = pool_key;
+    sol_fee_config.pyth_program = params.pyth_program;
+    sol_fee_config.token_price_feed = params.token_price_feed;
+    sol_fee_config.sol_price_feed = params.sol_price_feed;
+    sol_fee_config.max_price_age_seconds = params.max_price_age_seconds;
+    sol_fee_config.max_confidence_basis_points = params.max_confidence_basis_points;
+    sol_fee_config.enabled = params.enabled;
+    sol_fee_config.reserve_bump = ctx.bumps.sol_fee_reserve;
+    sol_fee_config.bump = ctx.bumps.sol_fee_config;
+
+    // Fund the reserve's rent up front so payouts only ever move lamports above it
+    let sol_fee_reserve = &ctx.accounts.sol_fee_reserve;
+    let top_up = sol_vault_rent_reserve()?.saturating_sub(sol_fee_reserve.lamports());
+    if top_up > 0 {
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.admin.key,
+                sol_fee_reserve.key,
+                top_up,
+            ),
+            &[
+                ctx.accounts.admin.to_account_info(),
+                sol_fee_reserve.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    emit!(SolFeeConfiguredEvent {
+        pool: pool_key,
+        pyth_program: params.pyth_program,
+        token_price_feed: params.token_price_feed,
+        sol_price_feed: params.sol_price_feed,
+        max_price_age_seconds: params.max_price_age_seconds,
+        max_confidence_basis_points: params.max_confidence_basis_points,
+        enabled: params.enabled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Withdraw SOL from a pool's SOL fee reserve (treasury only)
+pub fn withdraw_sol_fee_reserve(ctx: Context<WithdrawSolFeeReserve>, amount: u64) -> Result<()> {
+    let pool_key = ctx.accounts.pool.key();
+    let sol_fee_reserve = &ctx.accounts.sol_fee_reserve;
+
+    // Never dip into the reserve's rent reserve
+    let available = sol_fee_reserve.lamports().saturating_sub(sol_vault_rent_reserve()?);
+    if amount > available {
+        return Err(SolanaVeilError::InsufficientFunds.into());
+    }
+
+    let reserve_seeds = &[
+        SOL_FEE_RESERVE_SEED,
+        pool_key.as_ref(),
+        &[ctx.accounts.sol_fee_config.reserve_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::transfer(
+            sol_fee_reserve.key,
+            ctx.accounts.destination.key,
+            amount,
+        ),
+        &[
+            sol_fee_reserve.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&reserve_seeds[..]],
+    )?;
+
+    emit!(SolFeeReserveWithdrawnEvent {
+        pool: pool_key,
+        destination: ctx.accounts.destination.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureSolFee<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&admin.key(), Role::Admin) @ SolanaVeilError::MissingRole
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+
+    #[account(
+        constraint = pool.is_spl_token @ SolanaVeilError::InvalidSolFee
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + SolFeeConfig::LEN,
+        seeds = [b"sol_fee_config".as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pub sol_fee_config: Account<'info, SolFeeConfig>,
+
+    #[account(
+        mut,
+        seeds = [SOL_FEE_RESERVE_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub sol_fee_reserve: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolFeeReserve<'info> {
+    /// Treasury authority recorded in the protocol config
+    pub treasury: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        has_one = treasury @ SolanaVeilError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [b"sol_fee_config".as_ref(), pool.key().as_ref()],
+        bump = sol_fee_config.bump
+    )]
+    pub sol_fee_config: Account<'info, SolFeeConfig>,
+
+    #[account(
+        mut,
+        seeds = [SOL_FEE_RESERVE_SEED, pool.key().as_ref()],
+        bump = sol_fee_config.reserve_bump
+    )]
+    pub sol_fee_reserve: SystemAccount<'info>,
+
+    /// Receives the withdrawn SOL
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,113 +1,82 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
-use crate::error::*;
+use crate::errors::SolanaVeilError;
 use crate::events::*;
-use solana_program::program::invoke;
-use solana_program::program_log;
+use crate::verifier::{field_element_from_be_bytes, field_element_from_u64, verify_groth16_proof};
 
-// Compressed tree program ID (Light Protocol or Helius)
-const COMPRESSED_TREE_PROGRAM_ID: &str = "cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK";
-
-/// Accounts for initializing a compressed merkle tree
+/// Accounts for a permissionless batch root update
 #[derive(Accounts)]
-#[instruction(max_depth: u8, max_buffer_size: u32)]
-pub struct InitializeTree<'info> {
-    /// The pool authority (admin)
-    #[account(mut)]
-    pub authority: Signer<'info>,
+pub struct BatchUpdateRoot<'info> {
+    /// Anyone can submit a root update; the proof makes it trustless
+    pub updater: Signer<'info>,
     
-    /// The associated privacy pool
+    /// The privacy pool whose root is updated
     #[account(
         mut,
-        seeds = [b"pool", pool.denomination.to_le_bytes().as_ref()],
+        seeds = [b"pool", pool.denomination.to_le_bytes().as_ref(), pool.mint.as_ref()],
         bump = pool.bump,
-        has_one = authority @ SolanaVeilError::Unauthorized,
     )]
-    pub pool: Account<'info, PoolState>,
+    pub pool: Account<'info, Pool>,
     
-    /// The compressed tree program
-    /// CHECK: External program
+    /// The pool's merkle tree
     #[account(
-        constraint = compressed_tree_program.key() == COMPRESSED_TREE_PROGRAM_ID.parse::<Pubkey>().unwrap() 
-            @ SolanaVeilError::InvalidCompressedTreeProgram
+        mut,
+        constraint = tree.key() == pool.tree @ SolanaVeilError::InvalidMerkleTree,
+        constraint = tree.pool == pool.key() @ SolanaVeilError::InvalidMerkleTree,
     )]
-    pub compressed_tree_program: AccountInfo<'info>,
-    
-    /// System program for account creation
-    pub system_program: Program<'info, System>,
-}
-
-/// Initialize a compressed merkle tree for the pool
-pub fn initialize_tree(
-    ctx: Context<InitializeTree>,
-    max_depth: u8,
-    max_buffer_size: u32,
-) -> Result<()> {
-    // Validate inputs
-    if max_depth < 10 || max_depth > 32 {
-        return Err(SolanaVeilError::InvalidTreeHeight.into());
-    }
-    
-    if max_buffer_size < 2 || max_buffer_size > 256 {
-        return Err(SolanaVeilError::InvalidBufferSize.into());
-    }
+    pub tree: Box<Account<'info, MerkleTree>>,
     
-    // Emit tree initialization log for ZK Compression to pick up
-    let log_message = format!(
-        "initialize_compressed_tree:{},{},{}",
-        ctx.accounts.pool.key(),
-        max_depth,
-        max_buffer_size
-    );
-    program_log::sol_log(&log_message);
-    
-    // Update pool with tree information
-    let pool = &mut ctx.accounts.pool;
-    pool.tree_height = max_depth;
-    
-    // Emit tree initialization event
-    emit!(TreeInitializedEvent {
-        pool: pool.key(),
-        max_depth,
-        max_buffer_size,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
-    
-    Ok(())
-}
-
-/// Accounts for updating the merkle tree root
-#[derive(Accounts)]
-pub struct UpdateTreeRoot<'info> {
-    /// The pool authority (admin)
-    pub authority: Signer<'info>,
-    
-    /// The privacy pool to update
+    /// Verifying key of the pool's batch update circuit
     #[account(
-        mut,
-        seeds = [b"pool", pool.denomination.to_le_bytes().as_ref()],
-        bump = pool.bump,
-        has_one = authority @ SolanaVeilError::Unauthorized,
+        seeds = [
+            b"verifying_key".as_ref(),
+            pool.key().as_ref(),
+            &[VerifierCircuit::BatchUpdate as u8],
+        ],
+        bump = verifying_key.bump,
     )]
-    pub pool: Account<'info, PoolState>,
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
 }
 
-/// Update the merkle tree root (admin only)
-pub fn update_tree_root(
-    ctx: Context<UpdateTreeRoot>,
+/// Advance the tree root over the oldest pending batch of commitments.
+/// The proof shows that appending the batch whose hash chain is recorded on the
+/// tree account to the current root yields `new_root`.
+pub fn batch_update_root(
+    ctx: Context<BatchUpdateRoot>,
+    proof_data: Vec<u8>,
     new_root: [u8; 32],
 ) -> Result<()> {
-    // Update the root
+    let tree = &mut ctx.accounts.tree;
+    let batch = tree.next_pending_batch()
+        .ok_or(SolanaVeilError::NoPendingCommitments)?;
+    let old_root = tree.root;
+    
+    // Public inputs in circuit order: oldRoot, newRoot, hashChain, startIndex, batchSize
+    let public_inputs = [
+        field_element_from_be_bytes(&old_root)?,
+        field_element_from_be_bytes(&new_root)?,
+        field_element_from_be_bytes(&batch.hash_chain)?,
+        field_element_from_u64(batch.start_index),
+        field_element_from_u64(batch.count),
+    ];
+    verify_groth16_proof(&proof_data, &ctx.accounts.verifying_key.data, &public_inputs)?;
+    
+    tree.push_root(new_root);
+    tree.consume_pending_batch();
+    
     let pool = &mut ctx.accounts.pool;
     pool.merkle_root = new_root;
     
-    // Emit root update event
-    emit!(TreeRootUpdatedEvent {
+    emit!(BatchRootUpdatedEvent {
         pool: pool.key(),
-        old_root: pool.merkle_root,
+        tree: tree.key(),
+        old_root,
         new_root,
+        start_index: batch.start_index,
+        batch_size: batch.count,
+        updater: ctx.accounts.updater.key(),
         timestamp: Clock::get()?.unix_timestamp,
     });
     
     Ok(())
-}
\ No newline at end of file
+}
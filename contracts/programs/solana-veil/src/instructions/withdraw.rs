@@ -1,31 +1,67 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::program::invoke_signed;
-use anchor_lang::solana_program::system_instruction;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
-use crate::error::*;
+use crate::errors::SolanaVeilError;
+use crate::screening;
+use crate::quote::{self, RelayerQuote};
+use crate::callback::{self, WithdrawCallback, CALLBACK_AUTHORITY_SEED, MAX_CALLBACK_ACCOUNTS, MAX_CALLBACK_DATA_LEN, NO_CALLBACK_HASH};
+use crate::vault::{sol_vault_rent_reserve, PoolVault, SOL_VAULT_SEED};
+use crate::instructions::config::calculate_protocol_fee;
+use crate::events::{RelayerStatsEvent, SolFeePaidEvent, WithdrawalLimitReachedEvent};
+use crate::oracle::{self, OraclePrice};
+use crate::verifier::{field_element_from_be_bytes, field_element_from_pubkey, field_element_from_u64, verify_groth16_proof};
+
+/// Proof of a withdrawal and the public values it is made for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawArgs {
+    /// Groth16 proof of the pool's withdraw circuit
+    pub proof_data: Vec<u8>,
+    
+    /// Tree root the proof is made against: the current root or a recent one
+    pub root: [u8; 32],
+    
+    /// Hash of the note's nullifier, spent by this withdrawal
+    pub nullifier_hash: [u8; 32],
+    
+    /// Account receiving the withdrawn funds
+    pub recipient: Pubkey,
+    
+    /// Relayer fee taken from the denomination (0 when unrelayed)
+    pub fee: u64,
+    
+    /// The note's leaf index is below this bound, which must have aged enough
+    pub leaf_index_bound: u64,
+}
 
 // Withdraw funds from a pool
-pub fn withdraw(
-    ctx: Context<Withdraw>,
-    proof_data: Vec<u8>,
-    root: [u8; 32],
-    nullifier_hash: [u8; 32],
-    recipient: Pubkey,
-    fee: u64,
+pub fn withdraw<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+    args: WithdrawArgs,
+    callback: Option<WithdrawCallback>,
+    quote: Option<RelayerQuote>,
 ) -> Result<()> {
+    let WithdrawArgs {
+        proof_data,
+        root,
+        nullifier_hash,
+        recipient,
+        fee,
+        leaf_index_bound,
+    } = args;
     let pool = &ctx.accounts.pool;
     let nullifier = &mut ctx.accounts.nullifier;
     let tree = &ctx.accounts.tree;
     
-    // Check that the pool is active
-    if !pool.is_active {
-        return Err(SolanaVeilError::PoolInactive.into());
+    // Check that withdrawals are not paused
+    if pause_flags::is_paused(pool.pause_flags, pause_flags::WITHDRAWALS) {
+        return Err(SolanaVeilError::WithdrawalsPaused.into());
     }
     
-    // Verify that the provided root exists in the tree
-    // In a production implementation, we would verify this against recent valid roots
-    if root != tree.root {
+    // The proof may be against the current root or any recent one still in the history
+    if !tree.is_known_root(&root) {
         return Err(SolanaVeilError::InvalidMerkleRoot.into());
     }
     
@@ -34,6 +70,87 @@ pub fn withdraw(
         return Err(SolanaVeilError::NullifierAlreadySpent.into());
     }
     
+    // The proof shows the commitment's leaf index is below `leaf_index_bound`,
+    // so every leaf under the bound must have aged at least `min_deposit_age_slots`
+    let cutoff_slot = Clock::get()?.slot.saturating_sub(pool.min_deposit_age_slots);
+    let withdrawable_leaves = ctx.accounts.checkpoints
+        .leaves_inserted_before(cutoff_slot, tree.num_leaves);
+    if leaf_index_bound > withdrawable_leaves {
+        return Err(SolanaVeilError::DepositTooRecent.into());
+    }
+    
+    // The recipient account must be the recipient bound by the proof
+    if ctx.accounts.recipient.key() != recipient {
+        return Err(SolanaVeilError::InvalidRecipient.into());
+    }
+    
+    // Trailing remaining accounts belong to the callback, the rest to the screening policy
+    let callback_account_count = callback.as_ref()
+        .map_or(0, |callback| callback.account_count as usize);
+    if callback_account_count > ctx.remaining_accounts.len() {
+        return Err(SolanaVeilError::InvalidCallback.into());
+    }
+    let (policy_accounts, callback_accounts) = ctx.remaining_accounts
+        .split_at(ctx.remaining_accounts.len() - callback_account_count);
+    
+    // The proof's relayer input is the fee-receiving relayer account (zeros when unrelayed)
+    let relayer_input = ctx.accounts.relayer.as_ref()
+        .map(|relayer| relayer.key())
+        .unwrap_or_default();
+    
+    // Withdrawals with a callback may pay a program-derived recipient of the whitelisted
+    // program; the callback itself is bound to the proof through its hash
+    let callback_hash = match &callback {
+        Some(callback) => {
+            let callback_program = ctx.accounts.callback_program.as_ref()
+                .ok_or(SolanaVeilError::InvalidCallbackProgram)?;
+            if ctx.accounts.recipient_program.is_none() {
+                return Err(SolanaVeilError::InvalidCallbackProgram.into());
+            }
+            
+            if callback.data.len() > MAX_CALLBACK_DATA_LEN
+                || callback_accounts.len() > MAX_CALLBACK_ACCOUNTS
+            {
+                return Err(SolanaVeilError::InvalidCallback.into());
+            }
+            
+            let recipient_owner = ctx.accounts.recipient.owner;
+            if *recipient_owner != system_program::ID && *recipient_owner != callback_program.key() {
+                return Err(SolanaVeilError::InvalidRecipient.into());
+            }
+            
+            callback::callback_hash(&callback_program.key(), callback_accounts, &callback.data)
+        }
+        None => {
+            if *ctx.accounts.recipient.owner != system_program::ID {
+                return Err(SolanaVeilError::InvalidRecipient.into());
+            }
+            
+            NO_CALLBACK_HASH
+        }
+    };
+    
+    // Run the recipient through the pool's screening policy, if any
+    if screening::is_screening_enabled(&pool.screening_program) {
+        let screening_program = ctx.accounts.screening_program.as_ref()
+            .ok_or(SolanaVeilError::InvalidScreeningProgram)?;
+        
+        screening::screen_address(
+            screening_program,
+            pool.key(),
+            recipient,
+            policy_accounts,
+        )?;
+    }
+    
+    // A fee needs a relayer to receive it, and a relayer needs its relayer account
+    if fee > 0 && ctx.accounts.relayer.is_none() {
+        return Err(SolanaVeilError::InvalidFeeAmount.into());
+    }
+    if ctx.accounts.relayer.is_some() && ctx.accounts.relayer_stats.is_none() {
+        return Err(SolanaVeilError::InvalidRelayer.into());
+    }
+    
     // Validate fee
     let denomination = pool.denomination;
     if fee > denomination {
@@ -51,8 +168,84 @@ pub fn withdraw(
         return Err(SolanaVeilError::FeeTooHigh.into());
     }
     
+    let current_slot = Clock::get()?.slot;
+    
+    // Part of the fee the relayer takes in SOL rather than pool tokens
+    let sol_fee = quote.as_ref()
+        .filter(|_| fee > 0)
+        .map_or(0, |quote| quote.sol_fee);
+    
+    // Relayed withdrawals must carry a fee quote signed with the relayer's quote key
+    if fee > 0 {
+        let quote = quote.as_ref()
+            .ok_or(SolanaVeilError::MissingRelayerQuote)?;
+        let relayer_stats = ctx.accounts.relayer_stats.as_ref()
+            .ok_or(SolanaVeilError::InvalidRelayer)?;
+        
+        if !relayer_stats.is_active {
+            return Err(SolanaVeilError::RelayerInactive.into());
+        }
+        
+        if relayer_stats.quote_signer == Pubkey::default() {
+            return Err(SolanaVeilError::InvalidQuoteSignature.into());
+        }
+        
+        if quote.pool != pool.key() {
+            return Err(SolanaVeilError::QuotePoolMismatch.into());
+        }
+        
+        if current_slot > quote.expiry_slot {
+            return Err(SolanaVeilError::QuoteExpired.into());
+        }
+        
+        if fee > quote.fee {
+            return Err(SolanaVeilError::FeeExceedsQuote.into());
+        }
+        
+        // Only SPL pools with SOL fees enabled can pay part of the fee in SOL
+        if sol_fee > 0 {
+            let sol_fee_enabled = ctx.accounts.sol_fee_config.as_ref()
+                .is_some_and(|sol_fee_config| sol_fee_config.enabled);
+            if !pool.is_spl_token || !sol_fee_enabled || sol_fee > fee {
+                return Err(SolanaVeilError::InvalidSolFee.into());
+            }
+        }
+        
+        // During the exclusivity window nobody else can submit the relayer's proof
+        if current_slot <= quote.exclusive_until_slot && ctx.accounts.user.key() != relayer_input {
+            return Err(SolanaVeilError::RelayerExclusivityActive.into());
+        }
+        
+        let instructions_sysvar = ctx.accounts.instructions_sysvar.as_ref()
+            .ok_or(SolanaVeilError::InvalidQuoteSignature)?;
+        quote::verify_quote_signature(instructions_sysvar, &relayer_stats.quote_signer, quote)?;
+        
+        // Burn the nonce so the quote cannot be replayed
+        let relayer_key = relayer_stats.key();
+        let quote_nonce = ctx.accounts.quote_nonce.as_mut()
+            .ok_or(SolanaVeilError::MissingRelayerQuote)?;
+        if quote_nonce.used_at != 0 {
+            return Err(SolanaVeilError::QuoteNonceAlreadyUsed.into());
+        }
+        quote_nonce.relayer = relayer_key;
+        quote_nonce.nonce = quote.nonce;
+        quote_nonce.used_at = Clock::get()?.unix_timestamp;
+        quote_nonce.bump = ctx.bumps.quote_nonce;
+    }
+    
+    // Enforce the pool's withdrawal rate limit for the current window
+    pool.check_withdrawal_limit(current_slot, denomination)?;
+    
+    // Protocol fee is skimmed from the denomination on top of the relayer fee
+    let protocol_fee = calculate_protocol_fee(
+        denomination,
+        ctx.accounts.config.protocol_fee_basis_points,
+    )?;
+    
     // Calculate withdrawal amount
     let withdraw_amount = denomination.checked_sub(fee)
+        .ok_or(SolanaVeilError::CalculationError)?
+        .checked_sub(protocol_fee)
         .ok_or(SolanaVeilError::CalculationError)?;
     
     // Ensure withdrawal amount is above minimum
@@ -60,14 +253,20 @@ pub fn withdraw(
         return Err(SolanaVeilError::WithdrawalAmountTooLow.into());
     }
     
-    // Verify ZK proof by calling the verifier contract
-    // In this example, we'll just log the proof verification
-    // In a real implementation, we would call a separate verifier contract
-    msg!("verify_zk_proof:{{\"tree_id\":\"{}\",\"root\":\"{:?}\",\"nullifier_hash\":\"{:?}\"}}",
-        tree.key().to_string(),
-        root,
-        nullifier_hash
-    );
+    // Public inputs in circuit order: nullifierHash, root, pool, recipient, relayer, fee,
+    // denomination, leafIndexBound, callbackHash
+    let public_inputs = [
+        field_element_from_be_bytes(&nullifier_hash)?,
+        field_element_from_be_bytes(&root)?,
+        field_element_from_pubkey(&pool.key()),
+        field_element_from_pubkey(&recipient),
+        field_element_from_pubkey(&relayer_input),
+        field_element_from_u64(fee),
+        field_element_from_u64(denomination),
+        field_element_from_u64(leaf_index_bound),
+        field_element_from_be_bytes(&callback_hash)?,
+    ];
+    verify_groth16_proof(&proof_data, &ctx.accounts.verifying_key.data, &public_inputs)?;
     
     // Mark nullifier as spent
     nullifier.is_spent = true;
@@ -76,109 +275,181 @@ pub fn withdraw(
     nullifier.spent_at = Clock::get()?.unix_timestamp;
     nullifier.recipient = recipient;
     
-    // Transfer funds based on token type
-    if pool.is_spl_token {
-        // Handle SPL token withdrawal
+    // Build the payout source; native and SPL pools share the same path from here
+    let pool_key = pool.key();
+    let denomination_bytes = pool.denomination.to_le_bytes();
+    let pool_bump = [pool.bump];
+    let vault_bump = [pool.vault_bump];
+    let pool_seeds: &[&[u8]] = &[
+        b"pool".as_ref(),
+        &denomination_bytes,
+        pool.mint.as_ref(),
+        &pool_bump,
+    ];
+    let sol_vault_seeds: &[&[u8]] = &[
+        SOL_VAULT_SEED,
+        pool_key.as_ref(),
+        &vault_bump,
+    ];
+    
+    let vault = if pool.is_spl_token {
         let token_program = ctx.accounts.token_program.as_ref()
             .ok_or(SolanaVeilError::InvalidTokenAccount)?;
-            
+        
         let pool_token_account = ctx.accounts.pool_token_account.as_ref()
             .ok_or(SolanaVeilError::InvalidTokenAccount)?;
-            
-        let recipient_token_account = ctx.accounts.recipient_token_account.as_ref()
-            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
-        
-        // Transfer tokens to recipient
-        let pool_seeds = &[
-            b"pool".as_ref(),
-            &pool.denomination.to_le_bytes(),
-            &pool.mint.to_bytes(),
-            &[pool.bump],
-        ];
-        
-        let pool_signer = &[&pool_seeds[..]];
         
-        let transfer_ctx = CpiContext::new_with_signer(
-            token_program.to_account_info(),
-            Transfer {
-                from: pool_token_account.to_account_info(),
-                to: recipient_token_account.to_account_info(),
-                authority: ctx.accounts.pool.to_account_info(),
-            },
-            pool_signer,
-        );
+        let mint = ctx.accounts.mint.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
         
-        token::transfer(transfer_ctx, withdraw_amount)?;
+        PoolVault::Token {
+            vault: pool_token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            decimals: mint.decimals,
+            authority: ctx.accounts.pool.to_account_info(),
+            token_program: token_program.to_account_info(),
+            signer_seeds: pool_seeds,
+        }
+    } else {
+        let sol_vault = ctx.accounts.sol_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidVault)?;
         
-        // If there's a fee, transfer to relayer
-        if fee > 0 && ctx.accounts.relayer.is_some() {
-            let relayer_token_account = ctx.accounts.relayer_token_account.as_ref()
-                .ok_or(SolanaVeilError::InvalidTokenAccount)?;
-            
-            let fee_transfer_ctx = CpiContext::new_with_signer(
-                token_program.to_account_info(),
-                Transfer {
-                    from: pool_token_account.to_account_info(),
-                    to: relayer_token_account.to_account_info(),
-                    authority: ctx.accounts.pool.to_account_info(),
-                },
-                pool_signer,
-            );
-            
-            token::transfer(fee_transfer_ctx, fee)?;
+        PoolVault::Native {
+            vault: sol_vault.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            signer_seeds: sol_vault_seeds,
         }
+    };
+    
+    // The whole denomination must be payable without dipping into the vault's rent reserve
+    vault.ensure_available(denomination)?;
+    
+    // Destinations are system accounts for native pools and token accounts for SPL pools
+    let recipient_destination = if pool.is_spl_token {
+        prepare_recipient_token_account(ctx.accounts)?
     } else {
-        // Handle native SOL withdrawal
-        let pool_seeds = &[
-            b"pool".as_ref(),
-            &pool.denomination.to_le_bytes(),
-            &[pool.bump],
-        ];
+        ctx.accounts.recipient.to_account_info()
+    };
+    
+    vault.pay(&recipient_destination, withdraw_amount)?;
+    
+    // If there's a fee, transfer to relayer
+    if fee > 0 && ctx.accounts.relayer.is_some() {
+        let relayer_destination = if pool.is_spl_token {
+            ctx.accounts.relayer_token_account.as_ref().map(|account| account.to_account_info())
+        } else {
+            ctx.accounts.relayer.as_ref().map(|account| account.to_account_info())
+        }.ok_or(SolanaVeilError::InvalidTokenAccount)?;
         
-        let pool_signer = &[&pool_seeds[..]];
+        vault.pay(&relayer_destination, fee - sol_fee)?;
+    }
+    
+    // The SOL-paid part of the fee is swapped against the pool's SOL fee reserve: the
+    // relayer receives lamports from the reserve and the tokens go to the treasury
+    let sol_fee_payout = if sol_fee > 0 {
+        let payout = pay_sol_fee(ctx.accounts, sol_fee)?;
         
-        // Transfer SOL to recipient
-        invoke_signed(
-            &system_instruction::transfer(
-                &pool.key(),
-                &recipient,
-                withdraw_amount,
-            ),
-            &[
-                ctx.accounts.pool.to_account_info(),
-                ctx.accounts.recipient.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            pool_signer,
-        )?;
+        let treasury_token_account = ctx.accounts.treasury_token_account.as_ref()
+            .ok_or(SolanaVeilError::InvalidTreasuryVault)?;
+        vault.pay(&treasury_token_account.to_account_info(), sol_fee)?;
         
-        // If there's a fee, transfer to relayer
-        if fee > 0 && ctx.accounts.relayer.is_some() {
-            let relayer = ctx.accounts.relayer.as_ref().unwrap();
-            
-            invoke_signed(
-                &system_instruction::transfer(
-                    &pool.key(),
-                    &relayer.key(),
-                    fee,
-                ),
-                &[
-                    ctx.accounts.pool.to_account_info(),
-                    relayer.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                pool_signer,
-            )?;
-        }
+        Some(payout)
+    } else {
+        None
+    };
+    
+    // If there's a protocol fee, transfer to the treasury vault
+    if protocol_fee > 0 {
+        let treasury_destination = if pool.is_spl_token {
+            ctx.accounts.treasury_token_account.as_ref().map(|account| account.to_account_info())
+        } else {
+            ctx.accounts.treasury_vault.as_ref().map(|account| account.to_account_info())
+        }.ok_or(SolanaVeilError::InvalidTreasuryVault)?;
+        
+        vault.pay(&treasury_destination, protocol_fee)?;
     }
     
-    // Update relayer stats if applicable
-    if fee > 0 && ctx.accounts.relayer.is_some() && ctx.accounts.relayer_stats.is_some() {
-        let relayer_stats = &mut ctx.accounts.relayer_stats.as_ref().unwrap();
+    // Update the relayer's counters, overall and for the pool's mint
+    if fee > 0 {
+        let mint = ctx.accounts.pool.mint;
+        let relayer_stats = ctx.accounts.relayer_stats.as_mut()
+            .ok_or(SolanaVeilError::InvalidRelayer)?;
+        relayer_stats.successful_withdrawals = relayer_stats.successful_withdrawals.checked_add(1)
+            .ok_or(SolanaVeilError::CalculationError)?;
         relayer_stats.total_relayed = relayer_stats.total_relayed.checked_add(withdraw_amount)
             .ok_or(SolanaVeilError::CalculationError)?;
         relayer_stats.total_fees = relayer_stats.total_fees.checked_add(fee)
             .ok_or(SolanaVeilError::CalculationError)?;
+        relayer_stats.last_active_slot = current_slot;
+        
+        let relayer_key = relayer_stats.key();
+        let relayer_mint_stats = ctx.accounts.relayer_mint_stats.as_mut()
+            .ok_or(SolanaVeilError::InvalidRelayer)?;
+        if relayer_mint_stats.relayer == Pubkey::default() {
+            relayer_mint_stats.relayer = relayer_key;
+            relayer_mint_stats.mint = mint;
+            relayer_mint_stats.bump = ctx.bumps.relayer_mint_stats;
+        }
+        relayer_mint_stats.withdrawals = relayer_mint_stats.withdrawals.checked_add(1)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        relayer_mint_stats.total_relayed = relayer_mint_stats.total_relayed.checked_add(withdraw_amount)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        relayer_mint_stats.total_fees = relayer_mint_stats.total_fees.checked_add(fee)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        
+        emit!(RelayerStatsEvent {
+            relayer: relayer_key,
+            mint,
+            successful_withdrawals: relayer_stats.successful_withdrawals,
+            total_relayed: relayer_stats.total_relayed,
+            total_fees: relayer_stats.total_fees,
+            mint_withdrawals: relayer_mint_stats.withdrawals,
+            mint_total_relayed: relayer_mint_stats.total_relayed,
+            mint_total_fees: relayer_mint_stats.total_fees,
+            last_active_slot: relayer_stats.last_active_slot,
+            slash_count: relayer_stats.slash_count,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+    
+    if let Some((lamports, token_price, sol_price)) = sol_fee_payout {
+        let sol_fee_config = ctx.accounts.sol_fee_config.as_mut()
+            .ok_or(SolanaVeilError::InvalidSolFee)?;
+        sol_fee_config.total_tokens_converted = sol_fee_config.total_tokens_converted.checked_add(sol_fee)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        sol_fee_config.total_lamports_paid = sol_fee_config.total_lamports_paid.checked_add(lamports)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        
+        emit!(SolFeePaidEvent {
+            pool: ctx.accounts.pool.key(),
+            relayer: relayer_input,
+            token_amount: sol_fee,
+            lamports,
+            token_price: token_price.price,
+            sol_price: sol_price.price,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+    }
+    
+    // Update pool accounting; the full denomination leaves the pool vault
+    let pool = &mut ctx.accounts.pool;
+    pool.roll_withdrawal_window(current_slot);
+    pool.total_withdrawn = pool.total_withdrawn.checked_add(denomination)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    pool.total_protocol_fees = pool.total_protocol_fees.checked_add(protocol_fee)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    
+    // Announce the limit once the window leaves no room for another note
+    if pool.withdrawal_limit_amount > 0
+        && pool.withdrawal_limit_amount.saturating_sub(pool.withdrawn_in_window(current_slot)) < denomination
+    {
+        emit!(WithdrawalLimitReachedEvent {
+            pool: pool.key(),
+            withdrawal_limit_amount: pool.withdrawal_limit_amount,
+            window_start_slot: pool.withdrawal_window_start_slot,
+            window_slots: pool.withdrawal_limit_window_slots,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
     }
     
     // Emit a Withdraw event
@@ -187,38 +458,204 @@ pub fn withdraw(
         nullifier_hash,
         recipient,
         fee,
+        protocol_fee,
         amount: withdraw_amount,
+        callback_hash,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
+    // Hand control to the recipient program once the funds have landed
+    if let Some(callback) = &callback {
+        let pool = &ctx.accounts.pool;
+        let pool_key = pool.key();
+        let callback_authority_bump = [ctx.bumps.callback_authority];
+        let callback_authority_seeds: &[&[u8]] = &[
+            CALLBACK_AUTHORITY_SEED,
+            pool_key.as_ref(),
+            &callback_authority_bump,
+        ];
+        let callback_program = ctx.accounts.callback_program.as_ref()
+            .ok_or(SolanaVeilError::InvalidCallbackProgram)?;
+        let callback_authority = ctx.accounts.callback_authority.as_ref()
+            .ok_or(SolanaVeilError::InvalidCallback)?;
+        
+        callback::invoke_withdraw_callback(
+            &callback_program.to_account_info(),
+            &callback_authority.to_account_info(),
+            callback_authority_seeds,
+            &pool.to_account_info(),
+            &ctx.accounts.recipient.to_account_info(),
+            callback_accounts,
+            nullifier_hash,
+            pool.mint,
+            withdraw_amount,
+            &callback.data,
+        )?;
+    }
+    
     msg!("Withdrawal successful for amount: {}", withdraw_amount);
     
     Ok(())
 }
 
+// Price `sol_fee` pool tokens in SOL from the pool's Pyth feeds and pay the lamports to
+// the relayer out of the pool's SOL fee reserve. Returns the lamports paid and the
+// token and SOL prices used.
+fn pay_sol_fee(accounts: &Withdraw, sol_fee: u64) -> Result<(u64, OraclePrice, OraclePrice)> {
+    let sol_fee_config = accounts.sol_fee_config.as_ref()
+        .ok_or(SolanaVeilError::InvalidSolFee)?;
+    let sol_fee_reserve = accounts.sol_fee_reserve.as_ref()
+        .ok_or(SolanaVeilError::InvalidSolFee)?;
+    let token_price_feed = accounts.token_price_feed.as_ref()
+        .ok_or(SolanaVeilError::InvalidPriceFeed)?;
+    let sol_price_feed = accounts.sol_price_feed.as_ref()
+        .ok_or(SolanaVeilError::InvalidPriceFeed)?;
+    let mint = accounts.mint.as_ref()
+        .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+    let relayer = accounts.relayer.as_ref()
+        .ok_or(SolanaVeilError::InvalidRelayer)?;
+    
+    let now = Clock::get()?.unix_timestamp;
+    let load = |feed: &AccountInfo| oracle::load_price(
+        feed,
+        &sol_fee_config.pyth_program,
+        now,
+        sol_fee_config.max_price_age_seconds,
+        sol_fee_config.max_confidence_basis_points,
+    );
+    let token_price = load(&token_price_feed.to_account_info())?;
+    let sol_price = load(&sol_price_feed.to_account_info())?;
+    
+    let lamports = oracle::token_amount_in_lamports(sol_fee, mint.decimals, &token_price, &sol_price)?;
+    
+    // Never dip into the reserve's rent reserve
+    let available = sol_fee_reserve.lamports().saturating_sub(sol_vault_rent_reserve()?);
+    if lamports > available {
+        return Err(SolanaVeilError::InsufficientSolFeeReserve.into());
+    }
+    
+    if lamports > 0 {
+        let pool_key = accounts.pool.key();
+        let reserve_seeds: &[&[u8]] = &[
+            SOL_FEE_RESERVE_SEED,
+            pool_key.as_ref(),
+            &[sol_fee_config.reserve_bump],
+        ];
+        
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: sol_fee_reserve.to_account_info(),
+                    to: relayer.to_account_info(),
+                },
+                &[reserve_seeds],
+            ),
+            lamports,
+        )?;
+    }
+    
+    Ok((lamports, token_price, sol_price))
+}
+
+// Validate the recipient's token account for SPL withdrawals. A brand-new recipient wallet
+// may pass its not-yet-created associated token account instead; it is created here with
+// the transaction signer (the relayer, for relayed withdrawals) paying the rent, which the
+// relayer recovers through the withdrawal fee.
+fn prepare_recipient_token_account<'info>(accounts: &Withdraw<'info>) -> Result<AccountInfo<'info>> {
+    let recipient_token_account = accounts.recipient_token_account.as_ref()
+        .ok_or(SolanaVeilError::InvalidTokenAccount)?
+        .to_account_info();
+    
+    let mint = accounts.mint.as_ref()
+        .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+    
+    let token_program = accounts.token_program.as_ref()
+        .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+    
+    if recipient_token_account.data_is_empty() {
+        // Only the recipient's canonical associated token account may be created
+        let expected = get_associated_token_address_with_program_id(
+            &accounts.recipient.key(),
+            &mint.key(),
+            &token_program.key(),
+        );
+        if recipient_token_account.key() != expected {
+            return Err(SolanaVeilError::InvalidRecipient.into());
+        }
+        
+        let associated_token_program = accounts.associated_token_program.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+        
+        associated_token::create(CpiContext::new(
+            associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: accounts.user.to_account_info(),
+                associated_token: recipient_token_account.clone(),
+                authority: accounts.recipient.to_account_info(),
+                mint: mint.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+        ))?;
+        
+        msg!("Created recipient token account: {}", recipient_token_account.key());
+    } else {
+        if *recipient_token_account.owner != token_program.key() {
+            return Err(SolanaVeilError::InvalidTokenAccount.into());
+        }
+        let token_account = TokenAccount::try_deserialize(&mut &recipient_token_account.try_borrow_data()?[..])
+            .map_err(|_| SolanaVeilError::InvalidTokenAccount)?;
+        
+        if token_account.owner != accounts.recipient.key() {
+            return Err(SolanaVeilError::InvalidRecipient.into());
+        }
+        
+        if token_account.mint != mint.key() {
+            return Err(SolanaVeilError::TokenAccountMintMismatch.into());
+        }
+    }
+    
+    Ok(recipient_token_account)
+}
+
 #[derive(Accounts)]
-#[instruction(
-    proof_data: Vec<u8>,
-    root: [u8; 32],
-    nullifier_hash: [u8; 32],
-    recipient: Pubkey,
-    fee: u64
-)]
+#[instruction(args: WithdrawArgs, callback: Option<WithdrawCallback>, quote: Option<RelayerQuote>)]
 pub struct Withdraw<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     
-    #[account(
-        mut,
-        constraint = pool.is_active @ SolanaVeilError::PoolInactive
-    )]
+    #[account(mut)]
     pub pool: Account<'info, Pool>,
     
     #[account(
         constraint = tree.key() == pool.tree @ SolanaVeilError::InvalidMerkleTree,
         constraint = tree.pool == pool.key() @ SolanaVeilError::InvalidMerkleTree
     )]
-    pub tree: Account<'info, MerkleTree>,
+    pub tree: Box<Account<'info, MerkleTree>>,
+    
+    #[account(
+        seeds = [b"checkpoints".as_ref(), pool.key().as_ref()],
+        bump = checkpoints.bump
+    )]
+    pub checkpoints: Box<Account<'info, DepositCheckpoints>>,
+    
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// Verifying key of the pool's withdraw circuit
+    #[account(
+        seeds = [
+            b"verifying_key".as_ref(),
+            pool.key().as_ref(),
+            &[VerifierCircuit::Withdraw as u8],
+        ],
+        bump = verifying_key.bump,
+    )]
+    pub verifying_key: Box<Account<'info, VerifyingKeyAccount>>,
     
     #[account(
         init,
@@ -226,7 +663,7 @@ pub struct Withdraw<'info> {
         space = 8 + size_of::<Nullifier>(),
         seeds = [
             b"nullifier".as_ref(),
-            &nullifier_hash,
+            &args.nullifier_hash,
             pool.key().as_ref()
         ],
         bump
@@ -235,58 +672,178 @@ pub struct Withdraw<'info> {
     
     #[account(
         mut,
-        constraint = !pool.is_spl_token || (
-            pool_token_account.is_some() && 
-            pool_token_account.as_ref().unwrap().key() == pool.token_vault
-        ) @ SolanaVeilError::InvalidTokenAccount
+        constraint = !pool.is_spl_token || pool_token_account.key() == pool.vault @ SolanaVeilError::InvalidTokenAccount
+    )]
+    pub pool_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for native SOL withdrawals
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, pool.key().as_ref()],
+        bump = pool.vault_bump,
+        constraint = pool.is_spl_token || sol_vault.key() == pool.vault @ SolanaVeilError::InvalidVault
     )]
-    pub pool_token_account: Option<Account<'info, TokenAccount>>,
+    pub sol_vault: Option<SystemAccount<'info>>,
+    
+    /// Must be the proof's recipient; a system account, or for callback withdrawals
+    /// optionally an account owned by the callback program
+    /// CHECK: Key and owner are validated in the handler
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
     
-    /// Must be a valid account for the recipient
+    /// Token account for the recipient if using SPL tokens. May be the recipient's
+    /// uninitialized associated token account, which is then created by the withdrawal.
+    /// CHECK: Validated (or created) in `prepare_recipient_token_account`
     #[account(mut)]
-    pub recipient: SystemAccount<'info>,
+    pub recipient_token_account: Option<UncheckedAccount<'info>>,
+    
+    /// Optional relayer account, required when a fee is paid
+    #[account(mut)]
+    pub relayer: Option<SystemAccount<'info>>,
     
-    /// Must be a valid token account for the recipient if using SPL tokens
+    /// Relayer account of `relayer`, the PDA `[b"relayer", relayer]`; required with a relayer
+    #[account(
+        mut,
+        seeds = [
+            b"relayer".as_ref(),
+            relayer.as_ref().map(|relayer| relayer.key()).unwrap_or_default().as_ref(),
+        ],
+        bump = relayer_stats.bump,
+    )]
+    pub relayer_stats: Option<Account<'info, Relayer>>,
+    
+    /// Marks the relayer quote's nonce as used; only required for relayed withdrawals
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UsedQuoteNonce::LEN,
+        seeds = [
+            b"quote_nonce".as_ref(),
+            relayer_stats.as_ref().map(|relayer| relayer.key()).unwrap_or_default().as_ref(),
+            &quote.map(|quote| quote.nonce).unwrap_or_default().to_le_bytes(),
+        ],
+        bump
+    )]
+    pub quote_nonce: Option<Account<'info, UsedQuoteNonce>>,
+    
+    /// The relayer's counters for the pool's mint; only required for relayed withdrawals
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + RelayerMintStats::LEN,
+        seeds = [
+            b"relayer_mint_stats".as_ref(),
+            relayer_stats.as_ref().map(|relayer| relayer.key()).unwrap_or_default().as_ref(),
+            pool.mint.as_ref(),
+        ],
+        bump
+    )]
+    pub relayer_mint_stats: Option<Account<'info, RelayerMintStats>>,
+    
+    /// Only required for relayed withdrawals, to find the ed25519 quote signature
+    /// CHECK: Address is checked against the instructions sysvar
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+    
+    /// Optional relayer token account for receiving fees
     #[account(
         mut,
-        constraint = !pool.is_spl_token || recipient_token_account.is_some() @ SolanaVeilError::InvalidTokenAccount,
         constraint = !pool.is_spl_token || (
-            recipient_token_account.is_some() &&
-            recipient_token_account.as_ref().unwrap().owner == recipient.key()
-        ) @ SolanaVeilError::InvalidRecipient
+            args.fee > 0 &&
+            relayer.as_ref().map(|relayer| relayer.key()) == Some(relayer_token_account.owner)
+        ) @ SolanaVeilError::InvalidRelayer
     )]
-    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+    pub relayer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Optional relayer account
+    /// Treasury vault receiving the protocol fee for native SOL pools
     #[account(
         mut,
-        constraint = fee == 0 || relayer.is_some() @ SolanaVeilError::InvalidFeeAmount
+        seeds = [b"treasury_vault".as_ref(), pool.key().as_ref()],
+        bump
     )]
-    pub relayer: Option<SystemAccount<'info>>,
+    pub treasury_vault: Option<SystemAccount<'info>>,
     
-    /// Optional relayer statistics account
+    /// Treasury vault receiving the protocol fee for SPL token pools
     #[account(
         mut,
-        constraint = (relayer.is_some() && relayer_stats.is_some()) || relayer.is_none() @ SolanaVeilError::InvalidRelayer
+        seeds = [b"treasury_vault".as_ref(), pool.key().as_ref()],
+        bump
     )]
-    pub relayer_stats: Option<Account<'info, Relayer>>,
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     
-    /// Optional relayer token account for receiving fees
+    /// SOL fee settings of the pool; only required when the quote pays part of the fee in SOL
     #[account(
         mut,
-        constraint = !pool.is_spl_token || relayer_token_account.is_none() || (
-            fee > 0 && 
-            relayer.is_some() && 
-            relayer_token_account.is_some() &&
-            relayer_token_account.as_ref().unwrap().owner == relayer.as_ref().unwrap().key()
-        ) @ SolanaVeilError::InvalidRelayer
+        seeds = [b"sol_fee_config".as_ref(), pool.key().as_ref()],
+        bump = sol_fee_config.bump
     )]
-    pub relayer_token_account: Option<Account<'info, TokenAccount>>,
+    pub sol_fee_config: Option<Box<Account<'info, SolFeeConfig>>>,
     
-    pub system_program: Program<'info, System>,
+    /// Pays the SOL part of the fee; only required when the quote pays part of the fee in SOL
+    #[account(
+        mut,
+        seeds = [SOL_FEE_RESERVE_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub sol_fee_reserve: Option<SystemAccount<'info>>,
+    
+    /// Pyth price of the pool token in USD; only required for SOL fees
+    /// CHECK: Must be the configured feed; parsed in `oracle::load_price`
+    #[account(
+        constraint = sol_fee_config.as_ref().map(|config| config.token_price_feed) == Some(token_price_feed.key()) @ SolanaVeilError::InvalidPriceFeed
+    )]
+    pub token_price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// Pyth price of SOL in USD; only required for SOL fees
+    /// CHECK: Must be the configured feed; parsed in `oracle::load_price`
+    #[account(
+        constraint = sol_fee_config.as_ref().map(|config| config.sol_price_feed) == Some(sol_price_feed.key()) @ SolanaVeilError::InvalidPriceFeed
+    )]
+    pub sol_price_feed: Option<UncheckedAccount<'info>>,
     
     /// Only required for SPL token withdrawals
-    pub token_program: Option<Program<'info, Token>>,
+    #[account(
+        constraint = mint.key() == pool.mint @ SolanaVeilError::TokenAccountMintMismatch,
+        mint::token_program = token_program
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    /// Only required if the pool has a screening program configured
+    /// CHECK: Must match `pool.screening_program`; policy accounts are passed as remaining accounts
+    #[account(
+        executable,
+        constraint = screening_program.key() == pool.screening_program @ SolanaVeilError::InvalidScreeningProgram
+    )]
+    pub screening_program: Option<AccountInfo<'info>>,
+    
+    /// Only required for withdrawals with a callback
+    /// CHECK: Must be whitelisted through `recipient_program`
+    #[account(executable)]
+    pub callback_program: Option<UncheckedAccount<'info>>,
+    
+    /// Whitelist entry for `callback_program`; only required for withdrawals with a callback
+    #[account(
+        seeds = [b"recipient_program".as_ref(), recipient_program.program_id.as_ref()],
+        bump = recipient_program.bump,
+        constraint = callback_program.as_ref().map(|program| program.key()) == Some(recipient_program.program_id) @ SolanaVeilError::InvalidCallbackProgram
+    )]
+    pub recipient_program: Option<Account<'info, RecipientProgram>>,
+    
+    /// Signs the callback in place of the pool; only required for withdrawals with a callback
+    /// CHECK: Data-less PDA, never written
+    #[account(
+        seeds = [CALLBACK_AUTHORITY_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub callback_authority: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+    
+    /// Only required for SPL token withdrawals (SPL Token or Token-2022)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    /// Only required when the recipient's associated token account must be created
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
 }
 
 #[event]
@@ -295,6 +852,8 @@ pub struct WithdrawEvent {
     pub nullifier_hash: [u8; 32],
     pub recipient: Pubkey,
     pub fee: u64,
+    pub protocol_fee: u64,
     pub amount: u64,
+    pub callback_hash: [u8; 32],
     pub timestamp: i64,
 }
\ No newline at end of file
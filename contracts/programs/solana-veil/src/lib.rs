@@ -1,12 +1,20 @@
 use anchor_lang::prelude::*;
 
+pub mod bridge;
+pub mod callback;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod oracle;
+pub mod quote;
+pub mod screening;
 pub mod state;
+pub mod token_extensions;
+pub mod vault;
 pub mod verifier;
 
 use instructions::*;
+use state::pool::*;
 
 declare_id!("SoLVeiLzW99jkVhgcJCKpCoECGzUWMKDJvpoNk5AJ4b");
 
@@ -15,64 +23,220 @@ pub mod solana_veil {
     use super::*;
 
     // === Pool Management Instructions ===
-    pub fn initialize_pool(ctx: Context<InitializePool>, params: PoolParams) -> Result<()> {
-        instructions::initialize_pool(ctx, params)
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        denomination: u64,
+        max_depth: u8,
+        is_spl_token: bool,
+    ) -> Result<()> {
+        state::pool::initialize_pool(ctx, denomination, max_depth, is_spl_token)
+    }
+    pub fn update_pool_config(
+        ctx: Context<UpdatePoolConfig>,
+        min_withdrawal_amount: Option<u64>,
+        pauser: Option<Pubkey>,
+        screening_program: Option<Pubkey>,
+        min_deposit_age_slots: Option<u64>,
+        limits: PoolLimits,
+    ) -> Result<()> {
+        state::pool::update_pool_config(
+            ctx,
+            min_withdrawal_amount,
+            pauser,
+            screening_program,
+            min_deposit_age_slots,
+            limits,
+        )
+    }
+    pub fn set_pool_pause_flags(ctx: Context<SetPoolPauseFlags>, pause_flags: u8) -> Result<()> {
+        state::pool::set_pool_pause_flags(ctx, pause_flags)
+    }
+
+    // === Protocol Config Instructions ===
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        protocol_fee_basis_points: u16,
+        treasury: Pubkey,
+        admin_timelock_seconds: i64,
+    ) -> Result<()> {
+        instructions::initialize_config(ctx, protocol_fee_basis_points, treasury, admin_timelock_seconds)
+    }
+    pub fn update_config(ctx: Context<UpdateConfig>, pools_enabled: Option<bool>) -> Result<()> {
+        instructions::update_config(ctx, pools_enabled)
+    }
+    pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+        instructions::initialize_treasury_vault(ctx)
+    }
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>, amount: u64) -> Result<()> {
+        instructions::claim_protocol_fees(ctx, amount)
+    }
+
+    // === Governance Instructions ===
+    pub fn propose_pool_authority(ctx: Context<ProposePoolAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_pool_authority(ctx, new_authority)
+    }
+    pub fn accept_pool_authority(ctx: Context<AcceptPoolAuthority>) -> Result<()> {
+        instructions::accept_pool_authority(ctx)
+    }
+    pub fn propose_tree_authority(ctx: Context<ProposeTreeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_tree_authority(ctx, new_authority)
+    }
+    pub fn accept_tree_authority(ctx: Context<AcceptTreeAuthority>) -> Result<()> {
+        instructions::accept_tree_authority(ctx)
+    }
+    pub fn propose_bridge_authority(ctx: Context<ProposeBridgeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_bridge_authority(ctx, new_authority)
+    }
+    pub fn accept_bridge_authority(ctx: Context<AcceptBridgeAuthority>) -> Result<()> {
+        instructions::accept_bridge_authority(ctx)
+    }
+    pub fn queue_admin_action(ctx: Context<QueueAdminAction>, action: state::AdminAction) -> Result<()> {
+        instructions::queue_admin_action(ctx, action)
     }
-    pub fn update_pool(ctx: Context<UpdatePool>, params: PoolParams) -> Result<()> {
-        instructions::update_pool(ctx, params)
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+        instructions::cancel_admin_action(ctx)
+    }
+    pub fn execute_admin_action(
+        ctx: Context<ExecuteAdminAction>,
+        verifying_key_data: Option<Vec<u8>>,
+    ) -> Result<()> {
+        instructions::execute_admin_action(ctx, verifying_key_data)
+    }
+    pub fn initialize_verifying_key(
+        ctx: Context<InitializeVerifyingKey>,
+        circuit: state::VerifierCircuit,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::initialize_verifying_key(ctx, circuit, data)
+    }
+
+    // === Access Control Instructions ===
+    pub fn initialize_access_control(ctx: Context<InitializeAccessControl>) -> Result<()> {
+        instructions::initialize_access_control(ctx)
+    }
+    pub fn grant_role(ctx: Context<ManageRoles>, member: Pubkey, role: state::Role) -> Result<()> {
+        instructions::grant_role(ctx, member, role)
+    }
+    pub fn revoke_role(ctx: Context<ManageRoles>, member: Pubkey, role: state::Role) -> Result<()> {
+        instructions::revoke_role(ctx, member, role)
+    }
+
+    // === Recipient Program Instructions ===
+    pub fn add_recipient_program(ctx: Context<AddRecipientProgram>) -> Result<()> {
+        instructions::add_recipient_program(ctx)
+    }
+    pub fn remove_recipient_program(ctx: Context<RemoveRecipientProgram>) -> Result<()> {
+        instructions::remove_recipient_program(ctx)
+    }
+
+    // === Pool Registry Instructions ===
+    pub fn initialize_pool_registry(ctx: Context<InitializePoolRegistry>) -> Result<()> {
+        instructions::initialize_pool_registry(ctx)
     }
 
     // === Deposit Instructions ===
-    pub fn deposit(ctx: Context<Deposit>, amount: u64, commitment: [u8; 32]) -> Result<()> {
-        instructions::deposit(ctx, amount, commitment)
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::deposit(ctx, commitment)
+    }
+    pub fn deposit_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Deposit<'info>>,
+        commitments: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::deposit_batch(ctx, commitments)
     }
 
     // === Withdraw Instructions ===
-    pub fn withdraw(
-        ctx: Context<Withdraw>,
-        proof_data: Vec<u8>,
-        root: [u8; 32],
-        nullifier_hash: [u8; 32],
-        recipient: Pubkey,
-        relayer: Pubkey,
-        fee: u64,
-        refund: u64,
-    ) -> Result<()> {
-        instructions::withdraw(
-            ctx,
-            proof_data,
-            root,
-            nullifier_hash,
-            recipient,
-            relayer,
-            fee,
-            refund,
-        )
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+        args: WithdrawArgs,
+        callback: Option<callback::WithdrawCallback>,
+        quote: Option<quote::RelayerQuote>,
+    ) -> Result<()> {
+        instructions::withdraw(ctx, args, callback, quote)
     }
 
     // === Tree Management Instructions ===
-    pub fn initialize_merkle_tree(ctx: Context<InitializeMerkleTree>, height: u32) -> Result<()> {
-        instructions::initialize_merkle_tree(ctx, height)
-    }
-    pub fn update_merkle_tree(ctx: Context<UpdateMerkleTree>, leaf: [u8; 32]) -> Result<()> {
-        instructions::update_merkle_tree(ctx, leaf)
+    pub fn batch_update_root(
+        ctx: Context<BatchUpdateRoot>,
+        proof_data: Vec<u8>,
+        new_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::batch_update_root(ctx, proof_data, new_root)
     }
 
     // === Relayer Management Instructions ===
-    pub fn register_relayer(ctx: Context<RegisterRelayer>, fee: u64) -> Result<()> {
-        instructions::register_relayer(ctx, fee)
+    pub fn initialize_relayer_config(
+        ctx: Context<InitializeRelayerConfig>,
+        required_stake: u64,
+        unstake_cooldown_slots: u64,
+    ) -> Result<()> {
+        instructions::initialize_relayer_config(ctx, required_stake, unstake_cooldown_slots)
+    }
+    pub fn set_relayer(ctx: Context<SetRelayer>, is_active: bool, fee_basis_points: u16) -> Result<()> {
+        instructions::set_relayer(ctx, is_active, fee_basis_points)
+    }
+    pub fn stake_relayer(ctx: Context<StakeRelayer>, amount: u64) -> Result<()> {
+        instructions::stake_relayer(ctx, amount)
+    }
+    pub fn request_unstake(ctx: Context<RequestUnstake>) -> Result<()> {
+        instructions::request_unstake(ctx)
+    }
+    pub fn complete_unstake(ctx: Context<CompleteUnstake>) -> Result<()> {
+        instructions::complete_unstake(ctx)
+    }
+    pub fn slash_relayer(
+        ctx: Context<SlashRelayer>,
+        amount: u64,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::slash_relayer(ctx, amount, evidence_hash)
+    }
+    pub fn get_relayer_stats(ctx: Context<GetRelayerStats>) -> Result<()> {
+        instructions::get_relayer_stats(ctx)
+    }
+
+    // === Relayer Directory Instructions ===
+    pub fn initialize_relayer_directory(ctx: Context<InitializeRelayerDirectory>) -> Result<()> {
+        instructions::initialize_relayer_directory(ctx)
+    }
+    pub fn register_relayer_metadata(
+        ctx: Context<RegisterRelayerMetadata>,
+        params: state::RelayerMetadataParams,
+    ) -> Result<()> {
+        instructions::register_relayer_metadata(ctx, params)
+    }
+    pub fn update_relayer_metadata(
+        ctx: Context<UpdateRelayerMetadata>,
+        params: state::RelayerMetadataParams,
+    ) -> Result<()> {
+        instructions::update_relayer_metadata(ctx, params)
     }
-    pub fn update_relayer(ctx: Context<UpdateRelayer>, fee: u64) -> Result<()> {
-        instructions::update_relayer(ctx, fee)
+
+    // === SOL Fee Instructions ===
+    pub fn configure_sol_fee(ctx: Context<ConfigureSolFee>, params: state::SolFeeParams) -> Result<()> {
+        instructions::configure_sol_fee(ctx, params)
+    }
+    pub fn withdraw_sol_fee_reserve(ctx: Context<WithdrawSolFeeReserve>, amount: u64) -> Result<()> {
+        instructions::withdraw_sol_fee_reserve(ctx, amount)
     }
 
     // === Bridge Instructions ===
     pub fn initialize_bridge(
         ctx: Context<InitializeBridge>,
         fee_basis_points: u16,
-        bump: u8,
+        wormhole_finality: u8,
     ) -> Result<()> {
-        instructions::initialize_bridge(ctx, fee_basis_points, bump)
+        instructions::initialize_bridge(ctx, fee_basis_points, wormhole_finality)
+    }
+    pub fn update_bridge_config(
+        ctx: Context<UpdateBridgeConfig>,
+        new_wormhole_finality: Option<u8>,
+        new_pauser: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_bridge_config(ctx, new_wormhole_finality, new_pauser)
     }
     pub fn add_destination_chain(
         ctx: Context<UpdateBridge>,
@@ -81,9 +245,8 @@ pub mod solana_veil {
         instructions::add_destination_chain(ctx, chain_id)
     }
     pub fn add_supported_token(
-        ctx: Context<UpdateBridge>,
+        ctx: Context<AddSupportedToken>,
         chain_id: u16,
-        mint: Pubkey,
         dest_token_id: u64,
         min_amount: u64,
         max_amount: u64,
@@ -91,64 +254,45 @@ pub mod solana_veil {
         instructions::add_supported_token(
             ctx,
             chain_id,
-            mint,
             dest_token_id,
             min_amount,
             max_amount,
         )
     }
-    pub fn lock_tokens_for_bridge(
-        ctx: Context<LockTokensForBridge>,
+    pub fn register_external_emitter(
+        ctx: Context<RegisterExternalEmitter>,
+        chain_id: u16,
+        emitter_address: [u8; 32],
+    ) -> Result<()> {
+        instructions::register_external_emitter(ctx, chain_id, emitter_address)
+    }
+    pub fn initiate_cross_chain_transfer(
+        ctx: Context<InitiateCrossChainTransfer>,
         amount: u64,
         destination_chain_id: u16,
         destination_address: [u8; 32],
-        nullifier: [u8; 32],
-        bump: u8,
+        commitment: [u8; 32],
+        nonce: u32,
     ) -> Result<()> {
-        instructions::lock_tokens_for_bridge(
+        instructions::initiate_cross_chain_transfer(
             ctx,
             amount,
             destination_chain_id,
             destination_address,
-            nullifier,
-            bump,
+            commitment,
+            nonce,
         )
     }
     pub fn process_incoming_transfer(
         ctx: Context<ProcessIncomingTransfer>,
-        proof_data: Vec<u8>,
-        source_chain_id: u16,
-        source_nullifier: [u8; 32],
-        amount: u64,
-        recipient: Pubkey,
+        vaa_hash: [u8; 32],
     ) -> Result<()> {
-        instructions::process_incoming_transfer(
-            ctx,
-            proof_data,
-            source_chain_id,
-            source_nullifier,
-            amount,
-            recipient,
-        )
-    }
-    pub fn set_bridge_paused(
-        ctx: Context<UpdateBridge>,
-        paused: bool,
-    ) -> Result<()> {
-        instructions::set_bridge_paused(ctx, paused)
-    }
-    pub fn initialize_nullifier_account(
-        ctx: Context<InitializeNullifier>,
-        bump: u8,
-    ) -> Result<()> {
-        instructions::initialize_nullifier_account(ctx, bump)
+        instructions::process_incoming_transfer(ctx, vaa_hash)
     }
-    // Additional bridge instructions
-    pub fn initialize_relayer_config(
-        ctx: Context<InitializeRelayer>,
-        required_stake: u64,
-        bump: u8,
+    pub fn set_bridge_pause_flags(
+        ctx: Context<SetBridgePauseFlags>,
+        pause_flags: u8,
     ) -> Result<()> {
-        instructions::initialize_relayer_config(ctx, required_stake, bump)
+        instructions::set_bridge_pause_flags(ctx, pause_flags)
     }
-}
\ No newline at end of file
+}
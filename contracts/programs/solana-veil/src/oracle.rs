@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use crate::errors::SolanaVeilError;
+
+/// Magic number at the start of every Pyth account
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+
+/// Pyth account layout version this parser understands
+const PYTH_VERSION: u32 = 2;
+
+/// Pyth account type of price accounts
+const PYTH_PRICE_ACCOUNT: u32 = 3;
+
+/// Aggregate price status meaning the price is currently trading
+const PYTH_STATUS_TRADING: u32 = 1;
+
+// Offsets into a Pyth v2 price account
+const EXPONENT_OFFSET: usize = 20;
+const PUBLISH_TIME_OFFSET: usize = 96;
+const AGGREGATE_PRICE_OFFSET: usize = 208;
+const AGGREGATE_CONF_OFFSET: usize = 216;
+const AGGREGATE_STATUS_OFFSET: usize = 224;
+
+/// Smallest price account holding every field read here
+pub const PYTH_PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// Decimals of native SOL
+const LAMPORT_DECIMALS: i32 = 9;
+
+/// Aggregate price read from a Pyth price account: `price * 10^exponent`,
+/// with `conf` in the same units
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Read the aggregate price of a Pyth v2 price account owned by `pyth_program`.
+///
+/// The price must be trading, positive, no older than `max_age_seconds` and have
+/// a confidence interval within `max_confidence_basis_points` of the price.
+pub fn load_price(
+    price_account: &AccountInfo,
+    pyth_program: &Pubkey,
+    now: i64,
+    max_age_seconds: i64,
+    max_confidence_basis_points: u16,
+) -> Result<OraclePrice> {
+    // Anyone can write a Pyth-shaped account, only the Pyth program's are prices
+    if price_account.owner != pyth_program {
+        return Err(SolanaVeilError::InvalidPriceFeed.into());
+    }
+
+    let data = price_account.try_borrow_data()?;
+    if data.len() < PYTH_PRICE_ACCOUNT_MIN_LEN {
+        return Err(SolanaVeilError::InvalidPriceFeed.into());
+    }
+
+    let read_u32 = |at: usize| u32::from_le_bytes(data[at..at + 4].try_into().unwrap());
+    let read_u64 = |at: usize| u64::from_le_bytes(data[at..at + 8].try_into().unwrap());
+
+    if read_u32(0) != PYTH_MAGIC || read_u32(4) != PYTH_VERSION || read_u32(8) != PYTH_PRICE_ACCOUNT {
+        return Err(SolanaVeilError::InvalidPriceFeed.into());
+    }
+
+    let price = OraclePrice {
+        price: read_u64(AGGREGATE_PRICE_OFFSET) as i64,
+        conf: read_u64(AGGREGATE_CONF_OFFSET),
+        exponent: read_u32(EXPONENT_OFFSET) as i32,
+        publish_time: read_u64(PUBLISH_TIME_OFFSET) as i64,
+    };
+
+    if read_u32(AGGREGATE_STATUS_OFFSET) != PYTH_STATUS_TRADING || price.price <= 0 {
+        return Err(SolanaVeilError::InvalidPriceFeed.into());
+    }
+
+    if now.saturating_sub(price.publish_time) > max_age_seconds {
+        return Err(SolanaVeilError::StalePrice.into());
+    }
+
+    let conf_limit = (price.price as u128) * (max_confidence_basis_points as u128);
+    if (price.conf as u128) * 10000 > conf_limit {
+        return Err(SolanaVeilError::PriceConfidenceTooWide.into());
+    }
+
+    Ok(price)
+}
+
+/// Lamports worth `token_amount` base units of a token with `token_decimals`,
+/// given USD prices of the token and of SOL. Rounds down.
+pub fn token_amount_in_lamports(
+    token_amount: u64,
+    token_decimals: u8,
+    token_price: &OraclePrice,
+    sol_price: &OraclePrice,
+) -> Result<u64> {
+    // lamports = amount * token_price * 10^(token_exp - sol_exp + 9 - decimals) / sol_price
+    let scale = token_price.exponent
+        .checked_sub(sol_price.exponent)
+        .and_then(|scale| scale.checked_add(LAMPORT_DECIMALS))
+        .and_then(|scale| scale.checked_sub(token_decimals as i32))
+        .ok_or(SolanaVeilError::CalculationError)?;
+    let power = 10u128.checked_pow(scale.unsigned_abs())
+        .ok_or(SolanaVeilError::CalculationError)?;
+
+    let mut numerator = (token_amount as u128)
+        .checked_mul(token_price.price as u128)
+        .ok_or(SolanaVeilError::CalculationError)?;
+    let mut denominator = sol_price.price as u128;
+    if scale >= 0 {
+        numerator = numerator.checked_mul(power)
+            .ok_or(SolanaVeilError::CalculationError)?;
+    } else {
+        denominator = denominator.checked_mul(power)
+            .ok_or(SolanaVeilError::CalculationError)?;
+    }
+
+    u64::try_from(numerator / denominator)
+        .map_err(|_| SolanaVeilError::CalculationError.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_000;
+
+    fn price_account_data() -> Vec<u8> {
+        let mut data = vec![0u8; PYTH_PRICE_ACCOUNT_MIN_LEN];
+        data[0..4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PYTH_VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PYTH_PRICE_ACCOUNT.to_le_bytes());
+        data[EXPONENT_OFFSET..EXPONENT_OFFSET + 4].copy_from_slice(&(-8i32).to_le_bytes());
+        data[PUBLISH_TIME_OFFSET..PUBLISH_TIME_OFFSET + 8].copy_from_slice(&NOW.to_le_bytes());
+        data[AGGREGATE_PRICE_OFFSET..AGGREGATE_PRICE_OFFSET + 8].copy_from_slice(&100_000_000i64.to_le_bytes());
+        data[AGGREGATE_STATUS_OFFSET..AGGREGATE_STATUS_OFFSET + 4].copy_from_slice(&PYTH_STATUS_TRADING.to_le_bytes());
+        data
+    }
+
+    fn load_owned_by(owner: &Pubkey, pyth_program: &Pubkey) -> Result<OraclePrice> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = price_account_data();
+        let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, owner, false, 0);
+        load_price(&account, pyth_program, NOW, 60, 100)
+    }
+
+    #[test]
+    fn rejects_price_accounts_not_owned_by_pyth() {
+        let pyth_program = Pubkey::new_unique();
+
+        assert_eq!(load_owned_by(&pyth_program, &pyth_program).unwrap().price, 100_000_000);
+        assert_eq!(
+            load_owned_by(&Pubkey::new_unique(), &pyth_program).unwrap_err(),
+            SolanaVeilError::InvalidPriceFeed.into()
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::errors::SolanaVeilError;
+
+/// Domain separator prefixed to every signed quote so relayer keys cannot be
+/// tricked into signing a quote through an unrelated message
+pub const QUOTE_DOMAIN: &[u8] = b"solana-veil:relayer-quote:v1";
+
+/// Size of one `Ed25519SignatureOffsets` record in the ed25519 program's instruction data
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+
+/// Offsets start after the signature count and a padding byte
+const SIGNATURE_OFFSETS_START: usize = 2;
+
+/// Instruction index meaning "this instruction" in the ed25519 offsets
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Fee quote a relayer signs for a withdrawal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RelayerQuote {
+    /// Pool the quote is valid for
+    pub pool: Pubkey,
+
+    /// Highest fee the relayer may take
+    pub fee: u64,
+
+    /// Last slot the quote can be used in
+    pub expiry_slot: u64,
+
+    /// Relayer-chosen nonce; each nonce can be used once
+    pub nonce: u64,
+
+    /// Until this slot only the relayer itself may submit the withdrawal (0 = no exclusivity)
+    pub exclusive_until_slot: u64,
+
+    /// Part of the fee, in pool token units, paid out in SOL instead (SPL pools only)
+    pub sol_fee: u64,
+}
+
+impl RelayerQuote {
+    /// Bytes the relayer signs:
+    /// `QUOTE_DOMAIN || pool || fee || expiry_slot || nonce || exclusive_until_slot || sol_fee`,
+    /// integers little-endian
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(QUOTE_DOMAIN.len() + 32 + 8 + 8 + 8 + 8 + 8);
+        message.extend_from_slice(QUOTE_DOMAIN);
+        message.extend_from_slice(self.pool.as_ref());
+        message.extend_from_slice(&self.fee.to_le_bytes());
+        message.extend_from_slice(&self.expiry_slot.to_le_bytes());
+        message.extend_from_slice(&self.nonce.to_le_bytes());
+        message.extend_from_slice(&self.exclusive_until_slot.to_le_bytes());
+        message.extend_from_slice(&self.sol_fee.to_le_bytes());
+        message
+    }
+}
+
+/// Check that an ed25519 program instruction earlier in this transaction verified
+/// `signer`'s signature over `quote`.
+///
+/// The ed25519 program has already checked the signature by the time we run; we only
+/// confirm it covered the right key and message. Only offsets pointing into the ed25519
+/// instruction itself are accepted, so the key and message cannot be swapped for data
+/// elsewhere in the transaction.
+pub fn verify_quote_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    quote: &RelayerQuote,
+) -> Result<()> {
+    let message = quote.message();
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+        if instruction.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        if signs_message(&instruction.data, signer, &message) {
+            return Ok(());
+        }
+    }
+
+    Err(SolanaVeilError::InvalidQuoteSignature.into())
+}
+
+/// Whether ed25519 instruction data contains a signature by `signer` over `message`
+fn signs_message(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let Some(&count) = data.first() else {
+        return false;
+    };
+
+    (0..count as usize).any(|i| {
+        let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_LEN;
+        let Some(offsets) = data.get(start..start + SIGNATURE_OFFSETS_LEN) else {
+            return false;
+        };
+        let field = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        let signature_instruction = field(2);
+        let public_key_offset = field(4) as usize;
+        let public_key_instruction = field(6);
+        let message_offset = field(8) as usize;
+        let message_size = field(10) as usize;
+        let message_instruction = field(12);
+
+        if signature_instruction != CURRENT_INSTRUCTION
+            || public_key_instruction != CURRENT_INSTRUCTION
+            || message_instruction != CURRENT_INSTRUCTION
+        {
+            return false;
+        }
+
+        data.get(public_key_offset..public_key_offset + 32) == Some(signer.as_ref())
+            && data.get(message_offset..message_offset + message_size) == Some(message)
+    })
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+/// Name of the instruction every screening program must expose.
+/// It is called as `screen(pool: Pubkey, subject: Pubkey)` with the policy
+/// accounts the program needs (e.g. its deny list) and must fail to reject.
+const SCREEN_INSTRUCTION: &[u8] = b"global:screen";
+
+/// Returns true if the pool has a screening program configured
+pub fn is_screening_enabled(screening_program: &Pubkey) -> bool {
+    *screening_program != Pubkey::default()
+}
+
+/// CPI into the pool's screening program to check `subject`.
+///
+/// `policy_accounts` (normally `ctx.remaining_accounts`) are forwarded with their
+/// writability but never as signers, so the depositor's or relayer's signature does
+/// not reach the screening program. Any error returned by the screening program aborts the transaction.
+pub fn screen_address<'info>(
+    screening_program: &AccountInfo<'info>,
+    pool: Pubkey,
+    subject: Pubkey,
+    policy_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let mut data = Vec::with_capacity(8 + 32 + 32);
+    data.extend_from_slice(&hash(SCREEN_INSTRUCTION).to_bytes()[..8]);
+    data.extend_from_slice(pool.as_ref());
+    data.extend_from_slice(subject.as_ref());
+
+    let accounts = policy_accounts
+        .iter()
+        .map(|account| {
+            if account.is_writable {
+                AccountMeta::new(account.key(), false)
+            } else {
+                AccountMeta::new_readonly(account.key(), false)
+            }
+        })
+        .collect();
+
+    let instruction = Instruction {
+        program_id: screening_program.key(),
+        accounts,
+        data,
+    };
+
+    let mut account_infos = policy_accounts.to_vec();
+    account_infos.push(screening_program.clone());
+
+    msg!("Screening {} for pool {}", subject, pool);
+    invoke(&instruction, &account_infos)?;
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use crate::errors::SolanaVeilError;
+
+/// Maximum number of distinct keys that can hold roles
+pub const MAX_ROLE_MEMBERS: usize = 32;
+
+/// Operational role that can be granted to a key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// Grants and revokes roles, manages pool and tree settings
+    Admin,
+    /// Pauses and resumes pools and the bridge
+    Guardian,
+    /// Queues fee and treasury changes
+    FeeManager,
+    /// Manages bridge settings, chains, tokens and external emitters
+    BridgeOperator,
+    /// Manages proof verifying keys
+    KeyManager,
+}
+
+impl Role {
+    /// Bit representing this role in `RoleAssignment.roles`
+    pub fn mask(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+/// Roles held by a single key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
+pub struct RoleAssignment {
+    /// Key holding the roles
+    pub member: Pubkey,
+
+    /// Bitmask of `Role::mask` values
+    pub roles: u8,
+}
+
+impl RoleAssignment {
+    /// Serialized size of a RoleAssignment
+    pub const LEN: usize =
+        32 +    // member: Pubkey
+        1;      // roles: u8
+}
+
+/// Program-wide role registry, seeds `[b"access_control"]`
+#[account]
+pub struct AccessControl {
+    /// Keys holding at least one role
+    pub members: Vec<RoleAssignment>,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AccessControl {
+    /// Size of the AccessControl struct for space allocation
+    pub const LEN: usize =
+        4 + RoleAssignment::LEN * MAX_ROLE_MEMBERS +   // members: Vec<RoleAssignment>
+        1;                                              // bump: u8
+
+    /// Whether `member` holds `role`
+    pub fn has_role(&self, member: &Pubkey, role: Role) -> bool {
+        self.members.iter()
+            .any(|assignment| assignment.member == *member && assignment.roles & role.mask() != 0)
+    }
+
+    /// Fail unless `member` holds `role`
+    pub fn require_role(&self, member: &Pubkey, role: Role) -> Result<()> {
+        if !self.has_role(member, role) {
+            msg!("{} is missing role {:?}", member, role);
+            return Err(SolanaVeilError::MissingRole.into());
+        }
+        Ok(())
+    }
+
+    /// Give `role` to `member`
+    pub fn grant(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        if let Some(assignment) = self.members.iter_mut().find(|assignment| assignment.member == member) {
+            assignment.roles |= role.mask();
+            return Ok(());
+        }
+        if self.members.len() >= MAX_ROLE_MEMBERS {
+            return Err(SolanaVeilError::TooManyRoleMembers.into());
+        }
+        self.members.push(RoleAssignment { member, roles: role.mask() });
+        Ok(())
+    }
+
+    /// Take `role` from `member`, dropping the member once it holds no roles.
+    /// The last Admin cannot be revoked, so roles can always be managed.
+    pub fn revoke(&mut self, member: &Pubkey, role: Role) -> Result<()> {
+        if role == Role::Admin {
+            let admins = self.members.iter()
+                .filter(|assignment| assignment.roles & Role::Admin.mask() != 0)
+                .count();
+            if admins == 1 && self.has_role(member, Role::Admin) {
+                return Err(SolanaVeilError::LastAdmin.into());
+            }
+        }
+
+        let position = self.members.iter()
+            .position(|assignment| assignment.member == *member)
+            .ok_or(SolanaVeilError::MissingRole)?;
+        self.members[position].roles &= !role.mask();
+        if self.members[position].roles == 0 {
+            self.members.swap_remove(position);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::poseidon::{hashv, Endianness, Parameters};
+use crate::errors::SolanaVeilError;
+use super::MerkleTree;
+
+/// Depth of every pool tree (must match the withdraw and batch_update circuits)
+pub const TREE_DEPTH: u8 = 20;
+
+/// Commitments per root update batch (must match the batch_update circuit)
+pub const ROOT_UPDATE_BATCH_SIZE: u64 = 8;
+
+/// Full batches that may wait for a root update before deposits are rejected
+pub const MAX_SEALED_BATCHES: usize = 16;
+
+/// Number of recent roots withdrawals may be proven against
+pub const ROOT_HISTORY_SIZE: usize = 30;
+
+/// Commitments appended to the tree but not yet reflected in its root
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
+pub struct CommitmentBatch {
+    /// Poseidon hash chain of the batch's commitments:
+    /// `H(...H(H(0, c0), c1)..., cn)`
+    pub hash_chain: [u8; 32],
+
+    /// Leaf index of the first commitment in the batch
+    pub start_index: u64,
+
+    /// Number of commitments in the batch
+    pub count: u64,
+}
+
+impl MerkleTree {
+    /// Number of leaves that can still be appended before the tree is full
+    pub fn remaining_capacity(&self) -> u64 {
+        (1u64 << self.max_depth).saturating_sub(self.num_leaves)
+    }
+
+    /// Number of commitments that can still be appended before the root update
+    /// backlog is full, i.e. before `append_commitment` fails with `RootUpdateBacklogFull`
+    pub fn backlog_capacity(&self) -> u64 {
+        let free_batches = (MAX_SEALED_BATCHES as u64).saturating_sub(self.sealed_count as u64);
+        ((free_batches + 1) * ROOT_UPDATE_BATCH_SIZE - 1).saturating_sub(self.open_batch.count)
+    }
+
+    /// Append a commitment to the open batch, sealing the batch once it is full.
+    /// The root is not touched; `batch_update_root` applies batches with a proof.
+    pub fn append_commitment(&mut self, commitment: [u8; 32]) -> Result<u64> {
+        let leaf_index = self.num_leaves;
+
+        if self.remaining_capacity() == 0 {
+            return Err(SolanaVeilError::MerkleTreeFull.into());
+        }
+
+        if self.open_batch.count + 1 == ROOT_UPDATE_BATCH_SIZE
+            && self.sealed_count as usize >= MAX_SEALED_BATCHES
+        {
+            return Err(SolanaVeilError::RootUpdateBacklogFull.into());
+        }
+
+        // Hash with the same Poseidon parameters as circomlib so the circuit can recompute the chain
+        let hash_chain = hashv(
+            Parameters::Bn254X5,
+            Endianness::BigEndian,
+            &[&self.open_batch.hash_chain, &commitment],
+        )
+        .map_err(|_| SolanaVeilError::InvalidCommitment)?;
+
+        if self.open_batch.count == 0 {
+            self.open_batch.start_index = leaf_index;
+        }
+        self.open_batch.hash_chain = hash_chain.to_bytes();
+        self.open_batch.count += 1;
+
+        if self.open_batch.count == ROOT_UPDATE_BATCH_SIZE {
+            let tail = (self.sealed_head as usize + self.sealed_count as usize) % MAX_SEALED_BATCHES;
+            self.sealed_batches[tail] = self.open_batch;
+            self.sealed_count += 1;
+            self.open_batch = CommitmentBatch::default();
+        }
+
+        self.num_leaves = self.num_leaves.checked_add(1)
+            .ok_or(SolanaVeilError::CalculationError)?;
+
+        Ok(leaf_index)
+    }
+
+    /// Oldest batch awaiting a root update: the oldest sealed batch if any,
+    /// otherwise the partially filled open batch
+    pub fn next_pending_batch(&self) -> Option<CommitmentBatch> {
+        if self.sealed_count > 0 {
+            Some(self.sealed_batches[self.sealed_head as usize])
+        } else if self.open_batch.count > 0 {
+            Some(self.open_batch)
+        } else {
+            None
+        }
+    }
+
+    /// Make `root` the current root, remembering it in the root history
+    pub fn push_root(&mut self, root: [u8; 32]) {
+        let head = (self.root_history_head as usize + 1) % ROOT_HISTORY_SIZE;
+        self.root_history[head] = root;
+        self.root_history_head = head as u8;
+        self.root = root;
+    }
+
+    /// Whether `root` is the current root or one of the last `ROOT_HISTORY_SIZE`
+    /// roots set by batch updates
+    pub fn is_known_root(&self, root: &[u8; 32]) -> bool {
+        *root == self.root || (*root != [0; 32] && self.root_history.contains(root))
+    }
+
+    /// Drop the batch returned by `next_pending_batch` once the root covers it
+    pub fn consume_pending_batch(&mut self) {
+        if self.sealed_count > 0 {
+            self.sealed_batches[self.sealed_head as usize] = CommitmentBatch::default();
+            self.sealed_head = ((self.sealed_head as usize + 1) % MAX_SEALED_BATCHES) as u8;
+            self.sealed_count -= 1;
+        } else {
+            self.open_batch = CommitmentBatch::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_tree() -> MerkleTree {
+        let bytes = vec![0u8; std::mem::size_of::<MerkleTree>()];
+        let mut tree = MerkleTree::deserialize(&mut &bytes[..]).unwrap();
+        tree.max_depth = 20;
+        tree
+    }
+
+    #[test]
+    fn backlog_capacity_counts_the_appends_left() {
+        let mut tree = empty_tree();
+
+        let capacity = tree.backlog_capacity();
+        for appended in 0..capacity {
+            assert_eq!(tree.backlog_capacity(), capacity - appended);
+            tree.append_commitment([1; 32]).unwrap();
+        }
+
+        assert_eq!(tree.backlog_capacity(), 0);
+        assert_eq!(
+            tree.append_commitment([1; 32]).unwrap_err(),
+            SolanaVeilError::RootUpdateBacklogFull.into()
+        );
+    }
+
+    #[test]
+    fn accepts_roots_until_they_leave_the_history() {
+        let mut tree = empty_tree();
+        let root = |i: usize| [i as u8 + 1; 32];
+
+        for i in 0..ROOT_HISTORY_SIZE {
+            tree.push_root(root(i));
+        }
+        assert!((0..ROOT_HISTORY_SIZE).all(|i| tree.is_known_root(&root(i))));
+
+        tree.push_root(root(ROOT_HISTORY_SIZE));
+        assert!(!tree.is_known_root(&root(0)));
+        assert!(tree.is_known_root(&root(1)));
+        assert!(tree.is_known_root(&root(ROOT_HISTORY_SIZE)));
+
+        // Unused history slots never make the zero root valid
+        assert!(!empty_tree().is_known_root(&[1; 32]));
+        assert!(!tree.is_known_root(&[0; 32]));
+    }
+}
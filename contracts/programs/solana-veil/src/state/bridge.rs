@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use std::collections::BTreeMap; // For registered emitters map
 
 pub const MAX_SUPPORTED_CHAINS: usize = 10;
 pub const MAX_SUPPORTED_TOKENS: usize = 20;
@@ -10,6 +9,8 @@ pub const MAX_RELAYERS: usize = 50; // Example limit
 pub struct BridgeConfig {
     /// Authority who can manage the bridge settings.
     pub authority: Pubkey,
+    /// Proposed new authority awaiting acceptance (zeros if none).
+    pub pending_authority: Pubkey,
     /// Account that collects bridging fees.
     pub treasury: Pubkey,
     /// Wormhole Core Bridge program ID.
@@ -20,8 +21,10 @@ pub struct BridgeConfig {
     pub fee_basis_points: u16,
     /// Bump seed for the BridgeConfig PDA.
     pub bump: u8,
-    /// Flag to pause bridge operations.
-    pub paused: bool,
+    /// Bridge directions currently paused, see `pause_flags`.
+    pub pause_flags: u8,
+    /// Key allowed to change `pause_flags` besides the authority (zeros if none).
+    pub pauser: Pubkey,
     /// Number of currently supported chains.
     pub chain_count: u8,
     /// Configuration for each supported destination chain.
@@ -80,20 +83,14 @@ pub struct BridgeTransfer {
     pub bump: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy, Default)]
 pub enum TransferStatus {
+    #[default]
     Pending,   // Message published to Wormhole, awaiting confirmation/processing on destination
     Completed, // Transfer successfully processed on destination chain (commitment added)
     Failed,    // Transfer failed (e.g., VAA verification failed, relayer issue)
 }
 
-impl Default for TransferStatus {
-    fn default() -> Self {
-        TransferStatus::Pending
-    }
-}
-
-
 #[account]
 pub struct ExternalBridgeEmitter {
     /// Chain ID of the external blockchain (Wormhole format).
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+/// Number of checkpoints kept per pool before the oldest is evicted
+pub const MAX_DEPOSIT_CHECKPOINTS: usize = 128;
+
+/// Checkpoints recorded per `min_deposit_age_slots` window.
+/// Higher values give finer withdrawal granularity at the cost of history length.
+pub const CHECKPOINTS_PER_AGE_WINDOW: u64 = 16;
+
+/// A leaf-range checkpoint: every leaf with index below `next_index`
+/// was inserted at or before `slot`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
+pub struct DepositCheckpoint {
+    /// Slot at which the checkpoint was taken
+    pub slot: u64,
+
+    /// Pool's next leaf index at that slot
+    pub next_index: u64,
+}
+
+/// Ring buffer of leaf insertion checkpoints for a pool.
+/// Lets `withdraw` bound how recent a withdrawn leaf may be without storing per-leaf slots.
+#[account]
+pub struct DepositCheckpoints {
+    /// Pool these checkpoints belong to
+    pub pool: Pubkey,
+
+    /// Index of the next slot to write in `checkpoints`
+    pub head: u16,
+
+    /// Number of checkpoints currently stored
+    pub count: u16,
+
+    /// Slot of the most recent deposit
+    pub last_deposit_slot: u64,
+
+    /// Leaf count of the most recently evicted checkpoint
+    pub evicted_next_index: u64,
+
+    /// Slot of the most recently evicted checkpoint. The evicted leaf count is only a
+    /// safe bound for cutoffs at or after it, which raising `min_deposit_age_slots`
+    /// can move before it.
+    pub evicted_slot: u64,
+
+    /// Checkpoint ring buffer
+    pub checkpoints: [DepositCheckpoint; MAX_DEPOSIT_CHECKPOINTS],
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DepositCheckpoints {
+    /// Size of the DepositCheckpoints struct for space allocation
+    pub const LEN: usize =
+        32 +    // pool: Pubkey
+        2 +     // head: u16
+        2 +     // count: u16
+        8 +     // last_deposit_slot: u64
+        8 +     // evicted_next_index: u64
+        8 +     // evicted_slot: u64
+        16 * MAX_DEPOSIT_CHECKPOINTS + // checkpoints: [DepositCheckpoint; MAX_DEPOSIT_CHECKPOINTS]
+        1;      // bump: u8
+
+    /// Most recently written checkpoint, if any
+    pub fn latest(&self) -> Option<&DepositCheckpoint> {
+        if self.count == 0 {
+            return None;
+        }
+        let index = (self.head as usize + MAX_DEPOSIT_CHECKPOINTS - 1) % MAX_DEPOSIT_CHECKPOINTS;
+        Some(&self.checkpoints[index])
+    }
+
+    /// Record that `next_index` leaves existed before `slot`'s deposit.
+    /// A new checkpoint is only written once `interval` slots have passed since the last one.
+    pub fn record(&mut self, slot: u64, next_index: u64, interval: u64) {
+        self.last_deposit_slot = slot;
+
+        if let Some(latest) = self.latest() {
+            if slot < latest.slot.saturating_add(interval.max(1)) {
+                return;
+            }
+        }
+
+        let head = self.head as usize;
+        if self.count as usize == MAX_DEPOSIT_CHECKPOINTS {
+            self.evicted_next_index = self.checkpoints[head].next_index;
+            self.evicted_slot = self.checkpoints[head].slot;
+        } else {
+            self.count += 1;
+        }
+
+        self.checkpoints[head] = DepositCheckpoint { slot, next_index };
+        self.head = ((head + 1) % MAX_DEPOSIT_CHECKPOINTS) as u16;
+    }
+
+    /// Number of leaves that were all inserted at or before `cutoff_slot`,
+    /// given the pool currently holds `total_leaves`
+    pub fn leaves_inserted_before(&self, cutoff_slot: u64, total_leaves: u64) -> u64 {
+        if self.last_deposit_slot <= cutoff_slot {
+            return total_leaves;
+        }
+
+        let mut bound = if self.evicted_slot <= cutoff_slot { self.evicted_next_index } else { 0 };
+        for offset in 0..self.count as usize {
+            // Walk from oldest to newest
+            let index = (self.head as usize + MAX_DEPOSIT_CHECKPOINTS - self.count as usize + offset)
+                % MAX_DEPOSIT_CHECKPOINTS;
+            let checkpoint = &self.checkpoints[index];
+            if checkpoint.slot > cutoff_slot {
+                break;
+            }
+            bound = checkpoint.next_index;
+        }
+        bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_checkpoints() -> Box<DepositCheckpoints> {
+        let mut checkpoints = Box::new(DepositCheckpoints {
+            pool: Pubkey::default(),
+            head: 0,
+            count: 0,
+            last_deposit_slot: 0,
+            evicted_next_index: 0,
+            evicted_slot: 0,
+            checkpoints: [DepositCheckpoint::default(); MAX_DEPOSIT_CHECKPOINTS],
+            bump: 0,
+        });
+        // One leaf per slot, evicting the checkpoint of slot 100
+        for slot in 100..=100 + MAX_DEPOSIT_CHECKPOINTS as u64 {
+            checkpoints.record(slot, slot, 1);
+        }
+        checkpoints
+    }
+
+    #[test]
+    fn bounds_by_the_evicted_checkpoint_once_it_is_old_enough() {
+        let checkpoints = full_checkpoints();
+        assert_eq!(checkpoints.evicted_slot, 100);
+        assert_eq!(checkpoints.leaves_inserted_before(100, 1_000), 100);
+    }
+
+    #[test]
+    fn ignores_the_evicted_checkpoint_for_earlier_cutoffs() {
+        // A raised minimum age can put the cutoff before the evicted checkpoint
+        let checkpoints = full_checkpoints();
+        assert_eq!(checkpoints.leaves_inserted_before(99, 1_000), 0);
+    }
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::system_instruction;
+use crate::errors::SolanaVeilError;
+
+/// Seed prefix of a deposited commitment's record, `[b"commitment", pool, commitment]`
+pub const COMMITMENT_RECORD_SEED: &[u8] = b"commitment";
+
+/// Record of a deposited commitment, seeds `[b"commitment", pool, commitment]`.
+/// Every deposit creates one, so its existence rejects duplicates of any age.
+#[account]
+pub struct CommitmentRecord {
+    /// Pool the commitment was deposited into
+    pub pool: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl CommitmentRecord {
+    /// Size of the CommitmentRecord struct for space allocation
+    pub const LEN: usize =
+        32 +    // pool: Pubkey
+        1;      // bump: u8
+
+    /// Fail if `record` already holds a commitment record
+    pub fn ensure_absent(record: &AccountInfo) -> Result<()> {
+        if record.owner == &crate::ID && !record.data_is_empty() {
+            return Err(SolanaVeilError::DuplicateCommitment.into());
+        }
+        Ok(())
+    }
+
+    /// Create the record of `commitment` in `pool`, failing if it already exists.
+    /// `record` must be the commitment's record PDA; `payer` funds its rent.
+    pub fn create<'info>(
+        record: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        pool: &Pubkey,
+        commitment: &[u8; 32],
+    ) -> Result<()> {
+        let (expected, bump) = Pubkey::find_program_address(
+            &[COMMITMENT_RECORD_SEED, pool.as_ref(), commitment],
+            &crate::ID,
+        );
+        if record.key() != expected {
+            return Err(SolanaVeilError::InvalidCommitmentRecord.into());
+        }
+        Self::ensure_absent(record)?;
+
+        let space = 8 + Self::LEN;
+        let signer_seeds: &[&[u8]] = &[COMMITMENT_RECORD_SEED, pool.as_ref(), commitment, &[bump]];
+
+        // Top up, allocate and assign rather than `create_account`, so lamports sent to
+        // the address ahead of time cannot block the deposit
+        let top_up = Rent::get()?.minimum_balance(space).saturating_sub(record.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, record.key, top_up),
+                &[payer.clone(), record.clone(), system_program.clone()],
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(record.key, space as u64),
+            &[record.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(record.key, &crate::ID),
+            &[record.clone(), system_program.clone()],
+            &[signer_seeds],
+        )?;
+
+        let mut data = record.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        CommitmentRecord { pool: *pool, bump }.try_serialize(&mut writer)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_a_commitment_whose_record_exists() {
+        let pool = Pubkey::new_unique();
+        let commitment = [7u8; 32];
+        let (key, _) = Pubkey::find_program_address(
+            &[COMMITMENT_RECORD_SEED, pool.as_ref(), &commitment],
+            &crate::ID,
+        );
+        let system_program = anchor_lang::system_program::ID;
+
+        // Recorded by an earlier deposit, however long ago
+        let mut lamports = 1_000_000;
+        let mut data = [0u8; 8 + CommitmentRecord::LEN];
+        let record = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        let mut payer_lamports = 1_000_000;
+        let mut payer_data = [];
+        let payer_key = Pubkey::new_unique();
+        let payer = AccountInfo::new(&payer_key, true, true, &mut payer_lamports, &mut payer_data, &system_program, false, 0);
+        let mut program_lamports = 0;
+        let mut program_data = [];
+        let program = AccountInfo::new(&system_program, false, false, &mut program_lamports, &mut program_data, &system_program, true, 0);
+
+        assert_eq!(
+            CommitmentRecord::create(&record, &payer, &program, &pool, &commitment).unwrap_err(),
+            SolanaVeilError::DuplicateCommitment.into()
+        );
+
+        // Another commitment's record cannot stand in for this one's
+        assert_eq!(
+            CommitmentRecord::create(&record, &payer, &program, &pool, &[8u8; 32]).unwrap_err(),
+            SolanaVeilError::InvalidCommitmentRecord.into()
+        );
+    }
+
+    #[test]
+    fn existing_records_reject_duplicates() {
+        let key = Pubkey::new_unique();
+        let system_program = anchor_lang::system_program::ID;
+        let mut lamports = 1_000_000;
+
+        let mut empty = [];
+        let prefunded = AccountInfo::new(&key, false, true, &mut lamports, &mut empty, &system_program, false, 0);
+        assert!(CommitmentRecord::ensure_absent(&prefunded).is_ok());
+
+        let mut lamports = 1_000_000;
+        let mut data = [0u8; 8 + CommitmentRecord::LEN];
+        let recorded = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &crate::ID, false, 0);
+        assert_eq!(
+            CommitmentRecord::ensure_absent(&recorded).unwrap_err(),
+            SolanaVeilError::DuplicateCommitment.into()
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+/// Longest admin timelock the protocol config accepts (30 days)
+pub const MAX_ADMIN_TIMELOCK_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Largest serialized Groth16 verifying key a pool can store (up to 16 public inputs)
+pub const MAX_VERIFYING_KEY_LEN: usize = 1472;
+
+/// Circuit a verifying key belongs to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VerifierCircuit {
+    /// Withdrawal proofs
+    Withdraw,
+    /// Batch merkle root update proofs
+    BatchUpdate,
+}
+
+/// Sensitive admin change that only takes effect after the timelock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AdminAction {
+    /// Set `Config.protocol_fee_basis_points`
+    ProtocolFee { fee_basis_points: u16 },
+
+    /// Set `Config.treasury`
+    ProtocolTreasury { treasury: Pubkey },
+
+    /// Set `Config.admin_timelock_seconds`
+    AdminTimelock { seconds: i64 },
+
+    /// Set `BridgeConfig.fee_basis_points`
+    BridgeFee { fee_basis_points: u16 },
+
+    /// Set `BridgeConfig.treasury`
+    BridgeTreasury { treasury: Pubkey },
+
+    /// Set `Pool.max_fee_basis_points`
+    PoolMaxFee { max_fee_basis_points: u16 },
+
+    /// Replace a pool's verifying key for `circuit` with the key whose keccak hash is `key_hash`.
+    /// The key bytes themselves are supplied on execution.
+    VerifyingKey { circuit: VerifierCircuit, key_hash: [u8; 32] },
+}
+
+impl AdminAction {
+    /// Stable discriminator used in the pending action PDA seeds and events,
+    /// so at most one action of each kind is queued per target
+    pub fn kind(&self) -> u8 {
+        match self {
+            AdminAction::ProtocolFee { .. } => 0,
+            AdminAction::ProtocolTreasury { .. } => 1,
+            AdminAction::AdminTimelock { .. } => 2,
+            AdminAction::BridgeFee { .. } => 3,
+            AdminAction::BridgeTreasury { .. } => 4,
+            AdminAction::PoolMaxFee { .. } => 5,
+            AdminAction::VerifyingKey { circuit, .. } => 6 + *circuit as u8,
+        }
+    }
+}
+
+/// Admin action waiting out the timelock, seeds `[b"pending_action", target, kind]`
+#[account]
+pub struct PendingAction {
+    /// Account the action applies to (config, bridge config or pool)
+    pub target: Pubkey,
+
+    /// Authority that queued the action; receives the rent back when it is closed
+    pub proposer: Pubkey,
+
+    /// The queued change
+    pub action: AdminAction,
+
+    /// When the action was queued
+    pub queued_at: i64,
+
+    /// Earliest time the action can be executed
+    pub executable_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PendingAction {
+    /// Size of the PendingAction struct for space allocation
+    pub const LEN: usize =
+        32 +        // target: Pubkey
+        32 +        // proposer: Pubkey
+        1 + 33 +    // action: AdminAction (tag + largest variant)
+        8 +         // queued_at: i64
+        8 +         // executable_at: i64
+        1;          // bump: u8
+}
+
+/// Groth16 verifying key for one of a pool's circuits, seeds `[b"verifying_key", pool, circuit]`
+#[account]
+pub struct VerifyingKeyAccount {
+    /// Pool this key belongs to
+    pub pool: Pubkey,
+
+    /// Circuit whose proofs this key verifies
+    pub circuit: VerifierCircuit,
+
+    /// Keccak hash of `data`
+    pub key_hash: [u8; 32],
+
+    /// Uncompressed verifying key with beta, gamma and delta negated, see
+    /// `verifier::verify_groth16_proof`
+    pub data: Vec<u8>,
+
+    /// When the key was last set
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl VerifyingKeyAccount {
+    /// Size of the VerifyingKeyAccount struct for space allocation
+    pub const LEN: usize =
+        32 +                            // pool: Pubkey
+        1 +                             // circuit: VerifierCircuit
+        32 +                            // key_hash: [u8; 32]
+        4 + MAX_VERIFYING_KEY_LEN +     // data: Vec<u8>
+        8 +                             // updated_at: i64
+        1;                              // bump: u8
+}
@@ -1,6 +1,5 @@
 use anchor_lang::prelude::*;
-
-pub mod bridge;
+use crate::errors::SolanaVeilError;
 
 /// Pool account to store the state of each denomination pool
 /// This holds configuration and current state for a specific mixer pool
@@ -9,15 +8,15 @@ pub struct Pool {
     /// The authority that can update pool settings
     pub authority: Pubkey,
     
+    /// Proposed new authority awaiting acceptance (zeros if none)
+    pub pending_authority: Pubkey,
+    
     /// Denomination amount in lamports (or token smallest units)
     pub denomination: u64,
     
     /// Current merkle root (32 bytes for Poseidon hash)
     pub merkle_root: [u8; 32],
     
-    /// Next index for merkle tree insertion
-    pub next_index: u64,
-    
     /// Maximum depth of the merkle tree
     pub max_depth: u8,
     
@@ -27,8 +26,12 @@ pub struct Pool {
     /// The SPL token mint (if applicable, otherwise zeros)
     pub mint: Pubkey,
     
-    /// Token account holding deposits (if SPL token)
-    pub token_vault: Pubkey,
+    /// Account holding deposits: the token account for SPL pools,
+    /// or the data-less SOL vault PDA for native pools
+    pub vault: Pubkey,
+    
+    /// Bump seed of the SOL vault PDA (unused for SPL pools)
+    pub vault_bump: u8,
     
     /// Whether this pool uses SPL tokens or native SOL
     pub is_spl_token: bool,
@@ -39,19 +42,146 @@ pub struct Pool {
     /// Minimum withdrawal amount (prevents dust attacks)
     pub min_withdrawal_amount: u64,
     
-    /// Whether the pool is currently active
-    pub is_active: bool,
+    /// Operations currently paused, see `pause_flags`
+    pub pause_flags: u8,
+    
+    /// Key allowed to change `pause_flags` besides the authority (zeros if none)
+    pub pauser: Pubkey,
     
     /// Total deposited amount
     pub total_deposited: u64,
     
-    /// Total withdrawn amount
+    /// Total withdrawn amount (including relayer and protocol fees)
     pub total_withdrawn: u64,
     
+    /// Total protocol fees skimmed to the treasury vault
+    pub total_protocol_fees: u64,
+    
+    /// Protocol fees already claimed from the treasury vault
+    pub protocol_fees_claimed: u64,
+    
+    /// Program consulted on deposit and withdraw (zeros if screening is disabled)
+    pub screening_program: Pubkey,
+    
+    /// Minimum number of slots a deposit must age before it can be withdrawn
+    pub min_deposit_age_slots: u64,
+    
+    /// Position of this pool in the pool registry
+    pub registry_index: u64,
+    
+    /// Cap on outstanding deposits (total deposited minus total withdrawn), 0 for no cap
+    pub max_total_deposits: u64,
+    
+    /// Maximum amount withdrawable per window, 0 for no limit
+    pub withdrawal_limit_amount: u64,
+    
+    /// Length of the withdrawal limit window in slots
+    pub withdrawal_limit_window_slots: u64,
+    
+    /// Slot at which the current withdrawal limit bucket started
+    pub withdrawal_window_start_slot: u64,
+    
+    /// Value of `total_withdrawn` when the current bucket started
+    pub withdrawal_window_start_total: u64,
+    
+    /// Amount withdrawn during the bucket before the current one
+    pub withdrawal_previous_window_amount: u64,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
 }
 
+impl Pool {
+    /// Deposits currently held by the pool
+    pub fn outstanding_deposits(&self) -> u64 {
+        self.total_deposited.saturating_sub(self.total_withdrawn)
+    }
+    
+    /// Fail if depositing `amount` would exceed the pool's deposit cap
+    pub fn check_deposit_cap(&self, amount: u64) -> Result<()> {
+        if self.max_total_deposits == 0 {
+            return Ok(());
+        }
+        let outstanding = self.outstanding_deposits().checked_add(amount)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        if outstanding > self.max_total_deposits {
+            return Err(SolanaVeilError::DepositCapExceeded.into());
+        }
+        Ok(())
+    }
+    
+    /// Protocol fees accrued but not yet claimed. The treasury vault of SPL pools also
+    /// holds tokens swapped for SOL fees, which are not protocol fees.
+    pub fn unclaimed_protocol_fees(&self) -> u64 {
+        self.total_protocol_fees.saturating_sub(self.protocol_fees_claimed)
+    }
+    
+    /// Fail if claiming `amount` would exceed the unclaimed protocol fees, otherwise
+    /// count it as claimed
+    pub fn record_protocol_fee_claim(&mut self, amount: u64) -> Result<()> {
+        if amount > self.unclaimed_protocol_fees() {
+            return Err(SolanaVeilError::ProtocolFeeClaimExceeded.into());
+        }
+        self.protocol_fees_claimed += amount;
+        Ok(())
+    }
+    
+    /// Start, amount withdrawn and previous bucket's amount of the withdrawal limit
+    /// bucket containing `slot`. Buckets are `withdrawal_limit_window_slots` long and
+    /// follow each other unless a whole bucket passes without withdrawals.
+    fn withdrawal_buckets(&self, slot: u64) -> (u64, u64, u64) {
+        let window = self.withdrawal_limit_window_slots;
+        let start = self.withdrawal_window_start_slot;
+        let withdrawn = self.total_withdrawn.saturating_sub(self.withdrawal_window_start_total);
+        
+        if slot < start.saturating_add(window) {
+            (start, withdrawn, self.withdrawal_previous_window_amount)
+        } else if slot < start.saturating_add(window.saturating_mul(2)) {
+            (start + window, 0, withdrawn)
+        } else {
+            (slot, 0, 0)
+        }
+    }
+    
+    /// Amount withdrawn in the window of `withdrawal_limit_window_slots` ending at `slot`,
+    /// estimated from two buckets: the current one in full plus the share of the previous
+    /// one the window still overlaps (rounded up)
+    pub fn withdrawn_in_window(&self, slot: u64) -> u64 {
+        let window = self.withdrawal_limit_window_slots;
+        if window == 0 {
+            return 0;
+        }
+        let (start, current, previous) = self.withdrawal_buckets(slot);
+        let overlap = window.saturating_sub(slot.saturating_sub(start)) as u128;
+        let previous_share = (previous as u128 * overlap).div_ceil(window as u128) as u64;
+        current.saturating_add(previous_share)
+    }
+    
+    /// Fail if withdrawing `amount` at `slot` would exceed the window's limit
+    pub fn check_withdrawal_limit(&self, slot: u64, amount: u64) -> Result<()> {
+        if self.withdrawal_limit_amount == 0 {
+            return Ok(());
+        }
+        let withdrawn = self.withdrawn_in_window(slot).checked_add(amount)
+            .ok_or(SolanaVeilError::CalculationError)?;
+        if withdrawn > self.withdrawal_limit_amount {
+            return Err(SolanaVeilError::WithdrawalLimitExceeded.into());
+        }
+        Ok(())
+    }
+    
+    /// Move to the withdrawal limit bucket containing `slot`, if it is a new one.
+    /// Must be called before `total_withdrawn` is increased for this withdrawal.
+    pub fn roll_withdrawal_window(&mut self, slot: u64) {
+        let (start, _, previous) = self.withdrawal_buckets(slot);
+        if start != self.withdrawal_window_start_slot {
+            self.withdrawal_window_start_slot = start;
+            self.withdrawal_window_start_total = self.total_withdrawn;
+            self.withdrawal_previous_window_amount = previous;
+        }
+    }
+}
+
 /// Nullifier account to prevent double spending
 /// This is a compressed account that tracks used nullifiers
 #[account]
@@ -90,6 +220,27 @@ pub struct Relayer {
     /// Total fees earned
     pub total_fees: u64,
     
+    /// Number of withdrawals relayed
+    pub successful_withdrawals: u64,
+    
+    /// Slot of the latest relayed withdrawal (0 = never)
+    pub last_active_slot: u64,
+    
+    /// Stake held in the relayer's escrow `[b"relayer_stake", authority]`
+    pub stake_amount: u64,
+    
+    /// Slot `request_unstake` was called in (0 = no unstake pending)
+    pub unstake_requested_slot: u64,
+    
+    /// Slot the relayer was last slashed in (0 = never)
+    pub last_slashed_slot: u64,
+    
+    /// Number of times the relayer was slashed
+    pub slash_count: u64,
+    
+    /// Ed25519 key the relayer signs fee quotes with (zeros = not set)
+    pub quote_signer: Pubkey,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
 }
@@ -100,18 +251,40 @@ pub struct MerkleTree {
     /// The authority that can update the tree
     pub authority: Pubkey,
     
+    /// Proposed new authority awaiting acceptance (zeros if none)
+    pub pending_authority: Pubkey,
+    
     /// Maximum depth of the tree
     pub max_depth: u8,
     
     /// Current number of leaves in the tree
     pub num_leaves: u64,
     
-    /// Current root of the tree
+    /// Current root of the tree, covering every leaf not yet waiting in a batch
     pub root: [u8; 32],
     
+    /// Roots set by the latest batch updates, newest at `root_history_head`, so that
+    /// withdrawals proven against a root replaced in the meantime still verify
+    pub root_history: [[u8; 32]; ROOT_HISTORY_SIZE],
+    
+    /// Index of the newest root in `root_history`
+    pub root_history_head: u8,
+    
     /// The pool associated with this tree
     pub pool: Pubkey,
     
+    /// Batch currently receiving new commitments
+    pub open_batch: CommitmentBatch,
+    
+    /// Full batches waiting for a root update, oldest at `sealed_head`
+    pub sealed_batches: [CommitmentBatch; MAX_SEALED_BATCHES],
+    
+    /// Index of the oldest sealed batch
+    pub sealed_head: u8,
+    
+    /// Number of sealed batches waiting for a root update
+    pub sealed_count: u8,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
 }
@@ -131,16 +304,107 @@ pub struct Config {
     /// Whether new pools can be created
     pub pools_enabled: bool,
     
+    /// Delay between queuing a sensitive admin action and executing it
+    pub admin_timelock_seconds: i64,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
-}pub mod pool;
+}
+
+pub mod pool;
 pub mod nullifier;
-pub mod tree;
 pub mod relayer;
 pub mod bridge; // Add the new bridge module
+pub mod checkpoint;
+pub mod registry;
+pub mod pause;
+pub mod governance;
+pub mod access_control;
+pub mod batch;
+pub mod commitment_record;
+pub mod recipient_program;
+pub mod relayer_directory;
+pub mod sol_fee;
 
 pub use pool::*;
 pub use nullifier::*;
-pub use tree::*;
 pub use relayer::*;
-pub use bridge::*; // Export bridge types
\ No newline at end of file
+pub use bridge::*; // Export bridge types
+pub use checkpoint::*;
+pub use registry::*;
+pub use pause::*;
+pub use governance::*;
+pub use access_control::*;
+pub use batch::*;
+pub use commitment_record::*;
+pub use recipient_program::*;
+pub use relayer_directory::*;
+pub use sol_fee::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOW: u64 = 100;
+    const LIMIT: u64 = 10;
+
+    fn limited_pool() -> Pool {
+        let bytes = vec![0u8; std::mem::size_of::<Pool>()];
+        let mut pool = Pool::deserialize(&mut &bytes[..]).unwrap();
+        pool.withdrawal_limit_amount = LIMIT;
+        pool.withdrawal_limit_window_slots = WINDOW;
+        pool
+    }
+
+    fn withdraw(pool: &mut Pool, slot: u64, amount: u64) -> Result<()> {
+        pool.check_withdrawal_limit(slot, amount)?;
+        pool.roll_withdrawal_window(slot);
+        pool.total_withdrawn += amount;
+        Ok(())
+    }
+
+    #[test]
+    fn does_not_reset_at_bucket_boundaries() {
+        let mut pool = limited_pool();
+        withdraw(&mut pool, 99, LIMIT).unwrap();
+
+        // A tumbling window would allow another full limit one slot later
+        assert_eq!(pool.withdrawn_in_window(100), LIMIT);
+        assert_eq!(
+            withdraw(&mut pool, 100, 1).unwrap_err(),
+            SolanaVeilError::WithdrawalLimitExceeded.into()
+        );
+    }
+
+    #[test]
+    fn frees_headroom_as_the_previous_bucket_slides_out() {
+        let mut pool = limited_pool();
+        withdraw(&mut pool, 0, LIMIT).unwrap();
+
+        // Halfway through the next bucket, half of the previous one still counts
+        assert_eq!(pool.withdrawn_in_window(150), LIMIT / 2);
+        withdraw(&mut pool, 150, LIMIT / 2).unwrap();
+        assert_eq!(pool.withdrawn_in_window(150), LIMIT);
+
+        // Once the older bucket has slid out, only the newer one counts
+        assert_eq!(pool.withdrawn_in_window(200), LIMIT / 2);
+        assert_eq!(pool.withdrawn_in_window(300), 0);
+    }
+
+    #[test]
+    fn caps_claims_at_unclaimed_protocol_fees() {
+        let mut pool = limited_pool();
+        pool.total_protocol_fees = 10;
+
+        pool.record_protocol_fee_claim(6).unwrap();
+        assert_eq!(pool.unclaimed_protocol_fees(), 4);
+        assert_eq!(
+            pool.record_protocol_fee_claim(5).unwrap_err(),
+            SolanaVeilError::ProtocolFeeClaimExceeded.into()
+        );
+
+        // Fees accrued later become claimable again
+        pool.total_protocol_fees += 1;
+        pool.record_protocol_fee_claim(5).unwrap();
+        assert_eq!(pool.unclaimed_protocol_fees(), 0);
+    }
+}
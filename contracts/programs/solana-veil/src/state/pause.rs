@@ -0,0 +1,23 @@
+/// Pause bitflags stored in `Pool.pause_flags` and `BridgeConfig.pause_flags`.
+/// Each flag halts one kind of operation so incidents can be contained
+/// without freezing unrelated flows (e.g. stopping deposits while users exit).
+pub mod pause_flags {
+    /// Pool deposits are halted
+    pub const DEPOSITS: u8 = 1 << 0;
+    /// Pool withdrawals are halted
+    pub const WITHDRAWALS: u8 = 1 << 1;
+    /// Outbound bridge transfers are halted
+    pub const BRIDGE_OUTBOUND: u8 = 1 << 2;
+    /// Inbound bridge transfers and bridge withdrawals are halted
+    pub const BRIDGE_INBOUND: u8 = 1 << 3;
+
+    /// Flags meaningful on a pool
+    pub const POOL_FLAGS: u8 = DEPOSITS | WITHDRAWALS;
+    /// Flags meaningful on the bridge config
+    pub const BRIDGE_FLAGS: u8 = BRIDGE_OUTBOUND | BRIDGE_INBOUND;
+
+    /// Whether `flag` is set in `flags`
+    pub fn is_paused(flags: u8, flag: u8) -> bool {
+        flags & flag != 0
+    }
+}
@@ -1,7 +1,21 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
-use crate::error::*;
+use crate::errors::SolanaVeilError;
+use crate::events::PoolPauseFlagsUpdatedEvent;
+use crate::token_extensions;
+use crate::instructions::registry::{register_pool, registry_entry_mut};
+use crate::vault::{sol_vault_rent_reserve, SOL_VAULT_SEED};
+
+// Proofs for a tree of any other depth cannot be verified
+fn validate_tree_depth(max_depth: u8) -> Result<()> {
+    if max_depth != TREE_DEPTH {
+        return Err(SolanaVeilError::InvalidTreeDepth.into());
+    }
+    Ok(())
+}
 
 // Initialize a new pool with a specific denomination
 pub fn initialize_pool(
@@ -15,47 +29,117 @@ pub fn initialize_pool(
         return Err(SolanaVeilError::InvalidDenomination.into());
     }
     
-    if max_depth < 10 || max_depth > 30 {
-        return Err(SolanaVeilError::InvalidTreeDepth.into());
-    }
+    validate_tree_depth(max_depth)?;
     
     // Initialize pool account
     let pool = &mut ctx.accounts.pool;
     pool.authority = ctx.accounts.authority.key();
+    pool.pending_authority = Pubkey::default();
     pool.denomination = denomination;
     pool.merkle_root = [0; 32]; // Initial empty merkle root
-    pool.next_index = 0;
     pool.max_depth = max_depth;
     pool.tree = ctx.accounts.tree.key();
     pool.is_spl_token = is_spl_token;
     pool.max_fee_basis_points = 200; // Default 2% max fee
     pool.min_withdrawal_amount = denomination / 10; // Default 10% of denomination
-    pool.is_active = true;
+    pool.pause_flags = 0; // Nothing paused
+    pool.pauser = Pubkey::default(); // Only the authority can pause until configured
     pool.total_deposited = 0;
     pool.total_withdrawn = 0;
-    pool.bump = *ctx.bumps.get("pool").unwrap();
+    pool.total_protocol_fees = 0;
+    pool.protocol_fees_claimed = 0;
+    pool.screening_program = Pubkey::default(); // Screening disabled until configured
+    pool.min_deposit_age_slots = 0; // No minimum age until configured
+    pool.max_total_deposits = 0; // No deposit cap until configured
+    pool.withdrawal_limit_amount = 0; // No withdrawal limit until configured
+    pool.withdrawal_limit_window_slots = 0;
+    pool.withdrawal_window_start_slot = 0;
+    pool.withdrawal_window_start_total = 0;
+    pool.withdrawal_previous_window_amount = 0;
+    pool.bump = ctx.bumps.pool;
     
     // Set token-specific fields if using SPL token
     if is_spl_token {
-        pool.mint = ctx.accounts.mint.key();
-        pool.token_vault = ctx.accounts.token_vault.key();
+        // Token-2022 mints must not carry extensions that break privacy or custody
+        let mint = ctx.accounts.mint.as_ref()
+            .ok_or(SolanaVeilError::InvalidTokenAccount)?;
+        token_extensions::validate_pool_mint(&mint.to_account_info())?;
+        
+        pool.mint = mint.key();
+        pool.vault = ctx.accounts.token_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidVault)?
+            .key();
+        pool.vault_bump = 0;
     } else {
+        let sol_vault = ctx.accounts.sol_vault.as_ref()
+            .ok_or(SolanaVeilError::InvalidVault)?;
+        
         pool.mint = Pubkey::default();
-        pool.token_vault = Pubkey::default();
+        pool.vault = sol_vault.key();
+        pool.vault_bump = ctx.bumps.sol_vault;
+        
+        // Fund the vault's rent reserve up front so deposits and withdrawals
+        // only ever move lamports above it
+        let top_up = sol_vault_rent_reserve()?.saturating_sub(sol_vault.lamports());
+        if top_up > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    ctx.accounts.authority.key,
+                    sol_vault.key,
+                    top_up,
+                ),
+                &[
+                    ctx.accounts.authority.to_account_info(),
+                    sol_vault.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
     }
     
+    // List the pool in the registry so clients can discover it
+    pool.registry_index = register_pool(
+        &mut ctx.accounts.registry,
+        &mut ctx.accounts.registry_page,
+        ctx.bumps.registry_page,
+        PoolRegistryEntry {
+            pool: pool.key(),
+            mint: pool.mint,
+            denomination,
+            tree: ctx.accounts.tree.key(),
+            vault: pool.vault,
+            pause_flags: 0,
+        },
+    )?;
+    
     // Initialize the merkle tree
     let tree = &mut ctx.accounts.tree;
     tree.authority = ctx.accounts.authority.key();
+    tree.pending_authority = Pubkey::default();
     tree.max_depth = max_depth;
     tree.num_leaves = 0;
     tree.root = [0; 32];
+    tree.root_history = [[0; 32]; ROOT_HISTORY_SIZE];
+    tree.root_history_head = 0;
     tree.pool = pool.key();
-    tree.bump = *ctx.bumps.get("tree").unwrap();
+    tree.open_batch = CommitmentBatch::default();
+    tree.sealed_batches = [CommitmentBatch::default(); MAX_SEALED_BATCHES];
+    tree.sealed_head = 0;
+    tree.sealed_count = 0;
+    tree.bump = ctx.bumps.tree;
+    
+    // Initialize the leaf insertion checkpoints used for the minimum deposit age
+    let checkpoints = &mut ctx.accounts.checkpoints;
+    checkpoints.pool = pool.key();
+    checkpoints.head = 0;
+    checkpoints.count = 0;
+    checkpoints.last_deposit_slot = 0;
+    checkpoints.evicted_next_index = 0;
+    checkpoints.bump = ctx.bumps.checkpoints;
     
     // Emit an event for the ZK Compression system to initialize the tree
     // The log format is important and will be parsed by the ZK Compression indexer
-    msg!("initialize_compressed_merkle_tree:{\"max_depth\":{},\"tree_id\":\"{}\",\"denomination\":{}}",
+    msg!("initialize_compressed_merkle_tree:{{\"max_depth\":{},\"tree_id\":\"{}\",\"denomination\":{}}}",
         max_depth,
         tree.key().to_string(),
         denomination
@@ -67,29 +151,31 @@ pub fn initialize_pool(
     Ok(())
 }
 
-// Update pool configuration
+/// Deposit cap and withdrawal rate limit settings for `update_pool_config`;
+/// fields left as `None` keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PoolLimits {
+    /// Cap on outstanding deposits (0 removes the cap)
+    pub max_total_deposits: Option<u64>,
+    
+    /// Maximum amount withdrawable per window (0 removes the limit)
+    pub withdrawal_limit_amount: Option<u64>,
+    
+    /// Length of the withdrawal limit window in slots
+    pub withdrawal_limit_window_slots: Option<u64>,
+}
+
+// Update pool configuration (Admin together with the pool authority)
 pub fn update_pool_config(
     ctx: Context<UpdatePoolConfig>,
-    max_fee_basis_points: Option<u16>,
     min_withdrawal_amount: Option<u64>,
-    is_active: Option<bool>,
+    pauser: Option<Pubkey>,
+    screening_program: Option<Pubkey>,
+    min_deposit_age_slots: Option<u64>,
+    limits: PoolLimits,
 ) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     
-    // Validate authority
-    if pool.authority != ctx.accounts.authority.key() {
-        return Err(SolanaVeilError::Unauthorized.into());
-    }
-    
-    // Update max fee if provided
-    if let Some(fee_bp) = max_fee_basis_points {
-        // Max 5%
-        if fee_bp > 500 {
-            return Err(SolanaVeilError::FeeTooHigh.into());
-        }
-        pool.max_fee_basis_points = fee_bp;
-    }
-    
     // Update min withdrawal amount if provided
     if let Some(min_amount) = min_withdrawal_amount {
         if min_amount > pool.denomination {
@@ -98,9 +184,38 @@ pub fn update_pool_config(
         pool.min_withdrawal_amount = min_amount;
     }
     
-    // Update active status if provided
-    if let Some(active) = is_active {
-        pool.is_active = active;
+    // Update the designated pauser if provided (Pubkey::default() removes it)
+    if let Some(new_pauser) = pauser {
+        pool.pauser = new_pauser;
+    }
+    
+    // Update screening program if provided (Pubkey::default() disables screening)
+    if let Some(program) = screening_program {
+        pool.screening_program = program;
+    }
+    
+    // Update minimum deposit age if provided
+    if let Some(min_age) = min_deposit_age_slots {
+        pool.min_deposit_age_slots = min_age;
+    }
+    
+    // Update deposit cap if provided (0 removes the cap)
+    if let Some(max_deposits) = limits.max_total_deposits {
+        pool.max_total_deposits = max_deposits;
+    }
+    
+    // Update withdrawal rate limit if provided (amount 0 removes the limit)
+    if let Some(limit_amount) = limits.withdrawal_limit_amount {
+        pool.withdrawal_limit_amount = limit_amount;
+    }
+    if let Some(window_slots) = limits.withdrawal_limit_window_slots {
+        if window_slots == 0 {
+            return Err(SolanaVeilError::InvalidPoolConfig.into());
+        }
+        pool.withdrawal_limit_window_slots = window_slots;
+    }
+    if pool.withdrawal_limit_amount > 0 && pool.withdrawal_limit_window_slots == 0 {
+        return Err(SolanaVeilError::InvalidPoolConfig.into());
     }
     
     msg!("Updated pool configuration for denomination: {}", pool.denomination);
@@ -108,6 +223,31 @@ pub fn update_pool_config(
     Ok(())
 }
 
+// Pause or resume pool operations, callable by the authority, the designated pauser or a Guardian
+pub fn set_pool_pause_flags(
+    ctx: Context<SetPoolPauseFlags>,
+    pause_flags: u8,
+) -> Result<()> {
+    if pause_flags & !pause_flags::POOL_FLAGS != 0 {
+        return Err(SolanaVeilError::InvalidPauseFlags.into());
+    }
+    
+    let pool = &mut ctx.accounts.pool;
+    pool.pause_flags = pause_flags;
+    
+    // Keep the registry listing in sync so clients can hide paused pools
+    registry_entry_mut(&mut ctx.accounts.registry_page, pool)?.pause_flags = pause_flags;
+    
+    emit!(PoolPauseFlagsUpdatedEvent {
+        pool: pool.key(),
+        pause_flags,
+        updated_by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(
     denomination: u64,
@@ -125,9 +265,11 @@ pub struct InitializePool<'info> {
         seeds = [
             b"pool".as_ref(),
             &denomination.to_le_bytes(),
-            if is_spl_token { mint.key().as_ref() } else { &[] },
+            mint.as_ref().map(|mint| mint.key()).unwrap_or_default().as_ref(),
         ],
-        bump
+        bump,
+        // Native pools are stored with the default mint, so their seeds must use it too
+        constraint = is_spl_token || mint.is_none() @ SolanaVeilError::InvalidTokenAccount
     )]
     pub pool: Account<'info, Pool>,
     
@@ -138,18 +280,50 @@ pub struct InitializePool<'info> {
         seeds = [
             b"tree".as_ref(),
             &denomination.to_le_bytes(),
-            if is_spl_token { mint.key().as_ref() } else { &[] },
+            mint.as_ref().map(|mint| mint.key()).unwrap_or_default().as_ref(),
+        ],
+        bump
+    )]
+    pub tree: Box<Account<'info, MerkleTree>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DepositCheckpoints::LEN,
+        seeds = [
+            b"checkpoints".as_ref(),
+            pool.key().as_ref(),
         ],
         bump
     )]
-    pub tree: Account<'info, MerkleTree>,
+    pub checkpoints: Box<Account<'info, DepositCheckpoints>>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool_registry".as_ref()],
+        bump = registry.bump
+    )]
+    pub registry: Box<Account<'info, PoolRegistry>>,
+    
+    /// Registry page the pool is appended to, created once the previous page is full
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PoolRegistryPage::LEN,
+        seeds = [
+            b"pool_registry_page".as_ref(),
+            &registry.next_page_index().to_le_bytes(),
+        ],
+        bump
+    )]
+    pub registry_page: Box<Account<'info, PoolRegistryPage>>,
     
     /// Only required for SPL token pools
     #[account(
         mut,
-        constraint = (is_spl_token && mint.key() != Pubkey::default()) || !is_spl_token @ SolanaVeilError::InvalidTokenAccount
+        constraint = !is_spl_token || mint.key() != Pubkey::default() @ SolanaVeilError::InvalidTokenAccount
     )]
-    pub mint: Option<Account<'info, Mint>>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
     
     /// Only required for SPL token pools
     #[account(
@@ -157,21 +331,92 @@ pub struct InitializePool<'info> {
         payer = authority,
         token::mint = mint,
         token::authority = pool,
-        constraint = (is_spl_token && token_vault.key() != Pubkey::default()) || !is_spl_token @ SolanaVeilError::InvalidTokenAccount
+        token::token_program = token_program,
+        constraint = !is_spl_token || token_vault.key() != Pubkey::default() @ SolanaVeilError::InvalidTokenAccount
     )]
-    pub token_vault: Option<Account<'info, TokenAccount>>,
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Only required for native SOL pools; a data-less PDA holding the pool's lamports
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, pool.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: Option<SystemAccount<'info>>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Option<Program<'info, Token>>,
+    /// SPL Token or Token-2022, only required for SPL token pools
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
 pub struct UpdatePoolConfig<'info> {
+    /// Holder of the Admin role
+    pub admin: Signer<'info>,
+    
+    /// The pool's own authority; an Admin alone cannot reconfigure someone else's pool
     pub authority: Signer<'info>,
     
-    #[account(mut)]
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump,
+        constraint = access_control.has_role(&admin.key(), Role::Admin) @ SolanaVeilError::MissingRole
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+    
+    #[account(
+        mut,
+        has_one = authority @ SolanaVeilError::Unauthorized
+    )]
     pub pool: Account<'info, Pool>,
     
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct SetPoolPauseFlags<'info> {
+    /// Pool authority, designated pauser or Guardian
+    pub signer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"access_control".as_ref()],
+        bump = access_control.bump
+    )]
+    pub access_control: Box<Account<'info, AccessControl>>,
+    
+    #[account(
+        mut,
+        constraint = signer.key() == pool.authority
+            || (pool.pauser != Pubkey::default() && signer.key() == pool.pauser)
+            || access_control.has_role(&signer.key(), Role::Guardian)
+            @ SolanaVeilError::Unauthorized
+    )]
+    pub pool: Account<'info, Pool>,
+    
+    #[account(
+        mut,
+        seeds = [
+            b"pool_registry_page".as_ref(),
+            &(pool.registry_index / MAX_POOLS_PER_REGISTRY_PAGE as u64).to_le_bytes(),
+        ],
+        bump = registry_page.bump
+    )]
+    pub registry_page: Box<Account<'info, PoolRegistryPage>>,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_only_the_circuit_tree_depth() {
+        validate_tree_depth(TREE_DEPTH).unwrap();
+
+        for max_depth in (0..=u8::MAX).filter(|depth| *depth != TREE_DEPTH) {
+            assert_eq!(
+                validate_tree_depth(max_depth).unwrap_err(),
+                SolanaVeilError::InvalidTreeDepth.into()
+            );
+        }
+    }
+}
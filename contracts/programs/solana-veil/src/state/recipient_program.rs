@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Program that withdrawals may call back into, seeds `[b"recipient_program", program_id]`.
+/// The account existing is what whitelists the program.
+#[account]
+pub struct RecipientProgram {
+    /// Whitelisted program
+    pub program_id: Pubkey,
+
+    /// Admin that whitelisted the program
+    pub added_by: Pubkey,
+
+    /// When the program was whitelisted
+    pub added_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RecipientProgram {
+    /// Size of the RecipientProgram struct for space allocation
+    pub const LEN: usize =
+        32 +    // program_id: Pubkey
+        32 +    // added_by: Pubkey
+        8 +     // added_at: i64
+        1;      // bump: u8
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+/// Number of pools listed on each registry page
+pub const MAX_POOLS_PER_REGISTRY_PAGE: usize = 32;
+
+/// Registry header: tracks how many pools exist so clients know how many pages to fetch
+#[account]
+pub struct PoolRegistry {
+    /// Total number of registered pools
+    pub pool_count: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PoolRegistry {
+    /// Size of the PoolRegistry struct for space allocation
+    pub const LEN: usize =
+        8 +     // pool_count: u64
+        1;      // bump: u8
+
+    /// Page that the next registered pool will be appended to
+    pub fn next_page_index(&self) -> u64 {
+        self.pool_count / MAX_POOLS_PER_REGISTRY_PAGE as u64
+    }
+}
+
+/// Registry listing of a single pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
+pub struct PoolRegistryEntry {
+    /// Pool account
+    pub pool: Pubkey,
+
+    /// Token mint (zeros for native SOL pools)
+    pub mint: Pubkey,
+
+    /// Denomination amount in lamports (or token smallest units)
+    pub denomination: u64,
+
+    /// Merkle tree account
+    pub tree: Pubkey,
+
+    /// Account holding the pool's deposits
+    pub vault: Pubkey,
+
+    /// Operations currently paused on the pool, see `pause_flags`
+    pub pause_flags: u8,
+}
+
+impl PoolRegistryEntry {
+    /// Serialized size of a PoolRegistryEntry
+    pub const LEN: usize =
+        32 +    // pool: Pubkey
+        32 +    // mint: Pubkey
+        8 +     // denomination: u64
+        32 +    // tree: Pubkey
+        32 +    // vault: Pubkey
+        1;      // pause_flags: u8
+}
+
+/// One page of the pool registry, seeds `[b"pool_registry_page", page_index]`
+#[account]
+pub struct PoolRegistryPage {
+    /// Index of this page
+    pub page_index: u64,
+
+    /// Pools listed on this page, in registration order
+    pub entries: Vec<PoolRegistryEntry>,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PoolRegistryPage {
+    /// Size of the PoolRegistryPage struct for space allocation
+    pub const LEN: usize =
+        8 +     // page_index: u64
+        4 + PoolRegistryEntry::LEN * MAX_POOLS_PER_REGISTRY_PAGE + // entries: Vec<PoolRegistryEntry>
+        1;      // bump: u8
+}
@@ -35,4 +35,142 @@ impl RelayerState {
         8 +     // total_processed: u64
         8 +     // total_fees_earned: u64
         1;      // bump: u8
-}
\ No newline at end of file
+}
+/// Relayer staking parameters, seeds `[b"relayer_config"]`
+#[account]
+pub struct RelayerConfig {
+    /// Protocol authority that created the config
+    pub authority: Pubkey,
+
+    /// Stake a relayer must hold before it can be activated
+    pub required_stake: u64,
+
+    /// Mint stakes are held in (zeros for native SOL)
+    pub stake_mint: Pubkey,
+
+    /// Slots between `request_unstake` (or the latest slash) and `complete_unstake`
+    pub unstake_cooldown_slots: u64,
+
+    /// Total stake slashed to the treasury
+    pub total_slashed: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RelayerConfig {
+    /// Size of the RelayerConfig struct for space allocation
+    pub const LEN: usize =
+        32 +    // authority: Pubkey
+        8 +     // required_stake: u64
+        32 +    // stake_mint: Pubkey
+        8 +     // unstake_cooldown_slots: u64
+        8 +     // total_slashed: u64
+        1;      // bump: u8
+
+    /// Whether stakes are held in native SOL rather than an SPL mint
+    pub fn is_native_stake(&self) -> bool {
+        self.stake_mint == Pubkey::default()
+    }
+}
+
+/// Marks a relayer quote nonce as used, seeds `[b"quote_nonce", relayer, nonce]`
+#[account]
+pub struct UsedQuoteNonce {
+    /// Relayer account that signed the quote
+    pub relayer: Pubkey,
+
+    /// The quote nonce
+    pub nonce: u64,
+
+    /// When the quote was used (0 = not yet used)
+    pub used_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl UsedQuoteNonce {
+    /// Size of the UsedQuoteNonce struct for space allocation
+    pub const LEN: usize =
+        32 +    // relayer: Pubkey
+        8 +     // nonce: u64
+        8 +     // used_at: i64
+        1;      // bump: u8
+}
+
+/// A relayer's activity in one mint, seeds `[b"relayer_mint_stats", relayer, mint]`
+#[account]
+pub struct RelayerMintStats {
+    /// Relayer account the stats belong to
+    pub relayer: Pubkey,
+
+    /// Mint withdrawn (zeros for native SOL)
+    pub mint: Pubkey,
+
+    /// Number of withdrawals relayed in this mint
+    pub withdrawals: u64,
+
+    /// Amount paid out to recipients
+    pub total_relayed: u64,
+
+    /// Fees earned
+    pub total_fees: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RelayerMintStats {
+    /// Size of the RelayerMintStats struct for space allocation
+    pub const LEN: usize =
+        32 +    // relayer: Pubkey
+        32 +    // mint: Pubkey
+        8 +     // withdrawals: u64
+        8 +     // total_relayed: u64
+        8 +     // total_fees: u64
+        1;      // bump: u8
+}
+
+/// Relayer counters returned by `get_relayer_stats`, Borsh-encoded as return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelayerStatsView {
+    /// Relayer account
+    pub relayer: Pubkey,
+
+    /// Whether the relayer is active
+    pub is_active: bool,
+
+    /// Stake held in escrow
+    pub stake_amount: u64,
+
+    /// Number of withdrawals relayed
+    pub successful_withdrawals: u64,
+
+    /// Amount paid out to recipients, across mints
+    pub total_relayed: u64,
+
+    /// Fees earned, across mints
+    pub total_fees: u64,
+
+    /// Slot of the latest relayed withdrawal (0 = never)
+    pub last_active_slot: u64,
+
+    /// Number of times the relayer was slashed
+    pub slash_count: u64,
+
+    /// Slot the relayer was last slashed in (0 = never)
+    pub last_slashed_slot: u64,
+
+    /// Mint of the per-mint counters below (zeros if none were requested)
+    pub mint: Pubkey,
+
+    /// Withdrawals relayed in `mint`
+    pub mint_withdrawals: u64,
+
+    /// Amount paid out to recipients in `mint`
+    pub mint_total_relayed: u64,
+
+    /// Fees earned in `mint`
+    pub mint_total_fees: u64,
+}
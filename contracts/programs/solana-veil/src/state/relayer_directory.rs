@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+
+/// Longest relayer endpoint URL, in bytes
+pub const MAX_RELAYER_URL_LEN: usize = 128;
+
+/// Most pools a relayer can advertise
+pub const MAX_RELAYER_POOLS: usize = 16;
+
+/// Most mints a relayer can advertise
+pub const MAX_RELAYER_MINTS: usize = 8;
+
+/// Most entries in a relayer's fee schedule
+pub const MAX_RELAYER_FEE_TIERS: usize = 8;
+
+/// Number of relayers listed on each directory page
+pub const MAX_RELAYERS_PER_DIRECTORY_PAGE: usize = 32;
+
+/// Fee a relayer charges for withdrawals of one mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct RelayerFeeTier {
+    /// Mint the tier applies to (zeros for native SOL)
+    pub mint: Pubkey,
+
+    /// Fee in basis points of the withdrawn denomination
+    pub fee_basis_points: u16,
+
+    /// Minimum fee in the mint's smallest units, covering transaction costs
+    pub min_fee: u64,
+}
+
+impl RelayerFeeTier {
+    /// Serialized size of a RelayerFeeTier
+    pub const LEN: usize =
+        32 +    // mint: Pubkey
+        2 +     // fee_basis_points: u16
+        8;      // min_fee: u64
+}
+
+/// Service metadata a relayer publishes, as passed to register/update
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RelayerMetadataParams {
+    /// HTTPS endpoint of the relayer's API
+    pub endpoint: String,
+
+    /// Pools the relayer serves
+    pub supported_pools: Vec<Pubkey>,
+
+    /// Mints the relayer serves (zeros for native SOL)
+    pub supported_mints: Vec<Pubkey>,
+
+    /// Fees per mint
+    pub fee_schedule: Vec<RelayerFeeTier>,
+
+    /// Ed25519 key the relayer signs fee quotes with; stored on the `Relayer` account
+    pub quote_signer: Pubkey,
+}
+
+/// A relayer's published service metadata, seeds `[b"relayer_metadata", authority]`
+#[account]
+pub struct RelayerMetadata {
+    /// Relayer account this metadata describes
+    pub relayer: Pubkey,
+
+    /// Relayer authority
+    pub authority: Pubkey,
+
+    /// HTTPS endpoint of the relayer's API
+    pub endpoint: String,
+
+    /// Pools the relayer serves
+    pub supported_pools: Vec<Pubkey>,
+
+    /// Mints the relayer serves (zeros for native SOL)
+    pub supported_mints: Vec<Pubkey>,
+
+    /// Fees per mint
+    pub fee_schedule: Vec<RelayerFeeTier>,
+
+    /// Position of the relayer in the directory
+    pub directory_index: u64,
+
+    /// When the metadata was last updated
+    pub updated_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RelayerMetadata {
+    /// Size of the RelayerMetadata struct for space allocation
+    pub const LEN: usize =
+        32 +                                                // relayer: Pubkey
+        32 +                                                // authority: Pubkey
+        4 + MAX_RELAYER_URL_LEN +                           // endpoint: String
+        4 + 32 * MAX_RELAYER_POOLS +                        // supported_pools: Vec<Pubkey>
+        4 + 32 * MAX_RELAYER_MINTS +                        // supported_mints: Vec<Pubkey>
+        4 + RelayerFeeTier::LEN * MAX_RELAYER_FEE_TIERS +   // fee_schedule: Vec<RelayerFeeTier>
+        8 +                                                 // directory_index: u64
+        8 +                                                 // updated_at: i64
+        1;                                                  // bump: u8
+}
+
+/// Directory header: tracks how many relayers are listed so clients know how many pages to fetch
+#[account]
+pub struct RelayerDirectory {
+    /// Total number of listed relayers
+    pub relayer_count: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RelayerDirectory {
+    /// Size of the RelayerDirectory struct for space allocation
+    pub const LEN: usize =
+        8 +     // relayer_count: u64
+        1;      // bump: u8
+
+    /// Page that the next listed relayer will be appended to
+    pub fn next_page_index(&self) -> u64 {
+        self.relayer_count / MAX_RELAYERS_PER_DIRECTORY_PAGE as u64
+    }
+}
+
+/// Directory listing of a single relayer. Whether it is currently active is read
+/// from the `Relayer` account, which stays the single source of truth for staking state.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Copy)]
+pub struct RelayerDirectoryEntry {
+    /// Relayer account
+    pub relayer: Pubkey,
+
+    /// Relayer authority
+    pub authority: Pubkey,
+
+    /// Relayer metadata account
+    pub metadata: Pubkey,
+}
+
+impl RelayerDirectoryEntry {
+    /// Serialized size of a RelayerDirectoryEntry
+    pub const LEN: usize =
+        32 +    // relayer: Pubkey
+        32 +    // authority: Pubkey
+        32;     // metadata: Pubkey
+}
+
+/// One page of the relayer directory, seeds `[b"relayer_directory_page", page_index]`
+#[account]
+pub struct RelayerDirectoryPage {
+    /// Index of this page
+    pub page_index: u64,
+
+    /// Relayers listed on this page, in listing order
+    pub entries: Vec<RelayerDirectoryEntry>,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RelayerDirectoryPage {
+    /// Size of the RelayerDirectoryPage struct for space allocation
+    pub const LEN: usize =
+        8 +     // page_index: u64
+        4 + RelayerDirectoryEntry::LEN * MAX_RELAYERS_PER_DIRECTORY_PAGE + // entries: Vec<RelayerDirectoryEntry>
+        1;      // bump: u8
+}
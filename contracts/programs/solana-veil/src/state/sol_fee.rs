@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+/// Seed prefix of the data-less PDA holding a pool's SOL fee reserve
+pub const SOL_FEE_RESERVE_SEED: &[u8] = b"sol_fee_reserve";
+
+/// Longest price age an admin may allow, in seconds
+pub const MAX_PRICE_AGE_SECONDS: i64 = 300;
+
+/// Widest price confidence interval an admin may allow, in basis points of the price
+pub const MAX_PRICE_CONFIDENCE_BASIS_POINTS: u16 = 500;
+
+/// Lets relayers of an SPL pool take part of their fee in SOL, seeds `[b"sol_fee_config", pool]`.
+///
+/// The converted part of the fee is paid in lamports out of the pool's SOL fee reserve
+/// `[b"sol_fee_reserve", pool]`, priced from two Pyth USD price feeds; the tokens it
+/// replaces go to the pool's treasury vault, which funds the reserve.
+#[account]
+pub struct SolFeeConfig {
+    /// SPL pool this configuration belongs to
+    pub pool: Pubkey,
+
+    /// Program that must own both price accounts
+    pub pyth_program: Pubkey,
+
+    /// Pyth price account of the pool token in USD
+    pub token_price_feed: Pubkey,
+
+    /// Pyth price account of SOL in USD
+    pub sol_price_feed: Pubkey,
+
+    /// Oldest price accepted, in seconds
+    pub max_price_age_seconds: i64,
+
+    /// Widest confidence interval accepted, in basis points of the price
+    pub max_confidence_basis_points: u16,
+
+    /// Whether relayers may currently take fees in SOL
+    pub enabled: bool,
+
+    /// Pool tokens paid to the treasury in exchange for SOL
+    pub total_tokens_converted: u64,
+
+    /// Lamports paid to relayers out of the reserve
+    pub total_lamports_paid: u64,
+
+    /// Bump seed of the SOL fee reserve PDA
+    pub reserve_bump: u8,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl SolFeeConfig {
+    /// Size of the SolFeeConfig struct for space allocation
+    pub const LEN: usize =
+        32 +    // pool: Pubkey
+        32 +    // pyth_program: Pubkey
+        32 +    // token_price_feed: Pubkey
+        32 +    // sol_price_feed: Pubkey
+        8 +     // max_price_age_seconds: i64
+        2 +     // max_confidence_basis_points: u16
+        1 +     // enabled: bool
+        8 +     // total_tokens_converted: u64
+        8 +     // total_lamports_paid: u64
+        1 +     // reserve_bump: u8
+        1;      // bump: u8
+}
+
+/// Settings of [`SolFeeConfig`] an admin chooses
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SolFeeParams {
+    /// Program that must own both price accounts
+    pub pyth_program: Pubkey,
+
+    /// Pyth price account of the pool token in USD
+    pub token_price_feed: Pubkey,
+
+    /// Pyth price account of SOL in USD
+    pub sol_price_feed: Pubkey,
+
+    /// Oldest price accepted, in seconds
+    pub max_price_age_seconds: i64,
+
+    /// Widest confidence interval accepted, in basis points of the price
+    pub max_confidence_basis_points: u16,
+
+    /// Whether relayers may take fees in SOL
+    pub enabled: bool,
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use crate::errors::SolanaVeilError;
+
+/// Mint extensions that break pool privacy or custody:
+/// - `PermanentDelegate` lets a third party move funds out of the pool vault
+/// - `NonTransferable` means deposits could never be withdrawn
+/// - `ConfidentialTransferMint` can hide balances from the pool's accounting
+/// - `TransferHook` lets an external program block or observe withdrawals
+const DISALLOWED_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::PermanentDelegate,
+    ExtensionType::NonTransferable,
+    ExtensionType::ConfidentialTransferMint,
+    ExtensionType::TransferHook,
+];
+
+/// Reject Token-2022 mints carrying extensions that are unsafe to pool.
+/// Legacy SPL Token mints have no extensions and always pass.
+pub fn validate_pool_mint(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)
+        .map_err(|_| SolanaVeilError::InvalidTokenAccount)?;
+    let extensions = mint_state.get_extension_types()
+        .map_err(|_| SolanaVeilError::InvalidTokenAccount)?;
+
+    for extension in extensions {
+        if DISALLOWED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("Mint {} uses unsupported extension {:?}", mint.key(), extension);
+            return Err(SolanaVeilError::UnsupportedMintExtension.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Amount the sender must transfer so the receiver gets exactly `amount`
+/// after the mint's current transfer fee (if any) is withheld
+pub fn gross_amount_for(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(amount);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)
+        .map_err(|_| SolanaVeilError::InvalidTokenAccount)?;
+
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => {
+            let epoch = Clock::get()?.epoch;
+            let fee = fee_config
+                .calculate_inverse_epoch_fee(epoch, amount)
+                .ok_or(SolanaVeilError::CalculationError)?;
+            amount.checked_add(fee)
+                .ok_or(SolanaVeilError::CalculationError.into())
+        }
+        Err(_) => Ok(amount),
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::token_interface::{self, TransferChecked};
+use crate::errors::SolanaVeilError;
+
+/// Seed prefix of the data-less PDA holding a native SOL pool's funds
+pub const SOL_VAULT_SEED: &[u8] = b"sol_vault";
+
+/// Lamports a native vault keeps as rent reserve; never available for payouts
+pub fn sol_vault_rent_reserve() -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(0))
+}
+
+/// Source of pool payouts. Native and SPL pools share the same withdrawal path,
+/// only the transfer mechanics differ.
+pub enum PoolVault<'a, 'info> {
+    /// Data-less system account PDA `[b"sol_vault", pool]`, signed for with its own seeds
+    Native {
+        vault: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+        signer_seeds: &'a [&'a [u8]],
+    },
+    /// Token account owned by the pool PDA, signed for with the pool seeds
+    Token {
+        vault: AccountInfo<'info>,
+        mint: AccountInfo<'info>,
+        decimals: u8,
+        authority: AccountInfo<'info>,
+        token_program: AccountInfo<'info>,
+        signer_seeds: &'a [&'a [u8]],
+    },
+}
+
+impl<'a, 'info> PoolVault<'a, 'info> {
+    /// Fail unless the vault can pay out `amount` without touching its rent reserve
+    pub fn ensure_available(&self, amount: u64) -> Result<()> {
+        if let PoolVault::Native { vault, .. } = self {
+            let available = vault.lamports().saturating_sub(sol_vault_rent_reserve()?);
+            if available < amount {
+                return Err(SolanaVeilError::InsufficientFunds.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Pay `amount` out of the vault. `destination` is a system account for native
+    /// pools and a token account of the pool mint for SPL pools.
+    pub fn pay(&self, destination: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match self {
+            PoolVault::Native { vault, system_program, signer_seeds } => {
+                invoke_signed(
+                    &system_instruction::transfer(vault.key, destination.key, amount),
+                    &[vault.clone(), destination.clone(), system_program.clone()],
+                    &[signer_seeds],
+                )?;
+            }
+            PoolVault::Token { vault, mint, decimals, authority, token_program, signer_seeds } => {
+                let transfer_ctx = CpiContext::new_with_signer(
+                    token_program.clone(),
+                    TransferChecked {
+                        from: vault.clone(),
+                        mint: mint.clone(),
+                        to: destination.clone(),
+                        authority: authority.clone(),
+                    },
+                    std::slice::from_ref(signer_seeds),
+                );
+                token_interface::transfer_checked(transfer_ctx, amount, *decimals)?;
+            }
+        }
+
+        Ok(())
+    }
+}
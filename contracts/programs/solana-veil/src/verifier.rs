@@ -1,85 +1,138 @@
 use anchor_lang::prelude::*;
-use solana_program::keccak::hashv;
-use solana_program::sysvar;
-use solana_program::program_error::ProgramError;
+use anchor_lang::solana_program::alt_bn128::prelude::{
+    alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
+};
 
-// Add dependency for groth16 verifier (e.g., arkworks or solana-groth16-verifier)
-// use groth16_verifier::verify_groth16_proof;
-use ark_bn254::{Bn254, Fr};
-use ark_groth16::{Groth16, Proof, VerifyingKey, prepare_verifying_key, verify_proof};
-use ark_serialize::{CanonicalDeserialize};
+/// Size of an uncompressed G1 point: x | y, each 32 bytes big-endian
+pub const G1_LEN: usize = 64;
+
+/// Size of an uncompressed G2 point: x | y, each an Fq2 element encoded as c1 | c0
+pub const G2_LEN: usize = 128;
+
+/// Size of a serialized Groth16 proof: A (G1) | B (G2) | C (G1)
+pub const GROTH16_PROOF_LEN: usize = G1_LEN + G2_LEN + G1_LEN;
+
+/// Size of a verifying key before its IC points: alpha (G1) | -beta | -gamma | -delta (G2)
+pub const VERIFYING_KEY_PREFIX_LEN: usize = G1_LEN + 3 * G2_LEN;
+
+/// Output of the pairing syscall when the product of the pairings is the identity
+const PAIRING_SUCCESS: [u8; 32] = {
+    let mut result = [0u8; 32];
+    result[31] = 1;
+    result
+};
 
 /// Verify a bridge proof from another chain using Groth16 zk-SNARK
 pub fn verify_bridge_proof(
     proof_data: &[u8],
     vk_data: &[u8], // Verification key data (should be passed in via account or instruction data)
-    public_inputs: &[Fr], // Public inputs as expected by the circuit
+    public_inputs: &[[u8; 32]], // Public inputs as expected by the circuit
 ) -> Result<()> {
-    // Deserialize the verification key
-    let vk = VerifyingKey::<Bn254>::deserialize_compressed(vk_data)
-        .map_err(|_| ErrorCode::InvalidProof)?;
-    let pvk = prepare_verifying_key(&vk);
-
-    // Deserialize the proof
-    let proof = Proof::<Bn254>::deserialize_compressed(proof_data)
-        .map_err(|_| ErrorCode::InvalidProof)?;
-
-    // Verify the proof
-    let is_valid = verify_proof(&pvk, &proof, public_inputs)
-        .map_err(|_| ErrorCode::InvalidProof)?;
-    require!(is_valid, ErrorCode::InvalidProof);
+    verify_groth16_proof(proof_data, vk_data, public_inputs)
+}
+
+/// Number of public inputs a serialized verifying key takes, or `None` if its length
+/// does not match the layout `verify_groth16_proof` expects
+pub fn verifying_key_input_count(vk_data: &[u8]) -> Option<usize> {
+    let ic_len = vk_data.len().checked_sub(VERIFYING_KEY_PREFIX_LEN)?;
+    if ic_len == 0 || ic_len % G1_LEN != 0 {
+        return None;
+    }
+    Some(ic_len / G1_LEN - 1)
+}
+
+/// Verify a Groth16 proof with the alt_bn128 syscalls.
+///
+/// Points are uncompressed and big-endian, as the syscalls take them. The verifying key
+/// stores beta, gamma and delta negated, so the check is a single pairing product:
+/// `e(A, B) * e(alpha, -beta) * e(vk_x, -gamma) * e(C, -delta) == 1`
+/// with `vk_x = IC[0] + sum(input[i] * IC[i + 1])`. Public inputs are big-endian
+/// canonical scalars, see the `field_element_from_*` helpers.
+pub fn verify_groth16_proof(
+    proof_data: &[u8],
+    vk_data: &[u8],
+    public_inputs: &[[u8; 32]],
+) -> Result<()> {
+    require!(proof_data.len() == GROTH16_PROOF_LEN, ErrorCode::InvalidProof);
+    require!(
+        verifying_key_input_count(vk_data) == Some(public_inputs.len()),
+        ErrorCode::InvalidProof
+    );
+
+    let (alpha, negated_g2) = vk_data[..VERIFYING_KEY_PREFIX_LEN].split_at(G1_LEN);
+    let (negated_beta, negated_gamma_delta) = negated_g2.split_at(G2_LEN);
+    let (negated_gamma, negated_delta) = negated_gamma_delta.split_at(G2_LEN);
+    let mut ic = vk_data[VERIFYING_KEY_PREFIX_LEN..].chunks_exact(G1_LEN);
+
+    // Fold the public inputs into the IC points
+    let mut vk_x = [0u8; G1_LEN];
+    vk_x.copy_from_slice(ic.next().ok_or(ErrorCode::InvalidProof)?);
+    for (input, point) in public_inputs.iter().zip(ic) {
+        require!(is_canonical_field_element(input), ErrorCode::InvalidFieldElement);
+        // Zero inputs (e.g. no relayer, no callback) contribute nothing
+        if *input == [0u8; 32] {
+            continue;
+        }
+
+        let term = alt_bn128_multiplication(&[point, input.as_slice()].concat())
+            .map_err(|_| ErrorCode::InvalidProof)?;
+        let sum = alt_bn128_addition(&[vk_x.as_slice(), &term].concat())
+            .map_err(|_| ErrorCode::InvalidProof)?;
+        vk_x.copy_from_slice(&sum);
+    }
+
+    let (a, b_c) = proof_data.split_at(G1_LEN);
+    let (b, c) = b_c.split_at(G2_LEN);
+    let pairing_input = [a, b, alpha, negated_beta, &vk_x, negated_gamma, c, negated_delta].concat();
+    let result = alt_bn128_pairing(&pairing_input).map_err(|_| ErrorCode::InvalidProof)?;
+    require!(result == PAIRING_SUCCESS, ErrorCode::InvalidProof);
     Ok(())
 }
 
-/// Example structure for representing bridge proof public inputs
-struct BridgeProofPublicInputs {
-    source_chain_id: u16,
-    nullifier: [u8; 32],
-    amount: u64,
-    recipient: Pubkey,
+/// BN254 scalar field modulus `r`, big-endian
+pub const BN254_SCALAR_MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29,
+    0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91,
+    0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+/// Whether 32 big-endian bytes encode a BN254 scalar field element in canonical
+/// form (strictly less than the modulus). Byte arrays compare lexicographically,
+/// which for big-endian encodings is numeric order.
+pub fn is_canonical_field_element(bytes: &[u8; 32]) -> bool {
+    *bytes < BN254_SCALAR_MODULUS
 }
 
-/// Extract public inputs from a proof (placeholder implementation)
-fn extract_public_inputs(proof_data: &[u8]) -> Result<BridgeProofPublicInputs> {
-    // This is a placeholder - in a real implementation you would
-    // properly parse your ZK proof structure to extract inputs
-    
-    // For a real implementation, you would:
-    // 1. Deserialize the proof data
-    // 2. Extract the encoded public inputs
-    // 3. Parse them into your expected structure
-    
-    // Mock public input extraction - DO NOT USE IN PRODUCTION
-    let source_chain_id = u16::from_le_bytes([proof_data[0], proof_data[1]]);
-    
-    let mut nullifier = [0u8; 32];
-    if proof_data.len() >= 34 {
-        nullifier.copy_from_slice(&proof_data[2..34]);
+/// Interpret 32 big-endian bytes as a BN254 scalar field element.
+/// Non-canonical encodings (values >= the field modulus) are rejected so that
+/// each field element has exactly one byte representation on-chain.
+pub fn field_element_from_be_bytes(bytes: &[u8; 32]) -> Result<[u8; 32]> {
+    require!(is_canonical_field_element(bytes), ErrorCode::InvalidFieldElement);
+    Ok(*bytes)
+}
+
+/// Map an account address to a BN254 scalar field element, reducing it modulo the field.
+/// Addresses are public inputs as-is, so the reduction only has to agree with the circuit.
+pub fn field_element_from_pubkey(key: &Pubkey) -> [u8; 32] {
+    // 2^256 < 6r, so a few subtractions reduce any address
+    let mut element = key.to_bytes();
+    while !is_canonical_field_element(&element) {
+        let mut borrow = 0u16;
+        for i in (0..32).rev() {
+            let difference = 0x100 + element[i] as u16 - BN254_SCALAR_MODULUS[i] as u16 - borrow;
+            element[i] = difference as u8;
+            borrow = u16::from(difference < 0x100);
+        }
     }
-    
-    let amount = if proof_data.len() >= 42 {
-        u64::from_le_bytes([
-            proof_data[34], proof_data[35], proof_data[36], proof_data[37],
-            proof_data[38], proof_data[39], proof_data[40], proof_data[41],
-        ])
-    } else {
-        0
-    };
-    
-    let recipient = if proof_data.len() >= 74 {
-        let mut pubkey = [0u8; 32];
-        pubkey.copy_from_slice(&proof_data[42..74]);
-        Pubkey::new_from_array(pubkey)
-    } else {
-        Pubkey::default()
-    };
-    
-    Ok(BridgeProofPublicInputs {
-        source_chain_id,
-        nullifier,
-        amount,
-        recipient,
-    })
+    element
+}
+
+/// Encode an integer as a BN254 scalar field element
+pub fn field_element_from_u64(value: u64) -> [u8; 32] {
+    let mut element = [0u8; 32];
+    element[24..].copy_from_slice(&value.to_be_bytes());
+    element
 }
 
 /// Error codes for verifier operations
@@ -123,4 +176,108 @@ pub enum ErrorCode {
     
     #[msg("Insufficient stake")]
     InsufficientStake,
-}
\ No newline at end of file
+    
+    #[msg("Value is not a canonical field element")]
+    InvalidFieldElement,
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BN254 G1 generator (1, 2)
+    const G1_GENERATOR: [u8; 64] = {
+        let mut point = [0u8; 64];
+        point[31] = 1;
+        point[63] = 2;
+        point
+    };
+
+    /// BN254 G2 generator, negated
+    const NEGATED_G2_GENERATOR: [u8; 128] = [
+        0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a,
+        0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d, 0x25,
+        0xf1, 0xaa, 0x49, 0x33, 0x35, 0xa9, 0xe7, 0x12,
+        0x97, 0xe4, 0x85, 0xb7, 0xae, 0xf3, 0x12, 0xc2,
+        0x18, 0x00, 0xde, 0xef, 0x12, 0x1f, 0x1e, 0x76,
+        0x42, 0x6a, 0x00, 0x66, 0x5e, 0x5c, 0x44, 0x79,
+        0x67, 0x43, 0x22, 0xd4, 0xf7, 0x5e, 0xda, 0xdd,
+        0x46, 0xde, 0xbd, 0x5c, 0xd9, 0x92, 0xf6, 0xed,
+        0x27, 0x5d, 0xc4, 0xa2, 0x88, 0xd1, 0xaf, 0xb3,
+        0xcb, 0xb1, 0xac, 0x09, 0x18, 0x75, 0x24, 0xc7,
+        0xdb, 0x36, 0x39, 0x5d, 0xf7, 0xbe, 0x3b, 0x99,
+        0xe6, 0x73, 0xb1, 0x3a, 0x07, 0x5a, 0x65, 0xec,
+        0x1d, 0x9b, 0xef, 0xcd, 0x05, 0xa5, 0x32, 0x3e,
+        0x6d, 0xa4, 0xd4, 0x35, 0xf3, 0xb6, 0x17, 0xcd,
+        0xb3, 0xaf, 0x83, 0x28, 0x5c, 0x2d, 0xf7, 0x11,
+        0xef, 0x39, 0xc0, 0x15, 0x71, 0x82, 0x7f, 0x9d,
+    ];
+
+    /// `scalar` times the G1 generator
+    fn g1_mul(scalar: &[u8; 32]) -> Vec<u8> {
+        alt_bn128_multiplication(&[G1_GENERATOR.as_slice(), scalar].concat()).unwrap()
+    }
+
+    /// The scalar `-value`
+    fn negated(value: u64) -> [u8; 32] {
+        let mut low = [0u8; 16];
+        low.copy_from_slice(&BN254_SCALAR_MODULUS[16..]);
+        let mut scalar = BN254_SCALAR_MODULUS;
+        scalar[16..].copy_from_slice(&(u128::from_be_bytes(low) - value as u128).to_be_bytes());
+        scalar
+    }
+
+    /// Key with a public trapdoor (alpha = 2, beta = gamma = delta = 1, IC[i] = (i + 1) * G1)
+    /// and a proof it accepts for `inputs`: A = -G1, B = -G2, C = -(1 + IC(inputs)) * G1
+    fn stand_in(inputs: &[u64]) -> (Vec<u8>, Vec<u8>) {
+        let mut key = g1_mul(&field_element_from_u64(2));
+        for _ in 0..3 {
+            key.extend_from_slice(&NEGATED_G2_GENERATOR);
+        }
+        for i in 0..=inputs.len() {
+            key.extend(g1_mul(&field_element_from_u64(i as u64 + 1)));
+        }
+
+        let ic = 1 + inputs.iter().enumerate().map(|(i, input)| (i as u64 + 2) * input).sum::<u64>();
+        let proof = [
+            g1_mul(&negated(1)),
+            NEGATED_G2_GENERATOR.to_vec(),
+            g1_mul(&negated(1 + ic)),
+        ]
+        .concat();
+        (key, proof)
+    }
+
+    #[test]
+    fn accepts_proofs_for_their_inputs_only() {
+        let (key, proof) = stand_in(&[5, 0, 7]);
+        let inputs = [field_element_from_u64(5), field_element_from_u64(0), field_element_from_u64(7)];
+        assert!(verify_groth16_proof(&proof, &key, &inputs).is_ok());
+
+        let other_inputs = [field_element_from_u64(5), field_element_from_u64(1), field_element_from_u64(7)];
+        assert!(verify_groth16_proof(&proof, &key, &other_inputs).is_err());
+        assert!(verify_groth16_proof(&proof, &key, &inputs[..2]).is_err());
+    }
+
+    #[test]
+    fn reads_the_input_count_from_the_key_length() {
+        assert_eq!(verifying_key_input_count(&[0; VERIFYING_KEY_PREFIX_LEN + G1_LEN]), Some(0));
+        assert_eq!(verifying_key_input_count(&[0; VERIFYING_KEY_PREFIX_LEN + 10 * G1_LEN]), Some(9));
+        assert_eq!(verifying_key_input_count(&[0; VERIFYING_KEY_PREFIX_LEN]), None);
+        assert_eq!(verifying_key_input_count(&[0; VERIFYING_KEY_PREFIX_LEN + G1_LEN + 1]), None);
+        assert_eq!(verifying_key_input_count(&[0; G1_LEN]), None);
+    }
+
+    #[test]
+    fn reduces_addresses_into_the_field() {
+        let expected = [
+            0x0e, 0x0a, 0x77, 0xc1, 0x9a, 0x07, 0xdf, 0x2f,
+            0x66, 0x6e, 0xa3, 0x6f, 0x78, 0x79, 0x46, 0x2e,
+            0x36, 0xfc, 0x76, 0x95, 0x9f, 0x60, 0xcd, 0x29,
+            0xac, 0x96, 0x34, 0x1c, 0x4f, 0xff, 0xff, 0xfa,
+        ];
+        assert_eq!(field_element_from_pubkey(&Pubkey::new_from_array([0xff; 32])), expected);
+
+        let canonical = Pubkey::new_from_array(negated(1));
+        assert_eq!(field_element_from_pubkey(&canonical), negated(1));
+    }
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+declare_id!("CNE6JZwKGsEbGiKaydz1bViDurWWeu4xcE7ibaPG6Kaz");
+
+/// Maximum number of addresses the reference deny list can hold
+pub const MAX_DENIED_ADDRESSES: usize = 256;
+
+/// Reference screening program for SolanaVeil pools.
+///
+/// Pools that set `screening_program` to this program ID call `screen` on every
+/// deposit (with the depositor) and withdraw (with the recipient). The call fails
+/// if the address is on the deny list managed by the list authority.
+#[program]
+pub mod veil_screening {
+    use super::*;
+
+    pub fn initialize_deny_list(ctx: Context<InitializeDenyList>) -> Result<()> {
+        let deny_list = &mut ctx.accounts.deny_list;
+        deny_list.authority = ctx.accounts.authority.key();
+        deny_list.addresses = Vec::new();
+        deny_list.bump = ctx.bumps.deny_list;
+
+        Ok(())
+    }
+
+    pub fn add_denied_address(ctx: Context<UpdateDenyList>, address: Pubkey) -> Result<()> {
+        let deny_list = &mut ctx.accounts.deny_list;
+
+        require!(
+            !deny_list.addresses.contains(&address),
+            ScreeningError::AddressAlreadyDenied
+        );
+        require!(
+            deny_list.addresses.len() < MAX_DENIED_ADDRESSES,
+            ScreeningError::DenyListFull
+        );
+        deny_list.addresses.push(address);
+
+        emit!(DenyListUpdatedEvent {
+            address,
+            denied: true,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    pub fn remove_denied_address(ctx: Context<UpdateDenyList>, address: Pubkey) -> Result<()> {
+        let deny_list = &mut ctx.accounts.deny_list;
+
+        let position = deny_list
+            .addresses
+            .iter()
+            .position(|denied| *denied == address)
+            .ok_or(ScreeningError::AddressNotDenied)?;
+        deny_list.addresses.swap_remove(position);
+
+        emit!(DenyListUpdatedEvent {
+            address,
+            denied: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Screening entrypoint invoked by the SolanaVeil program via CPI
+    pub fn screen(ctx: Context<Screen>, pool: Pubkey, subject: Pubkey) -> Result<()> {
+        if ctx.accounts.deny_list.addresses.contains(&subject) {
+            msg!("Address {} denied for pool {}", subject, pool);
+            return err!(ScreeningError::AddressDenied);
+        }
+
+        Ok(())
+    }
+}
+
+/// Deny list account, a singleton PDA so callers cannot substitute their own list
+#[account]
+pub struct DenyList {
+    /// Authority that can add and remove addresses
+    pub authority: Pubkey,
+
+    /// Addresses that fail screening
+    pub addresses: Vec<Pubkey>,
+
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl DenyList {
+    /// Size of the DenyList struct for space allocation
+    pub const LEN: usize =
+        32 +                                // authority: Pubkey
+        4 + 32 * MAX_DENIED_ADDRESSES +     // addresses: Vec<Pubkey>
+        1;                                  // bump: u8
+}
+
+#[derive(Accounts)]
+pub struct InitializeDenyList<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DenyList::LEN,
+        seeds = [b"deny_list"],
+        bump
+    )]
+    pub deny_list: Account<'info, DenyList>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDenyList<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"deny_list"],
+        bump = deny_list.bump,
+        has_one = authority @ ScreeningError::Unauthorized,
+    )]
+    pub deny_list: Account<'info, DenyList>,
+}
+
+#[derive(Accounts)]
+pub struct Screen<'info> {
+    #[account(
+        seeds = [b"deny_list"],
+        bump = deny_list.bump,
+    )]
+    pub deny_list: Account<'info, DenyList>,
+}
+
+#[event]
+pub struct DenyListUpdatedEvent {
+    pub address: Pubkey,
+    pub denied: bool,
+    pub timestamp: i64,
+}
+
+#[error_code]
+pub enum ScreeningError {
+    #[msg("Address is on the deny list")]
+    AddressDenied,
+    #[msg("Address is already on the deny list")]
+    AddressAlreadyDenied,
+    #[msg("Address is not on the deny list")]
+    AddressNotDenied,
+    #[msg("Deny list is full")]
+    DenyListFull,
+    #[msg("Unauthorized access")]
+    Unauthorized,
+}
@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializePermanentDelegateInstruction,
+  getMintLen,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { SolanaVeil } from "../target/types/solana_veil";
+
+const WORMHOLE_PROGRAM_ID = new PublicKey("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+const ETHEREUM_CHAIN_ID = 2;
+
+function pda(programId: PublicKey, ...seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+describe("bridge tokens", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaVeil as Program<SolanaVeil>;
+  const admin = provider.wallet;
+
+  const config = pda(program.programId, Buffer.from("config"));
+  const accessControl = pda(program.programId, Buffer.from("access_control"));
+  const bridgeConfig = pda(program.programId, Buffer.from("bridge_config"));
+  const emitter = pda(program.programId, Buffer.from("emitter"));
+
+  // Other test files share the validator, so protocol-wide accounts may already exist
+  async function initializeOnce(address: PublicKey, initialize: () => Promise<string>): Promise<void> {
+    if (!(await provider.connection.getAccountInfo(address))) {
+      await initialize();
+    }
+  }
+
+  // Token-2022 mint carrying a permanent delegate, which could drain the bridge vault
+  async function createPermanentDelegateMint(): Promise<PublicKey> {
+    const mint = Keypair.generate();
+    const space = getMintLen([ExtensionType.PermanentDelegate]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: mint.publicKey,
+          space,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializePermanentDelegateInstruction(mint.publicKey, admin.publicKey, TOKEN_2022_PROGRAM_ID),
+        createInitializeMintInstruction(mint.publicKey, 6, admin.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [mint]
+    );
+    return mint.publicKey;
+  }
+
+  before(async () => {
+    await initializeOnce(config, () =>
+      program.methods
+        .initializeConfig(0, admin.publicKey, new BN(0))
+        .accounts({ authority: admin.publicKey, config, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await initializeOnce(accessControl, () =>
+      program.methods
+        .initializeAccessControl()
+        .accounts({ authority: admin.publicKey, config, accessControl, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await initializeOnce(bridgeConfig, () =>
+      program.methods
+        .initializeBridge(10, 1)
+        .accounts({
+          bridgeConfig,
+          authority: admin.publicKey,
+          treasury: admin.publicKey,
+          wormholeProgram: WORMHOLE_PROGRAM_ID,
+          wormholeTokenBridge: WORMHOLE_PROGRAM_ID,
+          wormholeBridge: pda(WORMHOLE_PROGRAM_ID, Buffer.from("Bridge")),
+          wormholeEmitter: emitter,
+          wormholeSequence: pda(WORMHOLE_PROGRAM_ID, Buffer.from("Sequence"), emitter.toBuffer()),
+          wormholeFeeCollector: pda(WORMHOLE_PROGRAM_ID, Buffer.from("fee_collector")),
+          wormholeClock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+          wormholeRent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc()
+    );
+
+    await program.methods
+      .grantRole(admin.publicKey, { bridgeOperator: {} })
+      .accounts({ admin: admin.publicKey, accessControl })
+      .rpc();
+
+    const { supportedChains, chainCount } = await program.account.bridgeConfig.fetch(bridgeConfig);
+    if (!supportedChains.slice(0, chainCount).some((chain) => chain.chainId === ETHEREUM_CHAIN_ID)) {
+      await program.methods
+        .addDestinationChain(ETHEREUM_CHAIN_ID)
+        .accounts({ bridgeConfig, accessControl, operator: admin.publicKey })
+        .rpc();
+    }
+  });
+
+  it("rejects mints with extensions pools refuse", async () => {
+    const mint = await createPermanentDelegateMint();
+
+    try {
+      await program.methods
+        .addSupportedToken(ETHEREUM_CHAIN_ID, new BN(1), new BN(1), new BN(1_000_000))
+        .accounts({ bridgeConfig, accessControl, operator: admin.publicKey, mint })
+        .rpc();
+      expect.fail("a mint with a permanent delegate should be refused");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnsupportedMintExtension");
+    }
+
+    const { supportedChains } = await program.account.bridgeConfig.fetch(bridgeConfig);
+    const chain = supportedChains.find((chain) => chain.chainId === ETHEREUM_CHAIN_ID);
+    expect(chain.tokens.slice(0, chain.tokenCount).some((token) => token.mint.equals(mint))).to.be.false;
+  });
+});
@@ -0,0 +1,166 @@
+import { BN } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { keccak_256 } from "@noble/hashes/sha3";
+
+// Groth16 key for a stand-in of withdraw.circom, so tests can make proofs the program's
+// alt_bn128 verifier accepts without a trusted setup. Its trapdoor is public
+// (alpha = 2, beta = gamma = delta = 1, IC[i] = (i + 1) * G1): never use it outside tests.
+
+const FIELD_MODULUS = 21888242871839275222246405745257275088696311157297823662689037894645226208583n;
+const SCALAR_MODULUS = 21888242871839275222246405745257275088548364400416034343698204186575808495617n;
+
+// BN254 G2 generator, negated, uncompressed big-endian with each coordinate's c1 first
+const NEGATED_G2_GENERATOR = Buffer.from(
+  "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2" +
+    "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed" +
+    "275dc4a288d1afb3cbb1ac09187524c7db36395df7be3b99e673b13a075a65ec" +
+    "1d9befcd05a5323e6da4d435f3b617cdb3af83285c2df711ef39c01571827f9d",
+  "hex"
+);
+
+const ALPHA = 2n;
+
+// Number of public signals of withdraw.circom
+const WITHDRAW_PUBLIC_SIGNALS = 9;
+
+type G1Point = { x: bigint; y: bigint } | null;
+
+function mod(value: bigint, modulus: bigint): bigint {
+  const result = value % modulus;
+  return result < 0n ? result + modulus : result;
+}
+
+function inverse(value: bigint): bigint {
+  let result = 1n;
+  let base = mod(value, FIELD_MODULUS);
+  for (let exponent = FIELD_MODULUS - 2n; exponent > 0n; exponent >>= 1n) {
+    if (exponent & 1n) result = (result * base) % FIELD_MODULUS;
+    base = (base * base) % FIELD_MODULUS;
+  }
+  return result;
+}
+
+function g1Add(a: G1Point, b: G1Point): G1Point {
+  if (a === null) return b;
+  if (b === null) return a;
+
+  let slope: bigint;
+  if (a.x === b.x) {
+    if (mod(a.y + b.y, FIELD_MODULUS) === 0n) return null;
+    slope = mod(3n * a.x * a.x * inverse(2n * a.y), FIELD_MODULUS);
+  } else {
+    slope = mod((b.y - a.y) * inverse(b.x - a.x), FIELD_MODULUS);
+  }
+  const x = mod(slope * slope - a.x - b.x, FIELD_MODULUS);
+  return { x, y: mod(slope * (a.x - x) - a.y, FIELD_MODULUS) };
+}
+
+// `scalar` times the G1 generator (1, 2)
+function g1Mul(scalar: bigint): G1Point {
+  let result: G1Point = null;
+  let addend: G1Point = { x: 1n, y: 2n };
+  for (let bits = mod(scalar, SCALAR_MODULUS); bits > 0n; bits >>= 1n) {
+    if (bits & 1n) result = g1Add(result, addend);
+    addend = g1Add(addend, addend);
+  }
+  return result;
+}
+
+function u256be(value: bigint): Buffer {
+  return Buffer.from(value.toString(16).padStart(64, "0"), "hex");
+}
+
+// Uncompressed big-endian encoding the alt_bn128 syscalls take, zeros for infinity
+function encodeG1(point: G1Point): Buffer {
+  if (point === null) return Buffer.alloc(64);
+  return Buffer.concat([u256be(point.x), u256be(point.y)]);
+}
+
+function u32le(value: number): Buffer {
+  const bytes = Buffer.alloc(4);
+  bytes.writeUInt32LE(value);
+  return bytes;
+}
+
+function fieldElement(bytes: Buffer | Uint8Array | number[]): bigint {
+  return mod(BigInt("0x" + Buffer.from(bytes).toString("hex")), SCALAR_MODULUS);
+}
+
+// Verifying key of the stand-in circuit, as stored by `initializeVerifyingKey`:
+// alpha, then beta, gamma and delta negated, then IC
+export function standInVerifyingKey(): Buffer {
+  const ic: Buffer[] = [];
+  for (let i = 0; i <= WITHDRAW_PUBLIC_SIGNALS; i++) {
+    ic.push(encodeG1(g1Mul(BigInt(i + 1))));
+  }
+  return Buffer.concat([
+    encodeG1(g1Mul(ALPHA)),
+    NEGATED_G2_GENERATOR,
+    NEGATED_G2_GENERATOR,
+    NEGATED_G2_GENERATOR,
+    ...ic,
+  ]);
+}
+
+export interface WithdrawPublicInputs {
+  nullifierHash: number[];
+  root: number[];
+  pool: PublicKey;
+  recipient: PublicKey;
+  relayer: PublicKey;
+  fee: BN;
+  denomination: BN;
+  leafIndexBound: BN;
+  callbackHash: Buffer | number[];
+}
+
+// Proof the stand-in key accepts for exactly `inputs`, in `withdraw`'s public input order:
+// with A and B the negated generators, C = (1 - alpha - IC(inputs)) * G1 balances the pairing check
+export function standInWithdrawProof(inputs: WithdrawPublicInputs): Buffer {
+  const signals = [
+    fieldElement(inputs.nullifierHash),
+    fieldElement(inputs.root),
+    fieldElement(inputs.pool.toBytes()),
+    fieldElement(inputs.recipient.toBytes()),
+    fieldElement(inputs.relayer.toBytes()),
+    BigInt(inputs.fee.toString()),
+    BigInt(inputs.denomination.toString()),
+    BigInt(inputs.leafIndexBound.toString()),
+    fieldElement(inputs.callbackHash),
+  ];
+
+  let ic = 1n;
+  signals.forEach((signal, i) => {
+    ic += signal * BigInt(i + 2);
+  });
+  return Buffer.concat([
+    encodeG1(g1Mul(-1n)),
+    NEGATED_G2_GENERATOR,
+    encodeG1(g1Mul(1n - ALPHA - ic)),
+  ]);
+}
+
+const CALLBACK_HASH_DOMAIN = Buffer.from("solana-veil:withdraw-callback:v1");
+
+// Mirrors `callback::callback_hash`: keccak(domain || program || account count ||
+// (key || is_writable) per account || data length || data) with the top byte cleared
+export function callbackHash(
+  program: PublicKey,
+  accounts: { pubkey: PublicKey; isWritable: boolean }[],
+  data: Buffer
+): Buffer {
+  const hash = Buffer.from(
+    keccak_256(
+      Buffer.concat([
+        CALLBACK_HASH_DOMAIN,
+        program.toBuffer(),
+        u32le(accounts.length),
+        ...accounts.map((account) => Buffer.concat([account.pubkey.toBuffer(), Buffer.from([account.isWritable ? 1 : 0])])),
+        u32le(data.length),
+        data,
+      ])
+    )
+  );
+  hash[0] = 0;
+  return hash;
+}
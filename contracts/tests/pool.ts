@@ -0,0 +1,64 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createMint } from "@solana/spl-token";
+import { expect } from "chai";
+import { SolanaVeil } from "../target/types/solana_veil";
+
+const DENOMINATION = new BN(LAMPORTS_PER_SOL / 50);
+
+function pda(programId: PublicKey, ...seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+describe("pool initialization", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaVeil as Program<SolanaVeil>;
+  const admin = provider.wallet;
+  const payer = (admin as anchor.Wallet).payer;
+
+  const denominationSeed = DENOMINATION.toArrayLike(Buffer, "le", 8);
+  const registry = pda(program.programId, Buffer.from("pool_registry"));
+
+  before(async () => {
+    // Other test files share the validator, so the registry may already exist
+    if (!(await provider.connection.getAccountInfo(registry))) {
+      await program.methods
+        .initializePoolRegistry()
+        .accounts({ payer: admin.publicKey, registry, systemProgram: SystemProgram.programId })
+        .rpc();
+    }
+  });
+
+  it("rejects native pools given a mint", async () => {
+    const mint = await createMint(provider.connection, payer, admin.publicKey, null, 6);
+    const pool = pda(program.programId, Buffer.from("pool"), denominationSeed, mint.toBuffer());
+    const { poolCount } = await program.account.poolRegistry.fetch(registry);
+
+    try {
+      await program.methods
+        .initializePool(DENOMINATION, 20, false)
+        .accounts({
+          authority: admin.publicKey,
+          pool,
+          tree: pda(program.programId, Buffer.from("tree"), denominationSeed, mint.toBuffer()),
+          checkpoints: pda(program.programId, Buffer.from("checkpoints"), pool.toBuffer()),
+          registry,
+          registryPage: pda(program.programId, Buffer.from("pool_registry_page"), poolCount.divn(32).toArrayLike(Buffer, "le", 8)),
+          mint,
+          tokenVault: null,
+          solVault: pda(program.programId, Buffer.from("sol_vault"), pool.toBuffer()),
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .rpc();
+      expect.fail("a native pool with a mint should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidTokenAccount");
+    }
+    expect(await provider.connection.getAccountInfo(pool)).to.be.null;
+  });
+});
@@ -0,0 +1,480 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import {
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { SolanaVeil } from "../target/types/solana_veil";
+import { MockPyth } from "../target/types/mock_pyth";
+import { standInVerifyingKey, standInWithdrawProof } from "./helpers";
+
+const DECIMALS = 6;
+const DENOMINATION = new BN(100 * 10 ** DECIMALS);
+const FEE = new BN(2 * 10 ** DECIMALS);
+const SOL_FEE = new BN(1.5 * 10 ** DECIMALS);
+const PRICE_ACCOUNT_LEN = 240;
+const QUOTE_DOMAIN = Buffer.from("solana-veil:relayer-quote:v1");
+
+// $1.00 per token and $150.00 per SOL, both with 8 decimals
+const TOKEN_PRICE = new BN(100_000_000);
+const SOL_PRICE = new BN(15_000_000_000);
+const PRICE_EXPONENT = -8;
+
+// 1.5 tokens at $1 buy 0.01 SOL at $150
+const SOL_FEE_LAMPORTS = 10_000_000;
+
+function pda(programId: PublicKey, ...seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+// Random commitment below the BN254 scalar modulus
+function randomCommitment(): number[] {
+  const commitment = Keypair.generate().publicKey.toBytes();
+  commitment[0] = 0;
+  return Array.from(commitment);
+}
+
+function u64(value: BN): Buffer {
+  return value.toArrayLike(Buffer, "le", 8);
+}
+
+describe("relayer fees in SOL", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaVeil as Program<SolanaVeil>;
+  const pyth = anchor.workspace.MockPyth as Program<MockPyth>;
+  const admin = provider.wallet;
+  const payer = (admin as anchor.Wallet).payer;
+  const relayer = Keypair.generate();
+  const tokenPriceFeed = Keypair.generate();
+  const solPriceFeed = Keypair.generate();
+
+  const config = pda(program.programId, Buffer.from("config"));
+  const accessControl = pda(program.programId, Buffer.from("access_control"));
+  const registry = pda(program.programId, Buffer.from("pool_registry"));
+  const relayerConfig = pda(program.programId, Buffer.from("relayer_config"));
+  const directory = pda(program.programId, Buffer.from("relayer_directory"));
+  const relayerStats = pda(program.programId, Buffer.from("relayer"), relayer.publicKey.toBuffer());
+  let relayerMintStats: PublicKey;
+
+  let mint: PublicKey;
+  let pool: PublicKey;
+  let tree: PublicKey;
+  let checkpoints: PublicKey;
+  let verifyingKey: PublicKey;
+  let tokenVault: PublicKey;
+  let treasuryTokenAccount: PublicKey;
+  let solFeeConfig: PublicKey;
+  let solFeeReserve: PublicKey;
+  let relayerTokenAccount: PublicKey;
+
+  // Other test files share the validator, so protocol-wide accounts may already exist
+  async function initializeOnce(address: PublicKey, initialize: () => Promise<string>): Promise<void> {
+    if (!(await provider.connection.getAccountInfo(address))) {
+      await initialize();
+    }
+  }
+
+  async function now(): Promise<number> {
+    return provider.connection.getBlockTime(await provider.connection.getSlot());
+  }
+
+  async function setPrice(feed: Keypair, price: BN, conf: BN, publishTime: number): Promise<void> {
+    await pyth.methods
+      .setPrice(price, conf, PRICE_EXPONENT, new BN(publishTime))
+      .accounts({ priceAccount: feed.publicKey })
+      .rpc();
+  }
+
+  async function setFreshPrices(): Promise<void> {
+    const publishTime = await now();
+    await setPrice(tokenPriceFeed, TOKEN_PRICE, new BN(10_000), publishTime);
+    await setPrice(solPriceFeed, SOL_PRICE, new BN(1_000_000), publishTime);
+  }
+
+  async function configureSolFee(pythProgram: PublicKey): Promise<void> {
+    await program.methods
+      .configureSolFee({
+        pythProgram,
+        tokenPriceFeed: tokenPriceFeed.publicKey,
+        solPriceFeed: solPriceFeed.publicKey,
+        maxPriceAgeSeconds: new BN(60),
+        maxConfidenceBasisPoints: 100,
+        enabled: true,
+      })
+      .accounts({
+        admin: admin.publicKey,
+        accessControl,
+        pool,
+        solFeeConfig,
+        solFeeReserve,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  }
+
+  before(async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(relayer.publicKey, 2 * LAMPORTS_PER_SOL)
+    );
+
+    await initializeOnce(config, () =>
+      program.methods
+        .initializeConfig(0, admin.publicKey, new BN(0))
+        .accounts({ authority: admin.publicKey, config, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await initializeOnce(accessControl, () =>
+      program.methods
+        .initializeAccessControl()
+        .accounts({ authority: admin.publicKey, config, accessControl, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await initializeOnce(registry, () =>
+      program.methods
+        .initializePoolRegistry()
+        .accounts({ payer: admin.publicKey, registry, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await initializeOnce(relayerConfig, () =>
+      program.methods
+        .initializeRelayerConfig(new BN(0), new BN(0))
+        .accounts({
+          authority: admin.publicKey,
+          config,
+          relayerConfig,
+          stakeMint: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc()
+    );
+    await initializeOnce(directory, () =>
+      program.methods
+        .initializeRelayerDirectory()
+        .accounts({ payer: admin.publicKey, directory, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+
+    // SPL pool
+    mint = await createMint(provider.connection, payer, admin.publicKey, null, DECIMALS);
+    relayerMintStats = pda(program.programId, Buffer.from("relayer_mint_stats"), relayerStats.toBuffer(), mint.toBuffer());
+    const denominationSeed = DENOMINATION.toArrayLike(Buffer, "le", 8);
+    pool = pda(program.programId, Buffer.from("pool"), denominationSeed, mint.toBuffer());
+    tree = pda(program.programId, Buffer.from("tree"), denominationSeed, mint.toBuffer());
+    checkpoints = pda(program.programId, Buffer.from("checkpoints"), pool.toBuffer());
+    verifyingKey = pda(program.programId, Buffer.from("verifying_key"), pool.toBuffer(), Buffer.from([0]));
+    treasuryTokenAccount = pda(program.programId, Buffer.from("treasury_vault"), pool.toBuffer());
+    solFeeConfig = pda(program.programId, Buffer.from("sol_fee_config"), pool.toBuffer());
+    solFeeReserve = pda(program.programId, Buffer.from("sol_fee_reserve"), pool.toBuffer());
+
+    const vaultKeypair = Keypair.generate();
+    tokenVault = vaultKeypair.publicKey;
+    const { poolCount } = await program.account.poolRegistry.fetch(registry);
+    await program.methods
+      .initializePool(DENOMINATION, 20, true)
+      .accounts({
+        authority: admin.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        registry,
+        registryPage: pda(program.programId, Buffer.from("pool_registry_page"), u64(poolCount.divn(32))),
+        mint,
+        tokenVault,
+        solVault: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([vaultKeypair])
+      .rpc();
+
+    await program.methods
+      .grantRole(admin.publicKey, { keyManager: {} })
+      .accounts({ admin: admin.publicKey, accessControl })
+      .rpc();
+    await program.methods
+      .initializeVerifyingKey({ withdraw: {} }, standInVerifyingKey())
+      .accounts({
+        keyManager: admin.publicKey,
+        accessControl,
+        pool,
+        verifyingKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .initializeTreasuryVault()
+      .accounts({
+        authority: admin.publicKey,
+        config,
+        pool,
+        treasuryVault: null,
+        treasuryTokenAccount,
+        mint,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    // Fund the pool with one note
+    const depositor = await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, admin.publicKey);
+    await mintTo(provider.connection, payer, mint, depositor.address, payer, BigInt(DENOMINATION.toString()));
+    const commitment = randomCommitment();
+    await program.methods
+      .deposit(commitment)
+      .accounts({
+        user: admin.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        userTokenAccount: depositor.address,
+        poolTokenAccount: tokenVault,
+        solVault: null,
+        mint,
+        screeningProgram: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        {
+          pubkey: pda(program.programId, Buffer.from("commitment"), pool.toBuffer(), Buffer.from(commitment)),
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .rpc();
+
+    // Active relayer signing quotes with its own key
+    await program.methods
+      .setRelayer(true, 100)
+      .accounts({
+        authority: relayer.publicKey,
+        relayerConfig,
+        relayer: relayerStats,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([relayer])
+      .rpc();
+
+    const { relayerCount } = await program.account.relayerDirectory.fetch(directory);
+    await program.methods
+      .registerRelayerMetadata({
+        endpoint: "https://relayer.example",
+        supportedPools: [pool],
+        supportedMints: [mint],
+        feeSchedule: [],
+        quoteSigner: relayer.publicKey,
+      })
+      .accounts({
+        authority: relayer.publicKey,
+        relayer: relayerStats,
+        metadata: pda(program.programId, Buffer.from("relayer_metadata"), relayer.publicKey.toBuffer()),
+        directory,
+        directoryPage: pda(program.programId, Buffer.from("relayer_directory_page"), u64(relayerCount.divn(32))),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([relayer])
+      .rpc();
+
+    relayerTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, relayer.publicKey)
+    ).address;
+
+    // Locally mocked Pyth price accounts
+    const rent = await provider.connection.getMinimumBalanceForRentExemption(PRICE_ACCOUNT_LEN);
+    const createFeeds = new Transaction().add(
+      ...[tokenPriceFeed, solPriceFeed].map((feed) =>
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: feed.publicKey,
+          lamports: rent,
+          space: PRICE_ACCOUNT_LEN,
+          programId: pyth.programId,
+        })
+      )
+    );
+    await provider.sendAndConfirm(createFeeds, [tokenPriceFeed, solPriceFeed]);
+    await setFreshPrices();
+
+    await configureSolFee(pyth.programId);
+
+    // The treasury funds the reserve with a plain transfer
+    await provider.sendAndConfirm(
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: solFeeReserve, lamports: LAMPORTS_PER_SOL })
+      )
+    );
+  });
+
+  // Relayed withdrawal taking `SOL_FEE` of its fee in SOL, with the ed25519 quote signature
+  async function relayedWithdraw(): Promise<PublicKey> {
+    const recipient = Keypair.generate().publicKey;
+    const nullifierHash = randomCommitment();
+    const nonce = new BN(Buffer.from(Keypair.generate().publicKey.toBytes().slice(0, 6)));
+    const expirySlot = new BN((await provider.connection.getSlot()) + 100);
+
+    const quote = {
+      pool,
+      fee: FEE,
+      expirySlot,
+      nonce,
+      exclusiveUntilSlot: new BN(0),
+      solFee: SOL_FEE,
+    };
+    const message = Buffer.concat([
+      QUOTE_DOMAIN,
+      pool.toBuffer(),
+      u64(FEE),
+      u64(expirySlot),
+      u64(nonce),
+      u64(new BN(0)),
+      u64(SOL_FEE),
+    ]);
+
+    const root = Array.from(Buffer.alloc(32));
+    const proof = standInWithdrawProof({
+      nullifierHash,
+      root,
+      pool,
+      recipient,
+      relayer: relayer.publicKey,
+      fee: FEE,
+      denomination: DENOMINATION,
+      leafIndexBound: new BN(0),
+      callbackHash: Buffer.alloc(32),
+    });
+
+    await program.methods
+      .withdraw(
+        { proofData: proof, root, nullifierHash, recipient, fee: FEE, leafIndexBound: new BN(0) },
+        null,
+        quote
+      )
+      .accounts({
+        user: relayer.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        config,
+        verifyingKey,
+        nullifier: pda(program.programId, Buffer.from("nullifier"), Buffer.from(nullifierHash), pool.toBuffer()),
+        poolTokenAccount: tokenVault,
+        solVault: null,
+        recipient,
+        recipientTokenAccount: getAssociatedTokenAddressSync(mint, recipient),
+        relayer: relayer.publicKey,
+        relayerStats,
+        quoteNonce: pda(program.programId, Buffer.from("quote_nonce"), relayerStats.toBuffer(), u64(nonce)),
+        relayerMintStats,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        relayerTokenAccount,
+        treasuryVault: null,
+        treasuryTokenAccount,
+        solFeeConfig,
+        solFeeReserve,
+        tokenPriceFeed: tokenPriceFeed.publicKey,
+        solPriceFeed: solPriceFeed.publicKey,
+        mint,
+        screeningProgram: null,
+        callbackProgram: null,
+        recipientProgram: null,
+        callbackAuthority: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      })
+      .preInstructions([Ed25519Program.createInstructionWithPrivateKey({ privateKey: relayer.secretKey, message })])
+      .signers([relayer])
+      .rpc();
+
+    return recipient;
+  }
+
+  async function expectWithdrawError(code: string): Promise<void> {
+    const reserveBefore = await provider.connection.getBalance(solFeeReserve);
+    try {
+      await relayedWithdraw();
+      expect.fail(`withdrawal should fail with ${code}`);
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal(code);
+    }
+    expect(await provider.connection.getBalance(solFeeReserve)).to.equal(reserveBefore);
+  }
+
+  it("rejects price accounts not owned by the configured Pyth program", async () => {
+    // The feeds hold valid prices but belong to the mock Pyth program
+    await configureSolFee(Keypair.generate().publicKey);
+
+    await expectWithdrawError("InvalidPriceFeed");
+    await configureSolFee(pyth.programId);
+  });
+
+  it("rejects stale prices", async () => {
+    await setPrice(tokenPriceFeed, TOKEN_PRICE, new BN(10_000), (await now()) - 3600);
+
+    await expectWithdrawError("StalePrice");
+    await setFreshPrices();
+  });
+
+  it("rejects prices with a wide confidence interval", async () => {
+    // 5% of the SOL price, above the configured 1%
+    await setPrice(solPriceFeed, SOL_PRICE, SOL_PRICE.divn(20), await now());
+
+    await expectWithdrawError("PriceConfidenceTooWide");
+    await setFreshPrices();
+  });
+
+  it("pays the converted part of the fee in SOL from the reserve", async () => {
+    const reserveBefore = await provider.connection.getBalance(solFeeReserve);
+    const relayerTokensBefore = (await getAccount(provider.connection, relayerTokenAccount)).amount;
+    const treasuryBefore = (await getAccount(provider.connection, treasuryTokenAccount)).amount;
+
+    const recipient = await relayedWithdraw();
+
+    // The relayer takes the rest of the fee in tokens, the treasury the converted tokens
+    const relayerTokens = (await getAccount(provider.connection, relayerTokenAccount)).amount;
+    expect((relayerTokens - relayerTokensBefore).toString()).to.equal(FEE.sub(SOL_FEE).toString());
+    const treasury = (await getAccount(provider.connection, treasuryTokenAccount)).amount;
+    expect((treasury - treasuryBefore).toString()).to.equal(SOL_FEE.toString());
+
+    expect(await provider.connection.getBalance(solFeeReserve)).to.equal(reserveBefore - SOL_FEE_LAMPORTS);
+
+    const recipientAccount = await getAccount(provider.connection, getAssociatedTokenAddressSync(mint, recipient));
+    expect(recipientAccount.amount.toString()).to.equal(DENOMINATION.sub(FEE).toString());
+
+    const settings = await program.account.solFeeConfig.fetch(solFeeConfig);
+    expect(settings.totalTokensConverted.toString()).to.equal(SOL_FEE.toString());
+    expect(settings.totalLamportsPaid.toNumber()).to.equal(SOL_FEE_LAMPORTS);
+  });
+
+  it("records the relayer's performance counters", async () => {
+    const stats = await program.account.relayer.fetch(relayerStats);
+    expect(stats.successfulWithdrawals.toNumber()).to.equal(1);
+    expect(stats.totalFees.toString()).to.equal(FEE.toString());
+    expect(stats.lastActiveSlot.toNumber()).to.be.greaterThan(0);
+
+    const mintStats = await program.account.relayerMintStats.fetch(relayerMintStats);
+    expect(mintStats.mint.toBase58()).to.equal(mint.toBase58());
+    expect(mintStats.withdrawals.toNumber()).to.equal(1);
+    expect(mintStats.totalFees.toString()).to.equal(FEE.toString());
+  });
+});
@@ -0,0 +1,253 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { SolanaVeil } from "../target/types/solana_veil";
+import { VeilScreening } from "../target/types/veil_screening";
+import { standInVerifyingKey, standInWithdrawProof } from "./helpers";
+
+const DENOMINATION = new BN(LAMPORTS_PER_SOL / 20);
+const NATIVE_MINT = PublicKey.default;
+
+function pda(programId: PublicKey, ...seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+// Random commitment below the BN254 scalar modulus
+function randomCommitment(): number[] {
+  const commitment = Keypair.generate().publicKey.toBytes();
+  commitment[0] = 0;
+  return Array.from(commitment);
+}
+
+describe("screening", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaVeil as Program<SolanaVeil>;
+  const screening = anchor.workspace.VeilScreening as Program<VeilScreening>;
+  const admin = provider.wallet;
+  const denied = Keypair.generate();
+
+  const denominationSeed = DENOMINATION.toArrayLike(Buffer, "le", 8);
+  const config = pda(program.programId, Buffer.from("config"));
+  const accessControl = pda(program.programId, Buffer.from("access_control"));
+  const registry = pda(program.programId, Buffer.from("pool_registry"));
+  const pool = pda(program.programId, Buffer.from("pool"), denominationSeed, NATIVE_MINT.toBuffer());
+  const tree = pda(program.programId, Buffer.from("tree"), denominationSeed, NATIVE_MINT.toBuffer());
+  const checkpoints = pda(program.programId, Buffer.from("checkpoints"), pool.toBuffer());
+  const solVault = pda(program.programId, Buffer.from("sol_vault"), pool.toBuffer());
+  const verifyingKey = pda(program.programId, Buffer.from("verifying_key"), pool.toBuffer(), Buffer.from([0]));
+  const denyList = pda(screening.programId, Buffer.from("deny_list"));
+
+  // Other test files share the validator, so protocol-wide accounts may already exist
+  async function initializeOnce(address: PublicKey, initialize: () => Promise<string>): Promise<void> {
+    if (!(await provider.connection.getAccountInfo(address))) {
+      await initialize();
+    }
+  }
+
+  before(async () => {
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(denied.publicKey, LAMPORTS_PER_SOL)
+    );
+
+    await initializeOnce(config, () =>
+      program.methods
+        .initializeConfig(0, admin.publicKey, new BN(0))
+        .accounts({ authority: admin.publicKey, config, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await initializeOnce(accessControl, () =>
+      program.methods
+        .initializeAccessControl()
+        .accounts({ authority: admin.publicKey, config, accessControl, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await initializeOnce(registry, () =>
+      program.methods
+        .initializePoolRegistry()
+        .accounts({ payer: admin.publicKey, registry, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+
+    const { poolCount } = await program.account.poolRegistry.fetch(registry);
+    await program.methods
+      .initializePool(DENOMINATION, 20, false)
+      .accounts({
+        authority: admin.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        registry,
+        registryPage: pda(program.programId, Buffer.from("pool_registry_page"), poolCount.divn(32).toArrayLike(Buffer, "le", 8)),
+        mint: null,
+        tokenVault: null,
+        solVault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: null,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .grantRole(admin.publicKey, { keyManager: {} })
+      .accounts({ admin: admin.publicKey, accessControl })
+      .rpc();
+    await program.methods
+      .initializeVerifyingKey({ withdraw: {} }, standInVerifyingKey())
+      .accounts({
+        keyManager: admin.publicKey,
+        accessControl,
+        pool,
+        verifyingKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    // Screen the pool against the reference deny list, with `denied` on it
+    await initializeOnce(denyList, () =>
+      screening.methods
+        .initializeDenyList()
+        .accounts({ authority: admin.publicKey, denyList, systemProgram: SystemProgram.programId })
+        .rpc()
+    );
+    await screening.methods
+      .addDeniedAddress(denied.publicKey)
+      .accounts({ authority: admin.publicKey, denyList })
+      .rpc();
+
+    await program.methods
+      .updatePoolConfig(null, null, screening.programId, null, {
+        maxTotalDeposits: null,
+        withdrawalLimitAmount: null,
+        withdrawalLimitWindowSlots: null,
+      })
+      .accounts({
+        admin: admin.publicKey,
+        authority: admin.publicKey,
+        accessControl,
+        pool,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  function deposit(user: PublicKey) {
+    const commitment = randomCommitment();
+    return program.methods
+      .deposit(commitment)
+      .accounts({
+        user,
+        pool,
+        tree,
+        checkpoints,
+        userTokenAccount: null,
+        poolTokenAccount: null,
+        solVault,
+        mint: null,
+        screeningProgram: screening.programId,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: null,
+      })
+      .remainingAccounts([
+        {
+          pubkey: pda(program.programId, Buffer.from("commitment"), pool.toBuffer(), Buffer.from(commitment)),
+          isWritable: true,
+          isSigner: false,
+        },
+        { pubkey: denyList, isWritable: false, isSigner: false },
+      ]);
+  }
+
+  function withdraw(recipient: PublicKey) {
+    const nullifierHash = randomCommitment();
+    const nullifier = pda(program.programId, Buffer.from("nullifier"), Buffer.from(nullifierHash), pool.toBuffer());
+    const root = Array.from(Buffer.alloc(32));
+    const proof = standInWithdrawProof({
+      nullifierHash,
+      root,
+      pool,
+      recipient,
+      relayer: PublicKey.default,
+      fee: new BN(0),
+      denomination: DENOMINATION,
+      leafIndexBound: new BN(0),
+      callbackHash: Buffer.alloc(32),
+    });
+
+    return program.methods
+      .withdraw(
+        { proofData: proof, root, nullifierHash, recipient, fee: new BN(0), leafIndexBound: new BN(0) },
+        null,
+        null
+      )
+      .accounts({
+        user: admin.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        config,
+        verifyingKey,
+        nullifier,
+        poolTokenAccount: null,
+        solVault,
+        recipient,
+        recipientTokenAccount: null,
+        relayer: null,
+        relayerStats: null,
+        quoteNonce: null,
+        relayerMintStats: null,
+        instructionsSysvar: null,
+        relayerTokenAccount: null,
+        treasuryVault: null,
+        treasuryTokenAccount: null,
+        solFeeConfig: null,
+        solFeeReserve: null,
+        tokenPriceFeed: null,
+        solPriceFeed: null,
+        mint: null,
+        screeningProgram: screening.programId,
+        callbackProgram: null,
+        recipientProgram: null,
+        callbackAuthority: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: null,
+        associatedTokenProgram: null,
+      })
+      .remainingAccounts([{ pubkey: denyList, isWritable: false, isSigner: false }]);
+  }
+
+  it("rejects deposits from a denied address", async () => {
+    try {
+      await deposit(denied.publicKey).signers([denied]).rpc();
+      expect.fail("deposit from a denied address should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AddressDenied");
+    }
+  });
+
+  it("rejects withdrawals to a denied address", async () => {
+    await deposit(admin.publicKey).rpc();
+
+    const balanceBefore = await provider.connection.getBalance(denied.publicKey);
+    try {
+      await withdraw(denied.publicKey).rpc();
+      expect.fail("withdrawal to a denied address should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("AddressDenied");
+    }
+    expect(await provider.connection.getBalance(denied.publicKey)).to.equal(balanceBefore);
+  });
+
+  it("lets allowed addresses deposit and withdraw", async () => {
+    const recipient = Keypair.generate().publicKey;
+
+    await deposit(admin.publicKey).rpc();
+    await withdraw(recipient).rpc();
+
+    const { totalWithdrawn } = await program.account.pool.fetch(pool);
+    expect(totalWithdrawn.gtn(0)).to.be.true;
+    expect(await provider.connection.getBalance(recipient)).to.be.greaterThan(0);
+  });
+});
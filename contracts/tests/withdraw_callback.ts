@@ -0,0 +1,319 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { SolanaVeil } from "../target/types/solana_veil";
+import { MockWithdrawReceiver } from "../target/types/mock_withdraw_receiver";
+import { callbackHash, standInVerifyingKey, standInWithdrawProof } from "./helpers";
+
+const DENOMINATION = new BN(LAMPORTS_PER_SOL / 10);
+const NATIVE_MINT = PublicKey.default;
+
+function pda(programId: PublicKey, ...seeds: (Buffer | Uint8Array)[]): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+// Random commitment below the BN254 scalar modulus
+function randomCommitment(): number[] {
+  const commitment = Keypair.generate().publicKey.toBytes();
+  commitment[0] = 0;
+  return Array.from(commitment);
+}
+
+describe("withdraw callbacks", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaVeil as Program<SolanaVeil>;
+  const receiver = anchor.workspace.MockWithdrawReceiver as Program<MockWithdrawReceiver>;
+  const admin = provider.wallet;
+
+  const denominationSeed = DENOMINATION.toArrayLike(Buffer, "le", 8);
+  const config = pda(program.programId, Buffer.from("config"));
+  const accessControl = pda(program.programId, Buffer.from("access_control"));
+  const registry = pda(program.programId, Buffer.from("pool_registry"));
+  const registryPage = pda(program.programId, Buffer.from("pool_registry_page"), new BN(0).toArrayLike(Buffer, "le", 8));
+  const pool = pda(program.programId, Buffer.from("pool"), denominationSeed, NATIVE_MINT.toBuffer());
+  const tree = pda(program.programId, Buffer.from("tree"), denominationSeed, NATIVE_MINT.toBuffer());
+  const checkpoints = pda(program.programId, Buffer.from("checkpoints"), pool.toBuffer());
+  const solVault = pda(program.programId, Buffer.from("sol_vault"), pool.toBuffer());
+  const recipientProgram = pda(program.programId, Buffer.from("recipient_program"), receiver.programId.toBuffer());
+  const callbackAuthority = pda(program.programId, Buffer.from("callback_authority"), pool.toBuffer());
+  const verifyingKey = pda(program.programId, Buffer.from("verifying_key"), pool.toBuffer(), Buffer.from([0]));
+
+  const receipt = pda(receiver.programId, Buffer.from("receipt"));
+  // Program-derived recipient owned (by derivation) by the receiver program
+  const position = pda(receiver.programId, Buffer.from("position"));
+
+  before(async () => {
+    await program.methods
+      .initializeConfig(0, admin.publicKey, new BN(0))
+      .accounts({ authority: admin.publicKey, config, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    await program.methods
+      .initializeAccessControl()
+      .accounts({ authority: admin.publicKey, config, accessControl, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    await program.methods
+      .initializePoolRegistry()
+      .accounts({ payer: admin.publicKey, registry, systemProgram: SystemProgram.programId })
+      .rpc();
+
+    await program.methods
+      .initializePool(DENOMINATION, 20, false)
+      .accounts({
+        authority: admin.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        registry,
+        registryPage,
+        mint: null,
+        tokenVault: null,
+        solVault,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: null,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    await program.methods
+      .grantRole(admin.publicKey, { keyManager: {} })
+      .accounts({ admin: admin.publicKey, accessControl })
+      .rpc();
+    await program.methods
+      .initializeVerifyingKey({ withdraw: {} }, standInVerifyingKey())
+      .accounts({
+        keyManager: admin.publicKey,
+        accessControl,
+        pool,
+        verifyingKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    await receiver.methods
+      .initializeReceipt(program.programId)
+      .accounts({ authority: admin.publicKey, receipt, systemProgram: SystemProgram.programId })
+      .rpc();
+  });
+
+  async function deposit(): Promise<void> {
+    const commitment = randomCommitment();
+    await program.methods
+      .deposit(commitment)
+      .accounts({
+        user: admin.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        userTokenAccount: null,
+        poolTokenAccount: null,
+        solVault,
+        mint: null,
+        screeningProgram: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: null,
+      })
+      .remainingAccounts([
+        {
+          pubkey: pda(program.programId, Buffer.from("commitment"), pool.toBuffer(), Buffer.from(commitment)),
+          isWritable: true,
+          isSigner: false,
+        },
+      ])
+      .rpc();
+  }
+
+  // Withdrawal to `position` running a callback with `data`, proven for `provenData`
+  function withdrawWithCallback(
+    data: Buffer,
+    callbackProgram: PublicKey,
+    whitelistEntry: PublicKey | null,
+    provenData: Buffer = data
+  ) {
+    const nullifierHash = randomCommitment();
+    const nullifier = pda(program.programId, Buffer.from("nullifier"), Buffer.from(nullifierHash), pool.toBuffer());
+    const root = Array.from(Buffer.alloc(32));
+    const proof = standInWithdrawProof({
+      nullifierHash,
+      root,
+      pool,
+      recipient: position,
+      relayer: PublicKey.default,
+      fee: new BN(0),
+      denomination: DENOMINATION,
+      leafIndexBound: new BN(0),
+      callbackHash: callbackHash(callbackProgram, [{ pubkey: receipt, isWritable: true }], provenData),
+    });
+
+    return program.methods
+      .withdraw(
+        { proofData: proof, root, nullifierHash, recipient: position, fee: new BN(0), leafIndexBound: new BN(0) },
+        { data, accountCount: 1 },
+        null
+      )
+      .accounts({
+        user: admin.publicKey,
+        pool,
+        tree,
+        checkpoints,
+        config,
+        verifyingKey,
+        nullifier,
+        poolTokenAccount: null,
+        solVault,
+        recipient: position,
+        recipientTokenAccount: null,
+        relayer: null,
+        relayerStats: null,
+        quoteNonce: null,
+        relayerMintStats: null,
+        instructionsSysvar: null,
+        relayerTokenAccount: null,
+        treasuryVault: null,
+        treasuryTokenAccount: null,
+        solFeeConfig: null,
+        solFeeReserve: null,
+        tokenPriceFeed: null,
+        solPriceFeed: null,
+        mint: null,
+        screeningProgram: null,
+        callbackProgram,
+        recipientProgram: whitelistEntry,
+        callbackAuthority,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: null,
+        associatedTokenProgram: null,
+      })
+      .remainingAccounts([{ pubkey: receipt, isWritable: true, isSigner: false }]);
+  }
+
+  it("rejects callbacks from callers that are not SolanaVeil pools", async () => {
+    const impostor = Keypair.generate();
+
+    try {
+      await receiver.methods
+        .onVeilWithdrawal(Array.from(Buffer.alloc(32)), NATIVE_MINT, DENOMINATION, Buffer.from("memo"))
+        .accounts({ callbackAuthority: impostor.publicKey, pool: impostor.publicKey, recipient: position, receipt })
+        .signers([impostor])
+        .rpc();
+      expect.fail("callback from a non-pool signer should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnknownCaller");
+    }
+  });
+
+  it("rejects callbacks naming a real pool without its callback authority", async () => {
+    const impostor = Keypair.generate();
+
+    try {
+      await receiver.methods
+        .onVeilWithdrawal(Array.from(Buffer.alloc(32)), NATIVE_MINT, DENOMINATION, Buffer.from("memo"))
+        .accounts({ callbackAuthority: impostor.publicKey, pool, recipient: position, receipt })
+        .signers([impostor])
+        .rpc();
+      expect.fail("callback signed by another key should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("UnknownCaller");
+    }
+  });
+
+  it("only lets admins whitelist recipient programs", async () => {
+    const stranger = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(stranger.publicKey, LAMPORTS_PER_SOL)
+    );
+
+    try {
+      await program.methods
+        .addRecipientProgram()
+        .accounts({
+          admin: stranger.publicKey,
+          accessControl,
+          program: receiver.programId,
+          recipientProgram,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+      expect.fail("non-admin whitelisting should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("MissingRole");
+    }
+  });
+
+  it("refuses callbacks into programs that are not whitelisted", async () => {
+    await deposit();
+
+    try {
+      await withdrawWithCallback(Buffer.from("memo"), receiver.programId, null).rpc();
+      expect.fail("callback into a non-whitelisted program should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidCallbackProgram");
+    }
+  });
+
+  it("pays a program-derived recipient and then runs the callback", async () => {
+    await program.methods
+      .addRecipientProgram()
+      .accounts({
+        admin: admin.publicKey,
+        accessControl,
+        program: receiver.programId,
+        recipientProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const data = Buffer.from("open-position:42");
+    const balanceBefore = await provider.connection.getBalance(position);
+
+    await withdrawWithCallback(data, receiver.programId, recipientProgram).rpc();
+
+    const balanceAfter = await provider.connection.getBalance(position);
+    const record = await receiver.account.receipt.fetch(receipt);
+    expect(record.calls.toNumber()).to.equal(1);
+    expect(record.lastPool.equals(pool)).to.be.true;
+    expect(record.lastRecipient.equals(position)).to.be.true;
+    expect(record.lastMint.equals(NATIVE_MINT)).to.be.true;
+    expect(record.lastAmount.toNumber()).to.equal(balanceAfter - balanceBefore);
+    expect(Buffer.from(record.lastData).equals(data)).to.be.true;
+  });
+
+  it("rejects callback payloads the proof was not made for", async () => {
+    await deposit();
+
+    // The proof commits to the program, forwarded accounts and payload through the callback hash
+    try {
+      await withdrawWithCallback(Buffer.from("open-position:43"), receiver.programId, recipientProgram, Buffer.from("open-position:42")).rpc();
+      expect.fail("a callback swapped after proving should fail");
+    } catch (error) {
+      expect(error.error.errorCode.code).to.equal("InvalidProof");
+    }
+  });
+
+  it("aborts the withdrawal when the callback fails", async () => {
+    await deposit();
+    await receiver.methods
+      .setReject(true)
+      .accounts({ authority: admin.publicKey, receipt })
+      .rpc();
+
+    const balanceBefore = await provider.connection.getBalance(position);
+    try {
+      await withdrawWithCallback(Buffer.from("memo"), receiver.programId, recipientProgram).rpc();
+      expect.fail("a rejected callback should abort the withdrawal");
+    } catch (error) {
+      expect(error.logs.join("\n")).to.include("Receiver is set to reject callbacks");
+    }
+    expect(await provider.connection.getBalance(position)).to.equal(balanceBefore);
+
+    await receiver.methods
+      .setReject(false)
+      .accounts({ authority: admin.publicKey, receipt })
+      .rpc();
+  });
+});
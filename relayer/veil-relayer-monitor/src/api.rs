@@ -0,0 +1,40 @@
+//! HTTP endpoints.
+//!
+//! - `GET /metrics`: Prometheus metrics
+//! - `GET /healthz`: 200 while the fee payer holds at least the minimum balance,
+//!   503 otherwise or before its balance was read
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+
+use crate::monitor::Monitor;
+
+pub fn router(monitor: Arc<Monitor>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics))
+        .route("/healthz", get(healthz))
+        .with_state(monitor)
+}
+
+async fn metrics(State(monitor): State<Arc<Monitor>>) -> Response {
+    match monitor.metrics().render() {
+        Ok(body) => ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], body).into_response(),
+        Err(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()).into_response(),
+    }
+}
+
+async fn healthz(State(monitor): State<Arc<Monitor>>) -> Response {
+    let health = monitor.health();
+    let status = if health.healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(health)).into_response()
+}
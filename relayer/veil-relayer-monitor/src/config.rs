@@ -0,0 +1,41 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use solana_sdk::pubkey::Pubkey;
+
+/// Prometheus metrics and health checks for a SolanaVeil relayer
+#[derive(Debug, Parser)]
+#[command(name = "relayer-monitor", version)]
+pub struct Config {
+    /// RPC endpoint of the cluster the relayer submits to
+    #[arg(long, env = "VEIL_RELAYER_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    pub rpc_url: String,
+
+    /// Address of the relayer's fee payer (the relayer key)
+    #[arg(long, env = "VEIL_MONITOR_PAYER")]
+    pub payer: Pubkey,
+
+    /// The relayer's job queue database
+    #[arg(long, env = "VEIL_RELAYER_QUEUE", default_value = "relayer-jobs.sqlite")]
+    pub queue_path: PathBuf,
+
+    /// Address the metrics and health endpoints listen on
+    #[arg(long, env = "VEIL_MONITOR_LISTEN", default_value = "127.0.0.1:9100")]
+    pub listen: SocketAddr,
+
+    /// Seconds between polls of the queue and the RPC node
+    #[arg(long, default_value_t = 15)]
+    pub poll_interval_seconds: u64,
+
+    /// `/healthz` fails while the fee payer holds fewer lamports than this
+    #[arg(long, default_value_t = 100_000_000)]
+    pub min_payer_balance: u64,
+}
+
+impl Config {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_seconds.max(1))
+    }
+}
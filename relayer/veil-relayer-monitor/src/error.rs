@@ -0,0 +1,16 @@
+use thiserror::Error;
+use veil_relayer_queue::QueueError;
+
+#[derive(Debug, Error)]
+pub enum MonitorError {
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    #[error("Metrics error: {0}")]
+    Metrics(#[from] prometheus::Error),
+
+    #[error(transparent)]
+    Queue(#[from] QueueError),
+}
+
+pub type Result<T> = std::result::Result<T, MonitorError>;
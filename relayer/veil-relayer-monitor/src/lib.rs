@@ -0,0 +1,15 @@
+//! Monitoring for SolanaVeil relayers.
+//!
+//! `relayer-monitor` runs next to a relayer and reads its job queue to count
+//! `withdraw` submissions, their latency, why they failed and the fees they earned.
+//! It also polls an RPC node for the fee payer's SOL balance and the node's health.
+//! Everything is exported as Prometheus metrics, and `/healthz` fails while the
+//! payer is running out of SOL.
+
+pub mod api;
+pub mod config;
+pub mod error;
+pub mod metrics;
+pub mod monitor;
+pub mod program_errors;
+pub mod rpc;
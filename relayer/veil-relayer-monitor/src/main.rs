@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use clap::Parser;
+use tracing_subscriber::EnvFilter;
+
+use veil_relayer_monitor::api;
+use veil_relayer_monitor::config::Config;
+use veil_relayer_monitor::monitor::Monitor;
+use veil_relayer_monitor::rpc::RpcNode;
+use veil_relayer_queue::JobQueue;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let config = Config::parse();
+
+    let queue = JobQueue::open(&config.queue_path)
+        .with_context(|| format!("cannot open job queue {}", config.queue_path.display()))?;
+
+    let rpc = Arc::new(RpcNode::new(config.rpc_url.clone()));
+    let monitor = Monitor::new(rpc, queue, config.payer, config.min_payer_balance)
+        .context("cannot set up metrics")?;
+
+    tokio::spawn(monitor.clone().run(config.poll_interval()));
+
+    tracing::info!(listen = %config.listen, payer = %config.payer, "serving relayer metrics");
+    axum::Server::bind(&config.listen)
+        .serve(api::router(monitor).into_make_service())
+        .await
+        .context("HTTP server failed")?;
+
+    Ok(())
+}
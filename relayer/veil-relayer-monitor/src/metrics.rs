@@ -0,0 +1,121 @@
+//! Prometheus metrics exported by the monitor.
+
+use prometheus::{
+    Encoder, Gauge, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+
+use crate::error::Result;
+
+/// Seconds from receiving a withdrawal to its confirmation
+const LATENCY_BUCKETS: &[f64] = &[1.0, 2.0, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0, 600.0];
+
+pub struct Metrics {
+    registry: Registry,
+
+    /// Finished withdrawals by `outcome` (`confirmed` or `failed`)
+    pub withdrawals: IntCounterVec,
+
+    /// `withdraw` transactions sent, including retries
+    pub withdraw_transactions: IntCounter,
+
+    pub withdraw_latency: Histogram,
+
+    /// Failed withdrawals by `reason`, see [`crate::program_errors::failure_reason`]
+    pub withdraw_failures: IntCounterVec,
+
+    /// Fees earned on confirmed withdrawals by `pool`, in the pool's base units
+    pub fee_revenue: IntCounterVec,
+
+    pub payer_balance: IntGauge,
+
+    /// 1 while the RPC node answers and reports itself healthy
+    pub rpc_up: IntGauge,
+
+    /// Duration of the latest RPC health check
+    pub rpc_latency: Gauge,
+
+    pub rpc_errors: IntCounter,
+
+    /// Unix time of the latest completed poll
+    pub last_poll: IntGauge,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self> {
+        let registry = Registry::new();
+
+        let withdrawals = IntCounterVec::new(
+            Opts::new("veil_relayer_withdrawals_total", "Finished withdrawals by outcome"),
+            &["outcome"],
+        )?;
+        let withdraw_transactions = IntCounter::new(
+            "veil_relayer_withdraw_transactions_total",
+            "Withdraw transactions sent, including retries",
+        )?;
+        let withdraw_latency = Histogram::with_opts(
+            HistogramOpts::new(
+                "veil_relayer_withdraw_latency_seconds",
+                "Seconds from receiving a withdrawal to its confirmation",
+            )
+            .buckets(LATENCY_BUCKETS.to_vec()),
+        )?;
+        let withdraw_failures = IntCounterVec::new(
+            Opts::new("veil_relayer_withdraw_failures_total", "Failed withdrawals by reason"),
+            &["reason"],
+        )?;
+        let fee_revenue = IntCounterVec::new(
+            Opts::new(
+                "veil_relayer_fee_revenue_total",
+                "Fees earned on confirmed withdrawals, in the pool's base units",
+            ),
+            &["pool"],
+        )?;
+        let payer_balance = IntGauge::new(
+            "veil_relayer_payer_balance_lamports",
+            "SOL balance of the relayer's fee payer",
+        )?;
+        let rpc_up = IntGauge::new("veil_relayer_rpc_up", "Whether the RPC node is reachable and healthy")?;
+        let rpc_latency = Gauge::new(
+            "veil_relayer_rpc_latency_seconds",
+            "Duration of the latest RPC health check",
+        )?;
+        let rpc_errors = IntCounter::new("veil_relayer_rpc_errors_total", "Failed RPC calls")?;
+        let last_poll = IntGauge::new(
+            "veil_relayer_monitor_last_poll_timestamp_seconds",
+            "Unix time of the latest completed poll",
+        )?;
+
+        registry.register(Box::new(withdrawals.clone()))?;
+        registry.register(Box::new(withdraw_transactions.clone()))?;
+        registry.register(Box::new(withdraw_latency.clone()))?;
+        registry.register(Box::new(withdraw_failures.clone()))?;
+        registry.register(Box::new(fee_revenue.clone()))?;
+        registry.register(Box::new(payer_balance.clone()))?;
+        registry.register(Box::new(rpc_up.clone()))?;
+        registry.register(Box::new(rpc_latency.clone()))?;
+        registry.register(Box::new(rpc_errors.clone()))?;
+        registry.register(Box::new(last_poll.clone()))?;
+
+        Ok(Self {
+            registry,
+            withdrawals,
+            withdraw_transactions,
+            withdraw_latency,
+            withdraw_failures,
+            fee_revenue,
+            payer_balance,
+            rpc_up,
+            rpc_latency,
+            rpc_errors,
+            last_poll,
+        })
+    }
+
+    /// All metrics in the Prometheus text format
+    pub fn render(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use veil_relayer_queue::{Job, JobQueue, JobState};
+
+use crate::error::Result;
+use crate::metrics::Metrics;
+use crate::program_errors::failure_reason;
+use crate::rpc::Rpc;
+
+/// The parts of the relayer's stored withdraw request the monitor reads
+#[derive(Debug, Deserialize)]
+struct RequestSummary {
+    pool: String,
+    fee: u64,
+}
+
+/// Whether the relayer can keep paying for transactions, as reported by `/healthz`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Health {
+    pub healthy: bool,
+
+    /// Latest known balance of the fee payer, `None` until one was read
+    pub payer_balance: Option<u64>,
+
+    pub min_payer_balance: u64,
+
+    pub rpc_up: bool,
+}
+
+#[derive(Default)]
+struct PollState {
+    /// Jobs finished at or after this Unix time have not all been counted yet
+    cursor: i64,
+
+    /// Finished jobs already counted at the cursor, with the update they were counted at
+    counted: HashMap<[u8; 32], (i64, JobState)>,
+
+    payer_balance: Option<u64>,
+
+    rpc_up: bool,
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+/// Watches one relayer through its job queue and an RPC node.
+///
+/// Withdrawals are counted once, when they are confirmed or failed; jobs finished
+/// before the monitor started are left out.
+pub struct Monitor {
+    rpc: Arc<dyn Rpc>,
+    queue: JobQueue,
+    payer: Pubkey,
+    min_payer_balance: u64,
+    metrics: Metrics,
+    state: Mutex<PollState>,
+}
+
+impl Monitor {
+    pub fn new(
+        rpc: Arc<dyn Rpc>,
+        queue: JobQueue,
+        payer: Pubkey,
+        min_payer_balance: u64,
+    ) -> Result<Arc<Self>> {
+        Ok(Arc::new(Self {
+            rpc,
+            queue,
+            payer,
+            min_payer_balance,
+            metrics: Metrics::new()?,
+            state: Mutex::new(PollState {
+                cursor: now(),
+                ..PollState::default()
+            }),
+        }))
+    }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    fn state(&self) -> MutexGuard<'_, PollState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Poll the queue and the RPC node once
+    pub async fn poll(&self) -> Result<()> {
+        self.poll_rpc().await;
+        self.poll_jobs()?;
+        self.metrics.last_poll.set(now());
+        Ok(())
+    }
+
+    /// Poll every `interval` until the process exits
+    pub async fn run(self: Arc<Self>, interval: Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(error) = self.poll().await {
+                tracing::error!(%error, "poll failed");
+            }
+        }
+    }
+
+    async fn poll_rpc(&self) {
+        let started = Instant::now();
+        let health = self.rpc.get_health().await;
+        self.metrics.rpc_latency.set(started.elapsed().as_secs_f64());
+
+        let balance = self.rpc.get_balance(&self.payer).await;
+
+        let errors = [health.as_ref().err(), balance.as_ref().err()];
+        for error in errors.into_iter().flatten() {
+            tracing::warn!(%error, "RPC check failed");
+            self.metrics.rpc_errors.inc();
+        }
+
+        let rpc_up = health.is_ok() && balance.is_ok();
+        self.metrics.rpc_up.set(rpc_up as i64);
+
+        let mut state = self.state();
+        state.rpc_up = rpc_up;
+        if let Ok(balance) = balance {
+            self.metrics.payer_balance.set(balance as i64);
+            state.payer_balance = Some(balance);
+        }
+    }
+
+    fn poll_jobs(&self) -> Result<()> {
+        let mut state = self.state();
+        let jobs = self.queue.finished_since(state.cursor)?;
+
+        for job in &jobs {
+            if state.counted.get(&job.nullifier_hash) == Some(&(job.updated_at, job.state)) {
+                continue;
+            }
+            self.count(job);
+            state.counted.insert(job.nullifier_hash, (job.updated_at, job.state));
+        }
+
+        if let Some(latest) = jobs.last() {
+            state.cursor = latest.updated_at;
+        }
+        // Only jobs at the cursor can be listed again
+        let cursor = state.cursor;
+        state.counted.retain(|_, (updated_at, _)| *updated_at >= cursor);
+
+        Ok(())
+    }
+
+    fn count(&self, job: &Job) {
+        self.metrics
+            .withdrawals
+            .with_label_values(&[job.state.as_str()])
+            .inc();
+        self.metrics.withdraw_transactions.inc_by(job.attempts.into());
+
+        match job.state {
+            JobState::Confirmed => {
+                let latency = (job.updated_at - job.created_at).max(0);
+                self.metrics.withdraw_latency.observe(latency as f64);
+
+                match serde_json::from_slice::<RequestSummary>(&job.payload) {
+                    Ok(request) => self
+                        .metrics
+                        .fee_revenue
+                        .with_label_values(&[&request.pool])
+                        .inc_by(request.fee),
+                    Err(error) => tracing::warn!(job = %job.id(), %error, "unreadable withdraw request"),
+                }
+            }
+            _ => {
+                let reason = failure_reason(job.last_error.as_deref().unwrap_or_default());
+                self.metrics
+                    .withdraw_failures
+                    .with_label_values(&[&reason])
+                    .inc();
+            }
+        }
+    }
+
+    pub fn health(&self) -> Health {
+        let state = self.state();
+
+        Health {
+            healthy: state
+                .payer_balance
+                .is_some_and(|balance| balance >= self.min_payer_balance),
+            payer_balance: state.payer_balance,
+            min_payer_balance: self.min_payer_balance,
+            rpc_up: state.rpc_up,
+        }
+    }
+}
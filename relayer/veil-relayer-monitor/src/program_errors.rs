@@ -0,0 +1,89 @@
+//! Names of the program's error codes.
+//!
+//! Anchor numbers `SolanaVeilError` variants from 6000 in declaration order and
+//! keeps no table to map a code back, so the names are read from the program's
+//! `errors.rs` at compile time and stay in step with it.
+
+use std::sync::OnceLock;
+
+/// Code of the first `SolanaVeilError` variant (Anchor's custom error offset)
+pub const ERROR_CODE_OFFSET: u32 = 6000;
+
+const ERRORS_SOURCE: &str = include_str!("../../../contracts/programs/solana-veil/src/errors.rs");
+
+fn names() -> &'static [&'static str] {
+    static NAMES: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+    NAMES.get_or_init(|| {
+        ERRORS_SOURCE
+            .lines()
+            .map(str::trim)
+            .skip_while(|line| !line.starts_with("pub enum SolanaVeilError"))
+            .skip(1)
+            .take_while(|line| *line != "}")
+            .filter_map(|line| line.strip_suffix(','))
+            .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric()))
+            .collect()
+    })
+}
+
+/// Variant name of a `SolanaVeilError` code, e.g. `StalePrice`
+pub fn error_name(code: u32) -> Option<&'static str> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    names().get(index as usize).copied()
+}
+
+/// Code of a `SolanaVeilError` variant
+pub fn error_code(name: &str) -> Option<u32> {
+    let index = names().iter().position(|candidate| *candidate == name)?;
+    Some(ERROR_CODE_OFFSET + index as u32)
+}
+
+/// Custom program error code in a transaction error message, as printed by
+/// `TransactionError` (`custom program error: 0x1770`)
+pub fn parse_custom_error(message: &str) -> Option<u32> {
+    let (_, rest) = message.split_once("custom program error: 0x")?;
+    let digits: String = rest.chars().take_while(char::is_ascii_hexdigit).collect();
+    u32::from_str_radix(&digits, 16).ok()
+}
+
+/// Metric label for why a withdrawal failed.
+///
+/// Program errors are labelled with their `SolanaVeilError` name, other custom
+/// codes (e.g. Anchor's own) as `custom_<code>`. Failures the relayer decided on
+/// without an on-chain error get a short label of their own.
+pub fn failure_reason(message: &str) -> String {
+    if let Some(code) = parse_custom_error(message) {
+        return match error_name(code) {
+            Some(name) => name.to_string(),
+            None => format!("custom_{code}"),
+        };
+    }
+
+    const RELAYER_REASONS: &[(&str, &str)] = &[
+        ("nullifier was spent by another transaction", "nullifier_spent_elsewhere"),
+        ("Nullifier has already been spent", "nullifier_spent"),
+        ("Root is not the pool's current merkle root", "stale_root"),
+        ("Proof failed verification", "invalid_proof"),
+        ("Withdrawals are paused", "withdrawals_paused"),
+        ("Relayer is not active", "relayer_inactive"),
+        ("Invalid request", "invalid_request"),
+        ("RPC error", "rpc_error"),
+        ("gave up after", "retries_exhausted"),
+    ];
+
+    if message.starts_with("Fee ") {
+        if message.contains("below") {
+            return "fee_too_low".to_string();
+        }
+        if message.contains("above") {
+            return "fee_too_high".to_string();
+        }
+    }
+
+    RELAYER_REASONS
+        .iter()
+        .find(|(pattern, _)| message.contains(pattern))
+        .map_or("other", |(_, reason)| reason)
+        .to_string()
+}
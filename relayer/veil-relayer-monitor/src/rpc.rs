@@ -0,0 +1,44 @@
+//! What the monitor asks of an RPC node, behind [`Rpc`] so tests can fake it.
+
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::error::{MonitorError, Result};
+
+#[async_trait]
+pub trait Rpc: Send + Sync {
+    async fn get_balance(&self, address: &Pubkey) -> Result<u64>;
+
+    /// `Ok` when the node reports itself healthy
+    async fn get_health(&self) -> Result<()>;
+}
+
+/// [`Rpc`] backed by a JSON-RPC node
+pub struct RpcNode {
+    client: RpcClient,
+}
+
+impl RpcNode {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+        }
+    }
+}
+
+fn rpc_error(error: impl std::fmt::Display) -> MonitorError {
+    MonitorError::Rpc(error.to_string())
+}
+
+#[async_trait]
+impl Rpc for RpcNode {
+    async fn get_balance(&self, address: &Pubkey) -> Result<u64> {
+        self.client.get_balance(address).await.map_err(rpc_error)
+    }
+
+    async fn get_health(&self) -> Result<()> {
+        self.client.get_health().await.map_err(rpc_error)
+    }
+}
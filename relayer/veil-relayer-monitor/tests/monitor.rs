@@ -0,0 +1,175 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use solana_sdk::pubkey::Pubkey;
+use veil_relayer_monitor::error::{MonitorError, Result};
+use veil_relayer_monitor::monitor::Monitor;
+use veil_relayer_monitor::program_errors::{error_code, error_name, failure_reason};
+use veil_relayer_monitor::rpc::Rpc;
+use veil_relayer_queue::{JobQueue, Submission};
+
+const MIN_BALANCE: u64 = 1_000_000;
+
+struct FakeRpc {
+    balance: AtomicU64,
+    healthy: AtomicBool,
+}
+
+#[async_trait]
+impl Rpc for FakeRpc {
+    async fn get_balance(&self, _address: &Pubkey) -> Result<u64> {
+        if !self.healthy.load(Ordering::SeqCst) {
+            return Err(MonitorError::Rpc("connection refused".into()));
+        }
+        Ok(self.balance.load(Ordering::SeqCst))
+    }
+
+    async fn get_health(&self) -> Result<()> {
+        if !self.healthy.load(Ordering::SeqCst) {
+            return Err(MonitorError::Rpc("connection refused".into()));
+        }
+        Ok(())
+    }
+}
+
+fn rpc(balance: u64) -> Arc<FakeRpc> {
+    Arc::new(FakeRpc {
+        balance: AtomicU64::new(balance),
+        healthy: AtomicBool::new(true),
+    })
+}
+
+fn request(pool: &Pubkey, fee: u64) -> Vec<u8> {
+    serde_json::to_vec(&serde_json::json!({ "pool": pool.to_string(), "fee": fee })).unwrap()
+}
+
+fn send(queue: &JobQueue, nullifier_hash: &[u8; 32], payload: &[u8], signature: &str) {
+    queue.receive(nullifier_hash, payload).unwrap();
+    queue.mark_verified(nullifier_hash).unwrap();
+    queue
+        .mark_submitted(
+            nullifier_hash,
+            &Submission {
+                signature: signature.to_string(),
+                last_valid_block_height: 100,
+            },
+        )
+        .unwrap();
+}
+
+fn metric(rendered: &str, series: &str) -> Option<u64> {
+    rendered
+        .lines()
+        .find_map(|line| line.strip_prefix(series)?.trim().parse().ok())
+}
+
+#[test]
+fn names_program_errors() {
+    assert_eq!(error_name(6000), Some("InsufficientFunds"));
+    assert_eq!(error_name(6001), Some("InvalidProof"));
+    assert_eq!(error_name(5999), None);
+
+    let stale_price = error_code("StalePrice").unwrap();
+    assert_eq!(error_name(stale_price), Some("StalePrice"));
+
+    let message = format!("Error processing Instruction 1: custom program error: {stale_price:#x}");
+    assert_eq!(failure_reason(&message), "StalePrice");
+    assert_eq!(failure_reason("custom program error: 0x7d6"), "custom_2006");
+    assert_eq!(failure_reason("Fee 10 is below the relayer's fee of 20"), "fee_too_low");
+    assert_eq!(failure_reason("nullifier was spent by another transaction"), "nullifier_spent_elsewhere");
+}
+
+#[tokio::test]
+async fn counts_finished_withdrawals_once() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("jobs.sqlite");
+    let pool = Pubkey::new_unique();
+
+    let monitor = Monitor::new(
+        rpc(MIN_BALANCE),
+        JobQueue::open(&path).unwrap(),
+        Pubkey::new_unique(),
+        MIN_BALANCE,
+    )
+    .unwrap();
+
+    // The relayer writes to the same database after the monitor started
+    let queue = &JobQueue::open(&path).unwrap();
+    send(queue, &[1; 32], &request(&pool, 5_000), "first");
+    queue.mark_confirmed(&[1; 32], "first").unwrap();
+
+    let spent_error = format!(
+        "Error processing Instruction 1: custom program error: {:#x}",
+        error_code("NullifierAlreadySpent").unwrap()
+    );
+    send(queue, &[2; 32], &request(&pool, 5_000), "second");
+    queue.mark_failed(&[2; 32], &spent_error).unwrap();
+
+    send(queue, &[3; 32], &request(&pool, 5_000), "pending");
+
+    monitor.poll().await.unwrap();
+    monitor.poll().await.unwrap();
+
+    let rendered = monitor.metrics().render().unwrap();
+    assert_eq!(metric(&rendered, r#"veil_relayer_withdrawals_total{outcome="confirmed"}"#), Some(1));
+    assert_eq!(metric(&rendered, r#"veil_relayer_withdrawals_total{outcome="failed"}"#), Some(1));
+    assert_eq!(metric(&rendered, "veil_relayer_withdraw_transactions_total"), Some(2));
+    assert_eq!(metric(&rendered, "veil_relayer_withdraw_latency_seconds_count"), Some(1));
+    assert_eq!(
+        metric(&rendered, r#"veil_relayer_withdraw_failures_total{reason="NullifierAlreadySpent"}"#),
+        Some(1)
+    );
+    assert_eq!(
+        metric(&rendered, &format!(r#"veil_relayer_fee_revenue_total{{pool="{pool}"}}"#)),
+        Some(5_000)
+    );
+
+    // A job finishing later is counted on the next poll
+    queue.mark_confirmed(&[3; 32], "pending").unwrap();
+    monitor.poll().await.unwrap();
+
+    let rendered = monitor.metrics().render().unwrap();
+    assert_eq!(metric(&rendered, r#"veil_relayer_withdrawals_total{outcome="confirmed"}"#), Some(2));
+    assert_eq!(
+        metric(&rendered, &format!(r#"veil_relayer_fee_revenue_total{{pool="{pool}"}}"#)),
+        Some(10_000)
+    );
+}
+
+#[tokio::test]
+async fn health_follows_payer_balance() {
+    let rpc = rpc(MIN_BALANCE - 1);
+    let monitor = Monitor::new(
+        rpc.clone(),
+        JobQueue::open_in_memory().unwrap(),
+        Pubkey::new_unique(),
+        MIN_BALANCE,
+    )
+    .unwrap();
+
+    // Unknown until the first poll
+    assert!(!monitor.health().healthy);
+
+    monitor.poll().await.unwrap();
+    let health = monitor.health();
+    assert!(!health.healthy);
+    assert_eq!(health.payer_balance, Some(MIN_BALANCE - 1));
+    assert!(health.rpc_up);
+
+    rpc.balance.store(MIN_BALANCE, Ordering::SeqCst);
+    monitor.poll().await.unwrap();
+    assert!(monitor.health().healthy);
+
+    // An unreachable node keeps the last known balance but shows as down
+    rpc.healthy.store(false, Ordering::SeqCst);
+    monitor.poll().await.unwrap();
+    let health = monitor.health();
+    assert!(health.healthy);
+    assert!(!health.rpc_up);
+
+    let rendered = monitor.metrics().render().unwrap();
+    assert_eq!(metric(&rendered, "veil_relayer_rpc_up"), Some(0));
+    assert_eq!(metric(&rendered, "veil_relayer_rpc_errors_total"), Some(2));
+    assert_eq!(metric(&rendered, "veil_relayer_payer_balance_lamports"), Some(MIN_BALANCE));
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+use crate::job::JobState;
+
+#[derive(Debug, Error)]
+pub enum QueueError {
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Unknown job: {0}")]
+    UnknownJob(String),
+
+    #[error("Job {id} cannot move from {from} to {to}")]
+    InvalidTransition { id: String, from: JobState, to: JobState },
+}
+
+pub type Result<T> = std::result::Result<T, QueueError>;
@@ -0,0 +1,127 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+/// Lifecycle of a withdrawal job:
+///
+/// ```text
+/// received -> verified -> submitted -> confirmed
+///     |           |           |
+///     +-----------+-----------+-----> failed
+/// ```
+///
+/// `submitted` repeats for every retry. A failed job goes back to `received` when
+/// the client submits it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// Accepted from a client, not yet checked
+    Received,
+
+    /// Pre-verified and ready to send
+    Verified,
+
+    /// At least one transaction recorded for sending, outcome not yet known
+    Submitted,
+
+    Confirmed,
+
+    Failed,
+}
+
+impl JobState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobState::Received => "received",
+            JobState::Verified => "verified",
+            JobState::Submitted => "submitted",
+            JobState::Confirmed => "confirmed",
+            JobState::Failed => "failed",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, JobState::Confirmed | JobState::Failed)
+    }
+
+    /// Whether a job may move from `self` to `to`
+    pub fn can_transition_to(&self, to: JobState) -> bool {
+        use JobState::*;
+
+        matches!(
+            (self, to),
+            (Received, Verified)
+                | (Verified | Submitted, Submitted)
+                | (Verified | Submitted, Confirmed)
+                | (Received | Verified | Submitted, Failed)
+                | (Failed, Received)
+        )
+    }
+}
+
+impl fmt::Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for JobState {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "received" => Ok(JobState::Received),
+            "verified" => Ok(JobState::Verified),
+            "submitted" => Ok(JobState::Submitted),
+            "confirmed" => Ok(JobState::Confirmed),
+            "failed" => Ok(JobState::Failed),
+            other => Err(format!("unknown job state {other}")),
+        }
+    }
+}
+
+/// A withdrawal job, keyed by the withdrawal's nullifier hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    pub nullifier_hash: [u8; 32],
+
+    /// The relayer's serialized request; opaque to the queue
+    pub payload: Vec<u8>,
+
+    pub state: JobState,
+
+    /// Transactions sent or attempted so far
+    pub attempts: u32,
+
+    /// Signature of the confirmed transaction, or of the latest one sent
+    pub signature: Option<String>,
+
+    /// Why the last attempt or the job failed
+    pub last_error: Option<String>,
+
+    /// Unix timestamps
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Job {
+    /// Hex encoded nullifier hash, used as the job id
+    pub fn id(&self) -> String {
+        job_id(&self.nullifier_hash)
+    }
+}
+
+pub fn job_id(nullifier_hash: &[u8; 32]) -> String {
+    nullifier_hash.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// A transaction recorded for a job before it is sent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub signature: String,
+
+    /// Block height after which the transaction's blockhash expires and it can no
+    /// longer land
+    pub last_valid_block_height: u64,
+}
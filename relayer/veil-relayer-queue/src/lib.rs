@@ -0,0 +1,17 @@
+//! Durable job queue for SolanaVeil relayers.
+//!
+//! Jobs are keyed by the withdrawal's nullifier hash and stored in SQLite, so a
+//! relayer that crashes mid-submission resumes its jobs on restart instead of
+//! dropping or double-submitting them. [`drive`] retries a job with a fresh
+//! blockhash only after its earlier transactions have expired, and stops as soon as
+//! the nullifier is spent.
+
+mod error;
+mod job;
+mod queue;
+mod worker;
+
+pub use error::{QueueError, Result};
+pub use job::{job_id, Job, JobState, Submission};
+pub use queue::{JobQueue, Received};
+pub use worker::{drive, RetryPolicy, SubmissionStatus, Submitter};
@@ -0,0 +1,339 @@
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension, Row, TransactionBehavior};
+
+use crate::error::{QueueError, Result};
+use crate::job::{job_id, Job, JobState, Submission};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS jobs (
+        nullifier_hash BLOB PRIMARY KEY,
+        payload BLOB NOT NULL,
+        state TEXT NOT NULL,
+        attempts INTEGER NOT NULL DEFAULT 0,
+        signature TEXT,
+        last_error TEXT,
+        created_at INTEGER NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS submissions (
+        nullifier_hash BLOB NOT NULL REFERENCES jobs (nullifier_hash),
+        signature TEXT NOT NULL,
+        last_valid_block_height INTEGER NOT NULL,
+        submitted_at INTEGER NOT NULL,
+        PRIMARY KEY (nullifier_hash, signature)
+    );
+
+    CREATE INDEX IF NOT EXISTS jobs_by_state ON jobs (state);
+    CREATE INDEX IF NOT EXISTS jobs_by_update ON jobs (updated_at);
+";
+
+const JOB_COLUMNS: &str =
+    "nullifier_hash, payload, state, attempts, signature, last_error, created_at, updated_at";
+
+/// Outcome of [`JobQueue::receive`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Received {
+    /// The job is new, or a failed job was restarted
+    New(Job),
+
+    /// A job for this nullifier is already in progress or confirmed
+    Existing(Job),
+}
+
+impl Received {
+    pub fn job(&self) -> &Job {
+        match self {
+            Received::New(job) | Received::Existing(job) => job,
+        }
+    }
+}
+
+/// Durable job queue in a SQLite database.
+///
+/// There is at most one job per nullifier hash. Every state change is committed
+/// before the call returns, so a relayer that restarts picks up exactly where it
+/// stopped (see [`JobQueue::unfinished`]).
+pub struct JobQueue {
+    connection: Mutex<Connection>,
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() as i64)
+}
+
+fn job_from_row(row: &Row) -> rusqlite::Result<Job> {
+    let nullifier_hash: Vec<u8> = row.get(0)?;
+    let state: String = row.get(2)?;
+
+    let corrupt = |message: String| {
+        rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Blob, message.into())
+    };
+
+    Ok(Job {
+        nullifier_hash: nullifier_hash
+            .try_into()
+            .map_err(|_| corrupt("nullifier hash is not 32 bytes".into()))?,
+        payload: row.get(1)?,
+        state: state.parse().map_err(corrupt)?,
+        attempts: row.get(3)?,
+        signature: row.get(4)?,
+        last_error: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+    })
+}
+
+fn load(connection: &Connection, nullifier_hash: &[u8; 32]) -> Result<Option<Job>> {
+    connection
+        .query_row(
+            &format!("SELECT {JOB_COLUMNS} FROM jobs WHERE nullifier_hash = ?1"),
+            params![nullifier_hash.as_slice()],
+            job_from_row,
+        )
+        .optional()
+        .map_err(QueueError::from)
+}
+
+fn load_existing(connection: &Connection, nullifier_hash: &[u8; 32]) -> Result<Job> {
+    load(connection, nullifier_hash)?.ok_or_else(|| QueueError::UnknownJob(job_id(nullifier_hash)))
+}
+
+fn check_transition(job: &Job, to: JobState) -> Result<()> {
+    if !job.state.can_transition_to(to) {
+        return Err(QueueError::InvalidTransition {
+            id: job.id(),
+            from: job.state,
+            to,
+        });
+    }
+    Ok(())
+}
+
+impl JobQueue {
+    /// Open (or create) the queue database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = Connection::open(path)?;
+        // WAL keeps readers unblocked; FULL sync makes every commit survive a crash
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.pragma_update(None, "synchronous", "FULL")?;
+        Self::with_connection(connection)
+    }
+
+    /// Queue that lives only as long as the process, for tests
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self> {
+        connection.pragma_update(None, "foreign_keys", "ON")?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        // A panic while holding the lock cannot leave a half-applied change behind:
+        // every write is a single statement or a committed transaction
+        self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Record a job for `nullifier_hash`.
+    ///
+    /// If a job for the nullifier already exists and has not failed, it is returned
+    /// unchanged, so resubmitting a withdrawal never sends it twice. A failed job is
+    /// restarted with the new payload.
+    pub fn receive(&self, nullifier_hash: &[u8; 32], payload: &[u8]) -> Result<Received> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let timestamp = now();
+
+        let received = match load(&transaction, nullifier_hash)? {
+            Some(job) if job.state != JobState::Failed => Received::Existing(job),
+            Some(_) => {
+                transaction.execute(
+                    "UPDATE jobs SET payload = ?2, state = ?3, last_error = NULL, updated_at = ?4
+                     WHERE nullifier_hash = ?1",
+                    params![nullifier_hash.as_slice(), payload, JobState::Received.as_str(), timestamp],
+                )?;
+                Received::New(load_existing(&transaction, nullifier_hash)?)
+            }
+            None => {
+                transaction.execute(
+                    "INSERT INTO jobs (nullifier_hash, payload, state, created_at, updated_at)
+                     VALUES (?1, ?2, ?3, ?4, ?4)",
+                    params![nullifier_hash.as_slice(), payload, JobState::Received.as_str(), timestamp],
+                )?;
+                Received::New(load_existing(&transaction, nullifier_hash)?)
+            }
+        };
+
+        transaction.commit()?;
+        Ok(received)
+    }
+
+    pub fn get(&self, nullifier_hash: &[u8; 32]) -> Result<Option<Job>> {
+        load(&self.connection(), nullifier_hash)
+    }
+
+    /// Jobs that were not finished, oldest first, to resume after a restart
+    pub fn unfinished(&self) -> Result<Vec<Job>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs WHERE state IN (?1, ?2, ?3) ORDER BY created_at, rowid"
+        ))?;
+        let jobs = statement
+            .query_map(
+                params![
+                    JobState::Received.as_str(),
+                    JobState::Verified.as_str(),
+                    JobState::Submitted.as_str(),
+                ],
+                job_from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(jobs)
+    }
+
+    /// Confirmed and failed jobs last updated at or after `since`, oldest first, for
+    /// monitoring
+    pub fn finished_since(&self, since: i64) -> Result<Vec<Job>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(&format!(
+            "SELECT {JOB_COLUMNS} FROM jobs WHERE state IN (?1, ?2) AND updated_at >= ?3
+             ORDER BY updated_at, rowid"
+        ))?;
+        let jobs = statement
+            .query_map(
+                params![JobState::Confirmed.as_str(), JobState::Failed.as_str(), since],
+                job_from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(jobs)
+    }
+
+    /// Apply a state change after checking it is allowed
+    fn transition(
+        &self,
+        nullifier_hash: &[u8; 32],
+        to: JobState,
+        update: impl FnOnce(&rusqlite::Transaction, i64) -> rusqlite::Result<()>,
+    ) -> Result<Job> {
+        let mut connection = self.connection();
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let timestamp = now();
+
+        let job = load_existing(&transaction, nullifier_hash)?;
+        check_transition(&job, to)?;
+
+        transaction.execute(
+            "UPDATE jobs SET state = ?2, updated_at = ?3 WHERE nullifier_hash = ?1",
+            params![nullifier_hash.as_slice(), to.as_str(), timestamp],
+        )?;
+        update(&transaction, timestamp)?;
+
+        let job = load_existing(&transaction, nullifier_hash)?;
+        transaction.commit()?;
+        Ok(job)
+    }
+
+    pub fn mark_verified(&self, nullifier_hash: &[u8; 32]) -> Result<Job> {
+        self.transition(nullifier_hash, JobState::Verified, |_, _| Ok(()))
+    }
+
+    /// Record a transaction for the job. Must be called before sending it, so a
+    /// restarted relayer knows to look for it before sending another.
+    pub fn mark_submitted(&self, nullifier_hash: &[u8; 32], submission: &Submission) -> Result<Job> {
+        self.transition(nullifier_hash, JobState::Submitted, |transaction, timestamp| {
+            transaction.execute(
+                "INSERT OR REPLACE INTO submissions
+                     (nullifier_hash, signature, last_valid_block_height, submitted_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    nullifier_hash.as_slice(),
+                    submission.signature,
+                    submission.last_valid_block_height,
+                    timestamp,
+                ],
+            )?;
+            transaction.execute(
+                "UPDATE jobs SET attempts = attempts + 1, signature = ?2, last_error = NULL
+                 WHERE nullifier_hash = ?1",
+                params![nullifier_hash.as_slice(), submission.signature],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Count an attempt that failed before a transaction was sent, keeping the state
+    pub fn record_attempt_error(&self, nullifier_hash: &[u8; 32], error: &str) -> Result<Job> {
+        let connection = self.connection();
+        let updated = connection.execute(
+            "UPDATE jobs SET attempts = attempts + 1, last_error = ?2, updated_at = ?3
+             WHERE nullifier_hash = ?1",
+            params![nullifier_hash.as_slice(), error, now()],
+        )?;
+        if updated == 0 {
+            return Err(QueueError::UnknownJob(job_id(nullifier_hash)));
+        }
+        load_existing(&connection, nullifier_hash)
+    }
+
+    /// Note that sending the latest recorded transaction failed, keeping the state.
+    /// The attempt was already counted when the transaction was recorded.
+    pub fn record_send_error(&self, nullifier_hash: &[u8; 32], error: &str) -> Result<Job> {
+        let connection = self.connection();
+        let updated = connection.execute(
+            "UPDATE jobs SET last_error = ?2, updated_at = ?3 WHERE nullifier_hash = ?1",
+            params![nullifier_hash.as_slice(), error, now()],
+        )?;
+        if updated == 0 {
+            return Err(QueueError::UnknownJob(job_id(nullifier_hash)));
+        }
+        load_existing(&connection, nullifier_hash)
+    }
+
+    pub fn mark_confirmed(&self, nullifier_hash: &[u8; 32], signature: &str) -> Result<Job> {
+        self.transition(nullifier_hash, JobState::Confirmed, |transaction, _| {
+            transaction.execute(
+                "UPDATE jobs SET signature = ?2, last_error = NULL WHERE nullifier_hash = ?1",
+                params![nullifier_hash.as_slice(), signature],
+            )?;
+            Ok(())
+        })
+    }
+
+    pub fn mark_failed(&self, nullifier_hash: &[u8; 32], reason: &str) -> Result<Job> {
+        self.transition(nullifier_hash, JobState::Failed, |transaction, _| {
+            transaction.execute(
+                "UPDATE jobs SET last_error = ?2 WHERE nullifier_hash = ?1",
+                params![nullifier_hash.as_slice(), reason],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Transactions recorded for the job, oldest first
+    pub fn submissions(&self, nullifier_hash: &[u8; 32]) -> Result<Vec<Submission>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT signature, last_valid_block_height FROM submissions
+             WHERE nullifier_hash = ?1 ORDER BY submitted_at, rowid",
+        )?;
+        let submissions = statement
+            .query_map(params![nullifier_hash.as_slice()], |row| {
+                Ok(Submission {
+                    signature: row.get(0)?,
+                    last_valid_block_height: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(submissions)
+    }
+}
@@ -0,0 +1,177 @@
+//! Driving a verified job to a final state.
+//!
+//! The rule that keeps retries safe: a new transaction is only sent once every
+//! earlier one can no longer land (its blockhash expired) and the nullifier is still
+//! unspent. Every transaction is recorded before it is sent, so this holds across
+//! crashes too. If the nullifier turns out to be spent, the job is confirmed when one of
+//! its own transactions spent it and failed otherwise. It is never sent again.
+
+use std::fmt::Display;
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::error::{QueueError, Result};
+use crate::job::{job_id, Job, JobState, Submission};
+use crate::queue::JobQueue;
+
+/// What became of a sent transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    /// Not landed yet and may still land
+    Pending,
+
+    Confirmed,
+
+    /// Landed and failed; resending the same job would fail the same way
+    Failed(String),
+
+    /// The blockhash expired without the transaction landing; it never will
+    Expired,
+}
+
+/// The chain side of job processing, implemented by the relayer
+#[async_trait]
+pub trait Submitter: Send + Sync {
+    type Error: Display + Send;
+
+    /// Signed transaction ready to send
+    type Transaction: Send + Sync;
+
+    /// Build the job's transaction with a fresh blockhash and sign it, without sending it
+    async fn prepare(&self, job: &Job) -> std::result::Result<(Submission, Self::Transaction), Self::Error>;
+
+    /// Send a transaction from [`Submitter::prepare`]. It may land even when this errors.
+    async fn send(&self, transaction: &Self::Transaction) -> std::result::Result<(), Self::Error>;
+
+    async fn status(&self, submission: &Submission) -> std::result::Result<SubmissionStatus, Self::Error>;
+
+    /// Whether the job's nullifier has been spent on-chain
+    async fn is_nullifier_spent(&self, job: &Job) -> std::result::Result<bool, Self::Error>;
+}
+
+/// How persistently a job is retried
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Most transactions (or failed send attempts) per job
+    pub max_attempts: u32,
+
+    /// Delay between status checks of a sent transaction
+    pub poll_interval: Duration,
+
+    /// Delay before retrying after a send or RPC error
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            poll_interval: Duration::from_millis(500),
+            backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Wait until `submission` lands or expires. RPC errors are retried: giving up on
+/// a transaction that may still land would make the next attempt unsafe.
+async fn settle<S: Submitter>(submitter: &S, submission: &Submission, policy: &RetryPolicy) -> SubmissionStatus {
+    loop {
+        match submitter.status(submission).await {
+            Ok(SubmissionStatus::Pending) => tokio::time::sleep(policy.poll_interval).await,
+            Ok(status) => return status,
+            Err(error) => {
+                tracing::debug!(signature = %submission.signature, %error, "status check failed");
+                tokio::time::sleep(policy.backoff).await;
+            }
+        }
+    }
+}
+
+/// Retry `operation` until the submitter stops erroring
+async fn until_ok<T, E: Display, F, Fut>(policy: &RetryPolicy, mut operation: F) -> T
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<T, E>>,
+{
+    loop {
+        match operation().await {
+            Ok(value) => return value,
+            Err(error) => {
+                tracing::debug!(%error, "submitter call failed");
+                tokio::time::sleep(policy.backoff).await;
+            }
+        }
+    }
+}
+
+/// Process the job for `nullifier_hash` until it is confirmed or failed.
+///
+/// Picks up wherever the job is, so it also resumes jobs left unfinished by a
+/// crash. Jobs still in `received` must be verified by the caller first and are
+/// returned unchanged.
+pub async fn drive<S: Submitter>(
+    queue: &JobQueue,
+    submitter: &S,
+    nullifier_hash: &[u8; 32],
+    policy: &RetryPolicy,
+) -> Result<Job> {
+    loop {
+        let job = queue
+            .get(nullifier_hash)?
+            .ok_or_else(|| QueueError::UnknownJob(job_id(nullifier_hash)))?;
+        if job.state.is_terminal() || job.state == JobState::Received {
+            return Ok(job);
+        }
+
+        // Let the latest transaction play out before considering another
+        if let Some(latest) = queue.submissions(nullifier_hash)?.pop() {
+            match settle(submitter, &latest, policy).await {
+                SubmissionStatus::Confirmed => return queue.mark_confirmed(nullifier_hash, &latest.signature),
+                SubmissionStatus::Failed(reason) => return queue.mark_failed(nullifier_hash, &reason),
+                SubmissionStatus::Pending | SubmissionStatus::Expired => {}
+            }
+        }
+
+        // Every earlier transaction has expired by now, but one may have landed
+        // unnoticed (e.g. before a crash). A spent nullifier ends the job.
+        if until_ok(policy, || submitter.is_nullifier_spent(&job)).await {
+            for submission in queue.submissions(nullifier_hash)? {
+                let status = until_ok(policy, || submitter.status(&submission)).await;
+                if status == SubmissionStatus::Confirmed {
+                    return queue.mark_confirmed(nullifier_hash, &submission.signature);
+                }
+            }
+            return queue.mark_failed(nullifier_hash, "nullifier was spent by another transaction");
+        }
+
+        if job.attempts >= policy.max_attempts {
+            let reason = match &job.last_error {
+                Some(error) => format!("gave up after {} attempts: {error}", job.attempts),
+                None => format!("gave up after {} attempts", job.attempts),
+            };
+            return queue.mark_failed(nullifier_hash, &reason);
+        }
+
+        match submitter.prepare(&job).await {
+            Ok((submission, transaction)) => {
+                // Record the signature first: a relayer that crashes mid-send then waits
+                // on this transaction after restarting instead of sending another
+                queue.mark_submitted(nullifier_hash, &submission)?;
+                tracing::info!(job = %job.id(), signature = %submission.signature, attempt = job.attempts + 1, "sending");
+
+                // The transaction may have reached the network anyway, so it is
+                // settled like any other before the next attempt
+                if let Err(error) = submitter.send(&transaction).await {
+                    tracing::warn!(job = %job.id(), signature = %submission.signature, %error, "send failed");
+                    queue.record_send_error(nullifier_hash, &error.to_string())?;
+                }
+            }
+            Err(error) => {
+                tracing::warn!(job = %job.id(), %error, "could not prepare transaction");
+                queue.record_attempt_error(nullifier_hash, &error.to_string())?;
+                tokio::time::sleep(policy.backoff).await;
+            }
+        }
+    }
+}
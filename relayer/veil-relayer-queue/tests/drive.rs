@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use veil_relayer_queue::{drive, Job, JobQueue, JobState, RetryPolicy, Submission, SubmissionStatus, Submitter};
+
+const NULLIFIER: [u8; 32] = [1; 32];
+
+/// Submitter whose transactions play out as scripted. Prepared transactions that
+/// are never sent expire.
+#[derive(Default)]
+struct MockSubmitter {
+    /// Status each sent transaction settles to, in send order; `Expired` once exhausted
+    outcomes: Mutex<Vec<SubmissionStatus>>,
+
+    /// Errors returned by the next `prepare` calls
+    prepare_errors: Mutex<u32>,
+
+    /// Next `send` calls that reach the network but still return an error
+    lost_acks: Mutex<u32>,
+
+    nullifier_spent: Mutex<bool>,
+
+    statuses: Mutex<HashMap<String, SubmissionStatus>>,
+    prepared: Mutex<u32>,
+    sent: Mutex<u32>,
+}
+
+impl MockSubmitter {
+    fn with_outcomes(outcomes: Vec<SubmissionStatus>) -> Self {
+        let mut outcomes = outcomes;
+        outcomes.reverse();
+        Self {
+            outcomes: Mutex::new(outcomes),
+            ..Self::default()
+        }
+    }
+
+    fn sent(&self) -> u32 {
+        *self.sent.lock().unwrap()
+    }
+}
+
+#[async_trait]
+impl Submitter for MockSubmitter {
+    type Error = String;
+    type Transaction = String;
+
+    async fn prepare(&self, _job: &Job) -> Result<(Submission, String), String> {
+        let mut prepare_errors = self.prepare_errors.lock().unwrap();
+        if *prepare_errors > 0 {
+            *prepare_errors -= 1;
+            return Err("node unavailable".into());
+        }
+
+        let mut prepared = self.prepared.lock().unwrap();
+        *prepared += 1;
+        let signature = format!("sig-{prepared}");
+        let submission = Submission {
+            signature: signature.clone(),
+            last_valid_block_height: 100 + *prepared as u64,
+        };
+        Ok((submission, signature))
+    }
+
+    async fn send(&self, signature: &String) -> Result<(), String> {
+        *self.sent.lock().unwrap() += 1;
+        let outcome = self.outcomes.lock().unwrap().pop().unwrap_or(SubmissionStatus::Expired);
+        if outcome == SubmissionStatus::Confirmed {
+            *self.nullifier_spent.lock().unwrap() = true;
+        }
+        self.statuses.lock().unwrap().insert(signature.clone(), outcome);
+
+        let mut lost_acks = self.lost_acks.lock().unwrap();
+        if *lost_acks > 0 {
+            *lost_acks -= 1;
+            return Err("request timed out".into());
+        }
+        Ok(())
+    }
+
+    async fn status(&self, submission: &Submission) -> Result<SubmissionStatus, String> {
+        Ok(self
+            .statuses
+            .lock()
+            .unwrap()
+            .get(&submission.signature)
+            .cloned()
+            .unwrap_or(SubmissionStatus::Expired))
+    }
+
+    async fn is_nullifier_spent(&self, _job: &Job) -> Result<bool, String> {
+        Ok(*self.nullifier_spent.lock().unwrap())
+    }
+}
+
+fn policy() -> RetryPolicy {
+    RetryPolicy {
+        max_attempts: 3,
+        poll_interval: Duration::from_millis(1),
+        backoff: Duration::from_millis(1),
+    }
+}
+
+fn verified_queue() -> JobQueue {
+    let queue = JobQueue::open_in_memory().unwrap();
+    queue.receive(&NULLIFIER, b"request").unwrap();
+    queue.mark_verified(&NULLIFIER).unwrap();
+    queue
+}
+
+#[tokio::test]
+async fn resends_with_a_fresh_blockhash_after_expiry() {
+    let queue = verified_queue();
+    let submitter = MockSubmitter::with_outcomes(vec![SubmissionStatus::Expired, SubmissionStatus::Confirmed]);
+
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Confirmed);
+    assert_eq!(job.signature.as_deref(), Some("sig-2"));
+    assert_eq!(job.attempts, 2);
+    assert_eq!(submitter.sent(), 2);
+}
+
+#[tokio::test]
+async fn does_not_resend_failed_transactions() {
+    let queue = verified_queue();
+    let submitter = MockSubmitter::with_outcomes(vec![SubmissionStatus::Failed("custom program error".into())]);
+
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Failed);
+    assert_eq!(job.last_error.as_deref(), Some("custom program error"));
+    assert_eq!(submitter.sent(), 1);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_attempts() {
+    let queue = verified_queue();
+    let submitter = MockSubmitter::default();
+    *submitter.prepare_errors.lock().unwrap() = 1;
+
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Failed);
+    assert_eq!(job.attempts, 3);
+    assert_eq!(submitter.sent(), 2);
+}
+
+#[tokio::test]
+async fn never_sends_when_the_nullifier_is_spent_elsewhere() {
+    let queue = verified_queue();
+    let submitter = MockSubmitter::default();
+    *submitter.nullifier_spent.lock().unwrap() = true;
+
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Failed);
+    assert_eq!(job.last_error.as_deref(), Some("nullifier was spent by another transaction"));
+    assert_eq!(submitter.sent(), 0);
+}
+
+#[tokio::test]
+async fn resumes_a_job_whose_transaction_landed_before_a_crash() {
+    let queue = verified_queue();
+    let submitter = MockSubmitter::with_outcomes(vec![SubmissionStatus::Confirmed]);
+
+    // The relayer sent a transaction and crashed before seeing it confirm
+    let job = queue.get(&NULLIFIER).unwrap().unwrap();
+    let (submission, transaction) = submitter.prepare(&job).await.unwrap();
+    queue.mark_submitted(&NULLIFIER, &submission).unwrap();
+    submitter.send(&transaction).await.unwrap();
+
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Confirmed);
+    assert_eq!(job.signature.as_deref(), Some("sig-1"));
+    assert_eq!(submitter.sent(), 1);
+}
+
+#[tokio::test]
+async fn resumes_a_job_that_crashed_between_recording_and_sending() {
+    let queue = verified_queue();
+    let submitter = MockSubmitter::with_outcomes(vec![SubmissionStatus::Confirmed]);
+
+    // The relayer recorded a transaction and crashed before sending it
+    let job = queue.get(&NULLIFIER).unwrap().unwrap();
+    let (submission, _transaction) = submitter.prepare(&job).await.unwrap();
+    queue.mark_submitted(&NULLIFIER, &submission).unwrap();
+
+    // The recorded transaction is waited out before a fresh one is sent
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Confirmed);
+    assert_eq!(job.signature.as_deref(), Some("sig-2"));
+    assert_eq!(job.attempts, 2);
+    assert_eq!(submitter.sent(), 1);
+
+    let signatures: Vec<_> = queue
+        .submissions(&NULLIFIER)
+        .unwrap()
+        .into_iter()
+        .map(|submission| submission.signature)
+        .collect();
+    assert_eq!(signatures, ["sig-1", "sig-2"]);
+}
+
+#[tokio::test]
+async fn does_not_resend_when_a_failed_send_landed() {
+    let queue = verified_queue();
+    let submitter = MockSubmitter::with_outcomes(vec![SubmissionStatus::Confirmed]);
+    *submitter.lost_acks.lock().unwrap() = 1;
+
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Confirmed);
+    assert_eq!(job.signature.as_deref(), Some("sig-1"));
+    assert_eq!(job.attempts, 1);
+    assert_eq!(submitter.sent(), 1);
+}
+
+#[tokio::test]
+async fn leaves_unverified_jobs_alone() {
+    let queue = JobQueue::open_in_memory().unwrap();
+    queue.receive(&NULLIFIER, b"request").unwrap();
+    let submitter = MockSubmitter::default();
+
+    let job = drive(&queue, &submitter, &NULLIFIER, &policy()).await.unwrap();
+    assert_eq!(job.state, JobState::Received);
+    assert_eq!(submitter.sent(), 0);
+}
@@ -0,0 +1,117 @@
+use veil_relayer_queue::{JobQueue, JobState, QueueError, Received, Submission};
+
+const NULLIFIER: [u8; 32] = [1; 32];
+
+fn submission(signature: &str) -> Submission {
+    Submission {
+        signature: signature.to_string(),
+        last_valid_block_height: 100,
+    }
+}
+
+#[test]
+fn one_job_per_nullifier() {
+    let queue = JobQueue::open_in_memory().unwrap();
+
+    let first = queue.receive(&NULLIFIER, b"request").unwrap();
+    assert!(matches!(first, Received::New(_)));
+    assert_eq!(first.job().state, JobState::Received);
+
+    let second = queue.receive(&NULLIFIER, b"other request").unwrap();
+    assert!(matches!(second, Received::Existing(_)));
+    assert_eq!(second.job().payload, b"request");
+}
+
+#[test]
+fn failed_jobs_restart_on_resubmission() {
+    let queue = JobQueue::open_in_memory().unwrap();
+    queue.receive(&NULLIFIER, b"request").unwrap();
+    queue.mark_failed(&NULLIFIER, "fee too low").unwrap();
+
+    let restarted = queue.receive(&NULLIFIER, b"better request").unwrap();
+    assert!(matches!(restarted, Received::New(_)));
+    assert_eq!(restarted.job().state, JobState::Received);
+    assert_eq!(restarted.job().payload, b"better request");
+    assert_eq!(restarted.job().last_error, None);
+}
+
+#[test]
+fn tracks_submissions() {
+    let queue = JobQueue::open_in_memory().unwrap();
+    queue.receive(&NULLIFIER, b"request").unwrap();
+    queue.mark_verified(&NULLIFIER).unwrap();
+
+    queue.mark_submitted(&NULLIFIER, &submission("first")).unwrap();
+    let job = queue.mark_submitted(&NULLIFIER, &submission("second")).unwrap();
+    assert_eq!(job.state, JobState::Submitted);
+    assert_eq!(job.attempts, 2);
+    assert_eq!(job.signature.as_deref(), Some("second"));
+    assert_eq!(
+        queue.submissions(&NULLIFIER).unwrap(),
+        vec![submission("first"), submission("second")]
+    );
+
+    let job = queue.mark_confirmed(&NULLIFIER, "first").unwrap();
+    assert_eq!(job.state, JobState::Confirmed);
+    assert_eq!(job.signature.as_deref(), Some("first"));
+}
+
+#[test]
+fn rejects_invalid_transitions() {
+    let queue = JobQueue::open_in_memory().unwrap();
+    queue.receive(&NULLIFIER, b"request").unwrap();
+
+    // Unverified jobs are never sent
+    let error = queue.mark_submitted(&NULLIFIER, &submission("sig")).unwrap_err();
+    assert!(matches!(
+        error,
+        QueueError::InvalidTransition { from: JobState::Received, to: JobState::Submitted, .. }
+    ));
+
+    queue.mark_verified(&NULLIFIER).unwrap();
+    queue.mark_confirmed(&NULLIFIER, "sig").unwrap();
+    assert!(queue.mark_failed(&NULLIFIER, "late failure").is_err());
+    assert!(queue.mark_verified(&[2; 32]).is_err());
+}
+
+#[test]
+fn survives_reopening() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("jobs.sqlite");
+
+    {
+        let queue = JobQueue::open(&path).unwrap();
+        queue.receive(&NULLIFIER, b"request").unwrap();
+        queue.mark_verified(&NULLIFIER).unwrap();
+        queue.mark_submitted(&NULLIFIER, &submission("sig")).unwrap();
+
+        queue.receive(&[2; 32], b"done").unwrap();
+        queue.mark_failed(&[2; 32], "stale root").unwrap();
+    }
+
+    let queue = JobQueue::open(&path).unwrap();
+    let unfinished = queue.unfinished().unwrap();
+    assert_eq!(unfinished.len(), 1);
+    assert_eq!(unfinished[0].nullifier_hash, NULLIFIER);
+    assert_eq!(unfinished[0].state, JobState::Submitted);
+    assert_eq!(queue.submissions(&NULLIFIER).unwrap(), vec![submission("sig")]);
+}
+
+#[test]
+fn lists_finished_jobs() {
+    let queue = JobQueue::open_in_memory().unwrap();
+    queue.receive(&NULLIFIER, b"request").unwrap();
+    queue.mark_verified(&NULLIFIER).unwrap();
+    queue.mark_submitted(&NULLIFIER, &submission("sig")).unwrap();
+    queue.mark_confirmed(&NULLIFIER, "sig").unwrap();
+
+    queue.receive(&[2; 32], b"rejected").unwrap();
+    queue.mark_failed(&[2; 32], "stale root").unwrap();
+    queue.receive(&[3; 32], b"pending").unwrap();
+
+    let finished = queue.finished_since(0).unwrap();
+    let states: Vec<_> = finished.iter().map(|job| (job.nullifier_hash, job.state)).collect();
+    assert_eq!(states, vec![(NULLIFIER, JobState::Confirmed), ([2; 32], JobState::Failed)]);
+
+    assert!(queue.finished_since(i64::MAX).unwrap().is_empty());
+}
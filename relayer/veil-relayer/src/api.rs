@@ -0,0 +1,67 @@
+//! HTTP API.
+//!
+//! - `GET /v1/status`: the relayer's key, fees and on-chain standing
+//! - `POST /v1/withdraw`: submit a [`WithdrawRequest`], returns its job id
+//! - `GET /v1/withdraw/:id`: status of a withdrawal job
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::json;
+
+use crate::error::RelayerError;
+use crate::request::WithdrawRequest;
+use crate::service::{JobView, RelayerService, RelayerStatus};
+
+pub fn router(service: Arc<RelayerService>) -> Router {
+    Router::new()
+        .route("/v1/status", get(status))
+        .route("/v1/withdraw", post(submit_withdraw))
+        .route("/v1/withdraw/:id", get(withdraw_status))
+        .with_state(service)
+}
+
+async fn status(State(service): State<Arc<RelayerService>>) -> Result<Json<RelayerStatus>, RelayerError> {
+    service.relayer_status().await.map(Json)
+}
+
+async fn submit_withdraw(
+    State(service): State<Arc<RelayerService>>,
+    Json(request): Json<WithdrawRequest>,
+) -> Result<impl IntoResponse, RelayerError> {
+    let id = service.submit(request).await?;
+    Ok((StatusCode::ACCEPTED, Json(json!({ "id": id }))))
+}
+
+async fn withdraw_status(
+    State(service): State<Arc<RelayerService>>,
+    Path(id): Path<String>,
+) -> Result<Json<JobView>, RelayerError> {
+    service.job_status(&id).await.map(Json)
+}
+
+impl IntoResponse for RelayerError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            RelayerError::InvalidRequest(_)
+            | RelayerError::StaleRoot
+            | RelayerError::FeeTooLow { .. }
+            | RelayerError::FeeTooHigh { .. }
+            | RelayerError::InvalidProof => StatusCode::UNPROCESSABLE_ENTITY,
+            RelayerError::NullifierSpent => StatusCode::CONFLICT,
+            RelayerError::UnknownPool(_) | RelayerError::UnknownJob(_) => StatusCode::NOT_FOUND,
+            RelayerError::WithdrawalsPaused(_)
+            | RelayerError::MissingVerifyingKey(_)
+            | RelayerError::RelayerInactive
+            | RelayerError::Misconfigured(_) => StatusCode::SERVICE_UNAVAILABLE,
+            RelayerError::Rpc(_) => StatusCode::BAD_GATEWAY,
+            RelayerError::Queue(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
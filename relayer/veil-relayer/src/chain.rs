@@ -0,0 +1,107 @@
+//! Access to the cluster the relayer submits to.
+//!
+//! Everything the relayer needs from a node goes through [`Chain`], so the service
+//! runs unchanged against an RPC node or an in-process `solana-program-test` bank.
+
+use anchor_lang::AccountDeserialize;
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+use crate::error::{RelayerError, Result};
+
+#[async_trait]
+pub trait Chain: Send + Sync {
+    async fn get_account(&self, address: &Pubkey) -> Result<Option<Account>>;
+
+    async fn get_slot(&self) -> Result<u64>;
+
+    /// Latest blockhash and the last block height at which it is valid
+    async fn get_latest_blockhash(&self) -> Result<(Hash, u64)>;
+
+    async fn get_block_height(&self) -> Result<u64>;
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature>;
+
+    /// `None` while the transaction is not yet confirmed, otherwise its outcome
+    async fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<std::result::Result<(), TransactionError>>>;
+}
+
+/// Fetch and deserialize an Anchor account, `None` if it does not exist
+pub async fn fetch<T: AccountDeserialize>(chain: &dyn Chain, address: &Pubkey) -> Result<Option<T>> {
+    let Some(account) = chain.get_account(address).await? else {
+        return Ok(None);
+    };
+    if account.owner != solana_veil::ID {
+        return Ok(None);
+    }
+
+    T::try_deserialize(&mut account.data.as_slice())
+        .map(Some)
+        .map_err(|error| RelayerError::Rpc(format!("cannot decode account {address}: {error}")))
+}
+
+/// [`Chain`] backed by a JSON-RPC node
+pub struct RpcChain {
+    client: RpcClient,
+}
+
+impl RpcChain {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+        }
+    }
+}
+
+fn rpc_error(error: impl std::fmt::Display) -> RelayerError {
+    RelayerError::Rpc(error.to_string())
+}
+
+#[async_trait]
+impl Chain for RpcChain {
+    async fn get_account(&self, address: &Pubkey) -> Result<Option<Account>> {
+        self.client
+            .get_account_with_commitment(address, self.client.commitment())
+            .await
+            .map(|response| response.value)
+            .map_err(rpc_error)
+    }
+
+    async fn get_slot(&self) -> Result<u64> {
+        self.client.get_slot().await.map_err(rpc_error)
+    }
+
+    async fn get_latest_blockhash(&self) -> Result<(Hash, u64)> {
+        self.client
+            .get_latest_blockhash_with_commitment(self.client.commitment())
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn get_block_height(&self) -> Result<u64> {
+        self.client.get_block_height().await.map_err(rpc_error)
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        self.client.send_transaction(transaction).await.map_err(rpc_error)
+    }
+
+    async fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<std::result::Result<(), TransactionError>>> {
+        self.client
+            .get_signature_status_with_commitment(signature, self.client.commitment())
+            .await
+            .map_err(rpc_error)
+    }
+}
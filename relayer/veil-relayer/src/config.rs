@@ -0,0 +1,53 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use clap::Parser;
+use veil_relayer_queue::RetryPolicy;
+
+use crate::service::ServiceSettings;
+
+/// Reference relayer for SolanaVeil withdrawals
+#[derive(Debug, Parser)]
+#[command(name = "veil-relayer", version)]
+pub struct Config {
+    /// RPC endpoint of the cluster to relay on
+    #[arg(long, env = "VEIL_RELAYER_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    pub rpc_url: String,
+
+    /// Relayer keypair; pays for transactions, receives fees and signs quotes
+    #[arg(long, env = "VEIL_RELAYER_KEYPAIR")]
+    pub keypair: PathBuf,
+
+    /// Address the HTTP API listens on
+    #[arg(long, env = "VEIL_RELAYER_LISTEN", default_value = "127.0.0.1:8080")]
+    pub listen: SocketAddr,
+
+    /// Slots a signed fee quote stays valid for
+    #[arg(long, default_value_t = 150)]
+    pub quote_validity_slots: u64,
+
+    /// Slots during which only this relayer may submit its withdrawals (0 = none)
+    #[arg(long, default_value_t = 150)]
+    pub exclusivity_slots: u64,
+
+    /// SQLite database holding the job queue
+    #[arg(long, env = "VEIL_RELAYER_QUEUE", default_value = "relayer-jobs.sqlite")]
+    pub queue_path: PathBuf,
+
+    /// Most transactions sent per withdrawal before it is marked failed
+    #[arg(long, default_value_t = 5)]
+    pub max_attempts: u32,
+}
+
+impl Config {
+    pub fn service_settings(&self) -> ServiceSettings {
+        ServiceSettings {
+            quote_validity_slots: self.quote_validity_slots,
+            exclusivity_slots: self.exclusivity_slots,
+            retry: RetryPolicy {
+                max_attempts: self.max_attempts,
+                ..RetryPolicy::default()
+            },
+        }
+    }
+}
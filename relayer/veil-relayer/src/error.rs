@@ -0,0 +1,51 @@
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+use veil_relayer_queue::QueueError;
+
+/// Reasons the relayer refuses or fails a withdrawal
+#[derive(Debug, Error)]
+pub enum RelayerError {
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Unknown pool: {0}")]
+    UnknownPool(Pubkey),
+
+    #[error("Withdrawals are paused for pool {0}")]
+    WithdrawalsPaused(Pubkey),
+
+    #[error("Root is not the pool's current merkle root")]
+    StaleRoot,
+
+    #[error("Nullifier has already been spent")]
+    NullifierSpent,
+
+    #[error("Fee {offered} is below the relayer's fee of {required}")]
+    FeeTooLow { offered: u64, required: u64 },
+
+    #[error("Fee {offered} is above the pool's maximum fee of {max}")]
+    FeeTooHigh { offered: u64, max: u64 },
+
+    #[error("No withdraw verifying key is stored for pool {0}")]
+    MissingVerifyingKey(Pubkey),
+
+    #[error("Proof failed verification")]
+    InvalidProof,
+
+    #[error("Relayer is not active on-chain")]
+    RelayerInactive,
+
+    #[error("Relayer is misconfigured: {0}")]
+    Misconfigured(String),
+
+    #[error("Unknown job: {0}")]
+    UnknownJob(String),
+
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    #[error(transparent)]
+    Queue(#[from] QueueError),
+}
+
+pub type Result<T> = std::result::Result<T, RelayerError>;
@@ -0,0 +1,78 @@
+//! Fee checks against the relayer's on-chain `Relayer` and `RelayerMetadata` accounts
+
+use solana_sdk::pubkey::Pubkey;
+use solana_veil::instructions::config::calculate_protocol_fee;
+use solana_veil::state::{Config, Pool, Relayer, RelayerMetadata};
+
+use crate::chain::{fetch, Chain};
+use crate::error::{RelayerError, Result};
+use crate::pda;
+
+/// `basis_points` of `amount`, rounded down
+pub fn basis_points_of(amount: u64, basis_points: u16) -> u64 {
+    (amount as u128 * basis_points as u128 / 10_000) as u64
+}
+
+/// Fee the relayer charges for one withdrawal from `pool`.
+///
+/// A fee tier published for the pool's mint takes precedence over the relayer's
+/// flat `fee_basis_points`; a tier's `min_fee` covers the transaction costs.
+pub fn required_fee(pool: &Pool, relayer: &Relayer, metadata: Option<&RelayerMetadata>) -> u64 {
+    let tier = metadata.and_then(|metadata| {
+        metadata.fee_schedule.iter().find(|tier| tier.mint == pool.mint)
+    });
+
+    match tier {
+        Some(tier) => basis_points_of(pool.denomination, tier.fee_basis_points).max(tier.min_fee),
+        None => basis_points_of(pool.denomination, relayer.fee_basis_points),
+    }
+}
+
+/// Load the relayer's `Relayer` account and check it can take relayed withdrawals
+pub async fn load_relayer(chain: &dyn Chain, authority: &Pubkey) -> Result<Relayer> {
+    let relayer = fetch::<Relayer>(chain, &pda::relayer(authority))
+        .await?
+        .ok_or_else(|| RelayerError::Misconfigured("relayer is not registered".into()))?;
+
+    if !relayer.is_active {
+        return Err(RelayerError::RelayerInactive);
+    }
+    if relayer.quote_signer != *authority {
+        return Err(RelayerError::Misconfigured(
+            "on-chain quote signer is not the relayer key".into(),
+        ));
+    }
+
+    Ok(relayer)
+}
+
+/// Check `fee` against the relayer's published fees and the pool's limits, so a
+/// withdrawal the program would reject is never submitted
+pub async fn check_fee(chain: &dyn Chain, authority: &Pubkey, pool: &Pool, fee: u64) -> Result<()> {
+    let relayer = load_relayer(chain, authority).await?;
+    let metadata = fetch::<RelayerMetadata>(chain, &pda::relayer_metadata(authority)).await?;
+
+    let required = required_fee(pool, &relayer, metadata.as_ref());
+    if fee < required {
+        return Err(RelayerError::FeeTooLow { offered: fee, required });
+    }
+
+    let max = basis_points_of(pool.denomination, pool.max_fee_basis_points);
+    if fee > max {
+        return Err(RelayerError::FeeTooHigh { offered: fee, max });
+    }
+
+    let config = fetch::<Config>(chain, &pda::config())
+        .await?
+        .ok_or_else(|| RelayerError::Misconfigured("protocol config not found".into()))?;
+    let protocol_fee = calculate_protocol_fee(pool.denomination, config.protocol_fee_basis_points)
+        .map_err(|_| RelayerError::InvalidRequest("protocol fee overflows".into()))?;
+    let withdraw_amount = pool.denomination.saturating_sub(fee).saturating_sub(protocol_fee);
+    if withdraw_amount < pool.min_withdrawal_amount {
+        return Err(RelayerError::InvalidRequest(
+            "withdrawal amount after fees is below the pool minimum".into(),
+        ));
+    }
+
+    Ok(())
+}
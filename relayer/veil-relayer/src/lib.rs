@@ -0,0 +1,17 @@
+//! Reference relayer for SolanaVeil withdrawals.
+//!
+//! Clients post withdraw requests over HTTP. The relayer checks each one against
+//! chain state, verifies its proof off-chain with the program's `verifier` code,
+//! checks the fee against its on-chain `Relayer` account, then signs a fee quote and
+//! submits the `withdraw` transaction, paying for it out of the fee.
+
+pub mod api;
+pub mod chain;
+pub mod config;
+pub mod error;
+pub mod fees;
+pub mod pda;
+pub mod request;
+pub mod service;
+pub mod transaction;
+pub mod verify;
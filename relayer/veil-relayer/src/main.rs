@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use clap::Parser;
+use solana_sdk::signature::read_keypair_file;
+use tracing_subscriber::EnvFilter;
+
+use veil_relayer::api;
+use veil_relayer::chain::RpcChain;
+use veil_relayer::config::Config;
+use veil_relayer::service::RelayerService;
+use veil_relayer_queue::JobQueue;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let config = Config::parse();
+    let keypair = read_keypair_file(&config.keypair)
+        .map_err(|error| anyhow!("cannot read keypair {}: {error}", config.keypair.display()))?;
+
+    let queue = JobQueue::open(&config.queue_path)
+        .with_context(|| format!("cannot open job queue {}", config.queue_path.display()))?;
+
+    let chain = Arc::new(RpcChain::new(config.rpc_url.clone()));
+    let service = RelayerService::new(chain, keypair, queue, config.service_settings());
+
+    // Refuse to start with a relayer the program would not accept
+    let status = service
+        .relayer_status()
+        .await
+        .context("relayer is not ready on-chain")?;
+    tracing::info!(relayer = %service.relayer(), stake = status.stake_amount, "relayer ready");
+
+    let resumed = service.resume().await.context("cannot resume queued jobs")?;
+    if resumed > 0 {
+        tracing::info!(resumed, "resumed unfinished withdrawals");
+    }
+
+    tracing::info!(listen = %config.listen, "serving relayer API");
+    axum::Server::bind(&config.listen)
+        .serve(api::router(service).into_make_service())
+        .await
+        .context("HTTP server failed")?;
+
+    Ok(())
+}
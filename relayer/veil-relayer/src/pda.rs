@@ -0,0 +1,50 @@
+//! Program-derived addresses of the SolanaVeil accounts the relayer reads and writes
+
+use solana_sdk::pubkey::Pubkey;
+use solana_veil::state::VerifierCircuit;
+use solana_veil::vault::SOL_VAULT_SEED;
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &solana_veil::ID).0
+}
+
+pub fn config() -> Pubkey {
+    find(&[b"config"])
+}
+
+pub fn checkpoints(pool: &Pubkey) -> Pubkey {
+    find(&[b"checkpoints", pool.as_ref()])
+}
+
+pub fn sol_vault(pool: &Pubkey) -> Pubkey {
+    find(&[SOL_VAULT_SEED, pool.as_ref()])
+}
+
+pub fn treasury_vault(pool: &Pubkey) -> Pubkey {
+    find(&[b"treasury_vault", pool.as_ref()])
+}
+
+pub fn nullifier(nullifier_hash: &[u8; 32], pool: &Pubkey) -> Pubkey {
+    find(&[b"nullifier", nullifier_hash, pool.as_ref()])
+}
+
+/// The relayer's `Relayer` account
+pub fn relayer(authority: &Pubkey) -> Pubkey {
+    find(&[b"relayer", authority.as_ref()])
+}
+
+pub fn relayer_metadata(authority: &Pubkey) -> Pubkey {
+    find(&[b"relayer_metadata", authority.as_ref()])
+}
+
+pub fn quote_nonce(relayer: &Pubkey, nonce: u64) -> Pubkey {
+    find(&[b"quote_nonce", relayer.as_ref(), &nonce.to_le_bytes()])
+}
+
+pub fn relayer_mint_stats(relayer: &Pubkey, mint: &Pubkey) -> Pubkey {
+    find(&[b"relayer_mint_stats", relayer.as_ref(), mint.as_ref()])
+}
+
+pub fn verifying_key(pool: &Pubkey, circuit: VerifierCircuit) -> Pubkey {
+    find(&[b"verifying_key", pool.as_ref(), &[circuit as u8]])
+}
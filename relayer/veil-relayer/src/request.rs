@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use solana_sdk::pubkey::Pubkey;
+use solana_veil::callback::NO_CALLBACK_HASH;
+use solana_veil::verifier::{field_element_from_be_bytes, field_element_from_pubkey, field_element_from_u64};
+
+use crate::error::{RelayerError, Result};
+
+/// Withdrawal a client asks the relayer to submit.
+///
+/// Byte fields are hex encoded, keys base58. The relayer binds itself as the proof's
+/// relayer, so the proof must have been generated with the relayer's key.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WithdrawRequest {
+    #[serde_as(as = "DisplayFromStr")]
+    pub pool: Pubkey,
+
+    /// Compressed Groth16 proof
+    #[serde(with = "hex::serde")]
+    pub proof: Vec<u8>,
+
+    #[serde(with = "hex::serde")]
+    pub root: [u8; 32],
+
+    #[serde(with = "hex::serde")]
+    pub nullifier_hash: [u8; 32],
+
+    #[serde_as(as = "DisplayFromStr")]
+    pub recipient: Pubkey,
+
+    /// Fee the relayer takes from the denomination
+    pub fee: u64,
+
+    pub leaf_index_bound: u64,
+
+    /// Policy accounts for pools with a screening program
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default)]
+    pub screening_accounts: Vec<Pubkey>,
+}
+
+impl WithdrawRequest {
+    /// Job id of the request; one job per nullifier
+    pub fn job_id(&self) -> String {
+        hex::encode(self.nullifier_hash)
+    }
+}
+
+/// Public signals of `withdraw.circom`, in the order snarkjs lays them out:
+/// the `nullifierHash` output first, then the public inputs in declaration order.
+///
+/// Keys are mapped into the field by reducing their big-endian bytes modulo the
+/// scalar field, matching how the client SDK feeds them to the circuit.
+pub struct WithdrawPublicInputs {
+    pub nullifier_hash: [u8; 32],
+    pub root: [u8; 32],
+    pub pool: Pubkey,
+    pub recipient: Pubkey,
+    pub relayer: Pubkey,
+    pub fee: u64,
+    pub denomination: u64,
+    pub leaf_index_bound: u64,
+    pub callback_hash: [u8; 32],
+}
+
+impl WithdrawPublicInputs {
+    pub fn new(request: &WithdrawRequest, relayer: Pubkey, denomination: u64) -> Self {
+        Self {
+            nullifier_hash: request.nullifier_hash,
+            root: request.root,
+            pool: request.pool,
+            recipient: request.recipient,
+            relayer,
+            fee: request.fee,
+            denomination,
+            leaf_index_bound: request.leaf_index_bound,
+            callback_hash: NO_CALLBACK_HASH,
+        }
+    }
+
+    pub fn field_elements(&self) -> Result<Vec<[u8; 32]>> {
+        let canonical = |bytes: &[u8; 32], name: &str| {
+            field_element_from_be_bytes(bytes).map_err(|_| {
+                RelayerError::InvalidRequest(format!("{name} is not a canonical field element"))
+            })
+        };
+        Ok(vec![
+            canonical(&self.nullifier_hash, "nullifier_hash")?,
+            canonical(&self.root, "root")?,
+            field_element_from_pubkey(&self.pool),
+            field_element_from_pubkey(&self.recipient),
+            field_element_from_pubkey(&self.relayer),
+            field_element_from_u64(self.fee),
+            field_element_from_u64(self.denomination),
+            field_element_from_u64(self.leaf_index_bound),
+            canonical(&self.callback_hash, "callback_hash")?,
+        ])
+    }
+}
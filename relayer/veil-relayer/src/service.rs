@@ -0,0 +1,303 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_with::{serde_as, DisplayFromStr};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+use solana_veil::state::{Nullifier, Pool};
+use veil_relayer_queue::{
+    drive, Job, JobQueue, JobState, Received, RetryPolicy, Submission, SubmissionStatus, Submitter,
+};
+
+use crate::chain::{fetch, Chain};
+use crate::error::{RelayerError, Result};
+use crate::fees;
+use crate::pda;
+use crate::request::WithdrawRequest;
+use crate::transaction;
+use crate::verify;
+
+/// Tunables of the relayer service
+#[derive(Debug, Clone)]
+pub struct ServiceSettings {
+    /// Slots a signed quote stays valid for
+    pub quote_validity_slots: u64,
+
+    /// Slots during which only this relayer may submit its withdrawals (0 = none)
+    pub exclusivity_slots: u64,
+
+    /// How jobs are retried
+    pub retry: RetryPolicy,
+}
+
+impl Default for ServiceSettings {
+    fn default() -> Self {
+        Self {
+            quote_validity_slots: 150,
+            exclusivity_slots: 150,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// A withdrawal job as reported by the API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JobView {
+    pub id: String,
+    pub status: JobState,
+    pub attempts: u32,
+    pub signature: Option<String>,
+    pub error: Option<String>,
+}
+
+impl From<Job> for JobView {
+    fn from(job: Job) -> Self {
+        Self {
+            id: job.id(),
+            status: job.state,
+            attempts: job.attempts,
+            signature: job.signature,
+            error: job.last_error,
+        }
+    }
+}
+
+/// The relayer's on-chain standing, as reported by the status endpoint
+#[serde_as]
+#[derive(Debug, Clone, Serialize)]
+pub struct RelayerStatus {
+    #[serde_as(as = "DisplayFromStr")]
+    pub relayer: Pubkey,
+    pub is_active: bool,
+    pub fee_basis_points: u16,
+    pub stake_amount: u64,
+    pub total_relayed: u64,
+    pub total_fees: u64,
+    pub successful_withdrawals: u64,
+    pub last_active_slot: u64,
+    pub slash_count: u64,
+}
+
+/// Accepts withdraw requests, pre-verifies them and submits them on-chain.
+///
+/// Jobs live in a [`JobQueue`] keyed by nullifier, so a resubmitted withdrawal
+/// never goes out twice and [`RelayerService::resume`] picks up after a restart.
+pub struct RelayerService {
+    chain: Arc<dyn Chain>,
+    keypair: Keypair,
+    queue: JobQueue,
+    settings: ServiceSettings,
+}
+
+fn decode_request(job: &Job) -> Result<WithdrawRequest> {
+    serde_json::from_slice(&job.payload)
+        .map_err(|error| RelayerError::InvalidRequest(format!("stored request is unreadable: {error}")))
+}
+
+impl RelayerService {
+    pub fn new(
+        chain: Arc<dyn Chain>,
+        keypair: Keypair,
+        queue: JobQueue,
+        settings: ServiceSettings,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            chain,
+            keypair,
+            queue,
+            settings,
+        })
+    }
+
+    pub fn relayer(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    pub async fn relayer_status(&self) -> Result<RelayerStatus> {
+        let relayer = fees::load_relayer(self.chain.as_ref(), &self.relayer()).await?;
+
+        Ok(RelayerStatus {
+            relayer: self.relayer(),
+            is_active: relayer.is_active,
+            fee_basis_points: relayer.fee_basis_points,
+            stake_amount: relayer.stake_amount,
+            total_relayed: relayer.total_relayed,
+            total_fees: relayer.total_fees,
+            successful_withdrawals: relayer.successful_withdrawals,
+            last_active_slot: relayer.last_active_slot,
+            slash_count: relayer.slash_count,
+        })
+    }
+
+    pub async fn job_status(&self, id: &str) -> Result<JobView> {
+        let mut nullifier_hash = [0u8; 32];
+        hex::decode_to_slice(id, &mut nullifier_hash)
+            .map_err(|_| RelayerError::UnknownJob(id.to_string()))?;
+
+        self.queue
+            .get(&nullifier_hash)?
+            .map(JobView::from)
+            .ok_or_else(|| RelayerError::UnknownJob(id.to_string()))
+    }
+
+    /// Pre-verify `request` and queue it for submission, returning its job id.
+    ///
+    /// Resubmitting a withdrawal that is in flight or confirmed returns the existing
+    /// job; a failed one is verified and tried again.
+    pub async fn submit(self: &Arc<Self>, request: WithdrawRequest) -> Result<String> {
+        let payload = serde_json::to_vec(&request)
+            .map_err(|error| RelayerError::InvalidRequest(error.to_string()))?;
+
+        let job = match self.queue.receive(&request.nullifier_hash, &payload)? {
+            Received::Existing(job) => return Ok(job.id()),
+            Received::New(job) => job,
+        };
+
+        self.verify(&job, &request).await?;
+        self.spawn(job.nullifier_hash);
+
+        Ok(job.id())
+    }
+
+    /// Resume the jobs a previous run left unfinished; returns how many were resumed
+    pub async fn resume(self: &Arc<Self>) -> Result<usize> {
+        let jobs = self.queue.unfinished()?;
+        let mut resumed = 0;
+
+        for job in jobs {
+            // Jobs received but not verified before the restart are verified again
+            if job.state == JobState::Received {
+                let verified = match decode_request(&job) {
+                    Ok(request) => self.verify(&job, &request).await,
+                    Err(error) => {
+                        self.queue.mark_failed(&job.nullifier_hash, &error.to_string())?;
+                        Err(error)
+                    }
+                };
+                if let Err(error) = verified {
+                    tracing::warn!(job = %job.id(), %error, "dropping unverifiable job");
+                    continue;
+                }
+            }
+
+            self.spawn(job.nullifier_hash);
+            resumed += 1;
+        }
+
+        Ok(resumed)
+    }
+
+    /// Pre-verify a received job, recording the outcome in the queue
+    async fn verify(&self, job: &Job, request: &WithdrawRequest) -> Result<()> {
+        match verify::preverify(self.chain.as_ref(), &self.relayer(), request).await {
+            Ok(_) => {
+                self.queue.mark_verified(&job.nullifier_hash)?;
+                Ok(())
+            }
+            Err(error) => {
+                self.queue.mark_failed(&job.nullifier_hash, &error.to_string())?;
+                Err(error)
+            }
+        }
+    }
+
+    fn spawn(self: &Arc<Self>, nullifier_hash: [u8; 32]) {
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            match drive(&service.queue, service.as_ref(), &nullifier_hash, &service.settings.retry).await {
+                Ok(job) if job.state == JobState::Failed => {
+                    tracing::warn!(job = %job.id(), reason = ?job.last_error, "withdrawal failed")
+                }
+                Ok(job) => tracing::info!(job = %job.id(), status = %job.state, "withdrawal finished"),
+                Err(error) => tracing::error!(%error, "job queue error"),
+            }
+        });
+    }
+
+    /// Sign the quote and build the withdraw transaction with a fresh blockhash
+    async fn build(&self, request: &WithdrawRequest, pool: &Pool) -> Result<(Submission, Transaction)> {
+        let slot = self.chain.get_slot().await?;
+        let quote = transaction::quote(
+            request,
+            slot,
+            self.settings.quote_validity_slots,
+            self.settings.exclusivity_slots,
+        );
+
+        let token_program = if pool.is_spl_token {
+            let mint = self
+                .chain
+                .get_account(&pool.mint)
+                .await?
+                .ok_or_else(|| RelayerError::Misconfigured(format!("mint {} not found", pool.mint)))?;
+            Some(mint.owner)
+        } else {
+            None
+        };
+
+        let instructions = transaction::withdraw_instructions(&self.keypair, pool, request, &quote, token_program);
+        let (blockhash, last_valid_block_height) = self.chain.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.relayer()),
+            &[&self.keypair],
+            blockhash,
+        );
+
+        let submission = Submission {
+            signature: transaction.signatures[0].to_string(),
+            last_valid_block_height,
+        };
+        Ok((submission, transaction))
+    }
+}
+
+#[async_trait]
+impl Submitter for RelayerService {
+    type Error = RelayerError;
+    type Transaction = Transaction;
+
+    async fn prepare(&self, job: &Job) -> Result<(Submission, Transaction)> {
+        let request = decode_request(job)?;
+        let pool = fetch::<Pool>(self.chain.as_ref(), &request.pool)
+            .await?
+            .ok_or(RelayerError::UnknownPool(request.pool))?;
+
+        self.build(&request, &pool).await
+    }
+
+    async fn send(&self, transaction: &Transaction) -> Result<()> {
+        self.chain.send_transaction(transaction).await?;
+        Ok(())
+    }
+
+    async fn status(&self, submission: &Submission) -> Result<SubmissionStatus> {
+        let signature: Signature = submission
+            .signature
+            .parse()
+            .map_err(|_| RelayerError::Rpc(format!("bad signature {}", submission.signature)))?;
+
+        // Read the height first: a transaction missing after its blockhash expired
+        // can no longer land
+        let block_height = self.chain.get_block_height().await?;
+        Ok(match self.chain.get_signature_status(&signature).await? {
+            Some(Ok(())) => SubmissionStatus::Confirmed,
+            Some(Err(error)) => SubmissionStatus::Failed(error.to_string()),
+            None if block_height > submission.last_valid_block_height => SubmissionStatus::Expired,
+            None => SubmissionStatus::Pending,
+        })
+    }
+
+    async fn is_nullifier_spent(&self, job: &Job) -> Result<bool> {
+        let request = decode_request(job)?;
+        let nullifier = fetch::<Nullifier>(
+            self.chain.as_ref(),
+            &pda::nullifier(&request.nullifier_hash, &request.pool),
+        )
+        .await?;
+
+        Ok(nullifier.is_some_and(|nullifier| nullifier.is_spent))
+    }
+}
@@ -0,0 +1,171 @@
+//! Building the relayer's `withdraw` transaction
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_sdk::ed25519_program;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_program;
+use solana_sdk::sysvar;
+use solana_veil::instructions::WithdrawArgs;
+use solana_veil::quote::RelayerQuote;
+use solana_veil::screening;
+use solana_veil::state::{Pool, VerifierCircuit};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+use crate::pda;
+use crate::request::WithdrawRequest;
+
+/// Offsets value meaning "this instruction" in the ed25519 program's offsets
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Signature count, padding byte and one `Ed25519SignatureOffsets` record
+const ED25519_HEADER_LEN: usize = 2 + 14;
+
+/// Quote for `request`, signed by the relayer.
+///
+/// The nonce is taken from the nullifier hash, so retries of the same withdrawal
+/// reuse one nonce and the program's replay check lets only one of them through.
+pub fn quote(request: &WithdrawRequest, slot: u64, validity_slots: u64, exclusivity_slots: u64) -> RelayerQuote {
+    let mut nonce = [0u8; 8];
+    nonce.copy_from_slice(&request.nullifier_hash[24..]);
+
+    RelayerQuote {
+        pool: request.pool,
+        fee: request.fee,
+        expiry_slot: slot + validity_slots,
+        nonce: u64::from_le_bytes(nonce),
+        exclusive_until_slot: if exclusivity_slots == 0 { 0 } else { slot + exclusivity_slots },
+        // The reference relayer takes its whole fee in pool tokens
+        sol_fee: 0,
+    }
+}
+
+/// ed25519 program instruction verifying `signature` by `signer` over `message`,
+/// with every offset pointing into the instruction itself as `withdraw` requires
+pub fn ed25519_instruction(signer: &Pubkey, signature: &Signature, message: &[u8]) -> Instruction {
+    let public_key_offset = ED25519_HEADER_LEN;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for field in [
+        signature_offset as u16,
+        CURRENT_INSTRUCTION,
+        public_key_offset as u16,
+        CURRENT_INSTRUCTION,
+        message_offset as u16,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Instructions of a relayed withdrawal: the quote signature check, the relayer's
+/// fee token account for SPL pools, and `withdraw` itself.
+/// `token_program` is the owner of the pool's mint, `None` for native pools.
+pub fn withdraw_instructions(
+    relayer: &Keypair,
+    pool: &Pool,
+    request: &WithdrawRequest,
+    quote: &RelayerQuote,
+    token_program: Option<Pubkey>,
+) -> Vec<Instruction> {
+    let relayer_key = relayer.pubkey();
+    let relayed = request.fee > 0;
+    let message = quote.message();
+
+    let mut instructions = Vec::with_capacity(3);
+    if relayed {
+        let signature = relayer.sign_message(&message);
+        instructions.push(ed25519_instruction(&relayer_key, &signature, &message));
+    }
+
+    let spl = token_program.filter(|_| pool.is_spl_token);
+    if let (Some(token_program), true) = (spl, relayed) {
+        instructions.push(create_associated_token_account_idempotent(
+            &relayer_key,
+            &relayer_key,
+            &pool.mint,
+            &token_program,
+        ));
+    }
+
+    let ata = |owner: &Pubkey| spl.map(|token_program| {
+        get_associated_token_address_with_program_id(owner, &pool.mint, &token_program)
+    });
+    let relayer_stats = pda::relayer(&relayer_key);
+
+    let accounts = solana_veil::accounts::Withdraw {
+        user: relayer_key,
+        pool: request.pool,
+        tree: pool.tree,
+        checkpoints: pda::checkpoints(&request.pool),
+        config: pda::config(),
+        verifying_key: pda::verifying_key(&request.pool, VerifierCircuit::Withdraw),
+        nullifier: pda::nullifier(&request.nullifier_hash, &request.pool),
+        pool_token_account: spl.map(|_| pool.vault),
+        sol_vault: (!pool.is_spl_token).then_some(pool.vault),
+        recipient: request.recipient,
+        recipient_token_account: ata(&request.recipient),
+        relayer: relayed.then_some(relayer_key),
+        relayer_stats: relayed.then_some(relayer_stats),
+        quote_nonce: relayed.then(|| pda::quote_nonce(&relayer_stats, quote.nonce)),
+        relayer_mint_stats: relayed.then(|| pda::relayer_mint_stats(&relayer_stats, &pool.mint)),
+        instructions_sysvar: relayed.then_some(sysvar::instructions::ID),
+        relayer_token_account: if relayed { ata(&relayer_key) } else { None },
+        treasury_vault: (!pool.is_spl_token).then(|| pda::treasury_vault(&request.pool)),
+        treasury_token_account: spl.map(|_| pda::treasury_vault(&request.pool)),
+        sol_fee_config: None,
+        sol_fee_reserve: None,
+        token_price_feed: None,
+        sol_price_feed: None,
+        mint: spl.map(|_| pool.mint),
+        screening_program: screening::is_screening_enabled(&pool.screening_program)
+            .then_some(pool.screening_program),
+        callback_program: None,
+        recipient_program: None,
+        callback_authority: None,
+        system_program: system_program::ID,
+        token_program: spl,
+        associated_token_program: spl.map(|_| spl_associated_token_account::ID),
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(
+        request.screening_accounts.iter().map(|account| AccountMeta::new_readonly(*account, false)),
+    );
+
+    let data = solana_veil::instruction::Withdraw {
+        args: WithdrawArgs {
+            proof_data: request.proof.clone(),
+            root: request.root,
+            nullifier_hash: request.nullifier_hash,
+            recipient: request.recipient,
+            fee: request.fee,
+            leaf_index_bound: request.leaf_index_bound,
+        },
+        callback: None,
+        quote: relayed.then_some(*quote),
+    }
+    .data();
+
+    instructions.push(Instruction {
+        program_id: solana_veil::ID,
+        accounts: account_metas,
+        data,
+    });
+    instructions
+}
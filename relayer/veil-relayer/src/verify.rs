@@ -0,0 +1,73 @@
+//! Off-chain pre-verification of withdraw requests.
+//!
+//! Runs every check the program would, plus the Groth16 proof check through the
+//! program's own `verifier` module, before the relayer spends anything on a transaction.
+
+use solana_sdk::pubkey::Pubkey;
+use solana_veil::screening;
+use solana_veil::state::{
+    pause_flags, DepositCheckpoints, MerkleTree, Nullifier, Pool, VerifierCircuit, VerifyingKeyAccount,
+};
+use solana_veil::verifier::verify_groth16_proof;
+
+use crate::chain::{fetch, Chain};
+use crate::error::{RelayerError, Result};
+use crate::fees;
+use crate::pda;
+use crate::request::{WithdrawPublicInputs, WithdrawRequest};
+
+/// Check `request` against current chain state and verify its proof, returning the pool
+pub async fn preverify(chain: &dyn Chain, relayer: &Pubkey, request: &WithdrawRequest) -> Result<Pool> {
+    let pool = fetch::<Pool>(chain, &request.pool)
+        .await?
+        .ok_or(RelayerError::UnknownPool(request.pool))?;
+
+    if pause_flags::is_paused(pool.pause_flags, pause_flags::WITHDRAWALS) {
+        return Err(RelayerError::WithdrawalsPaused(request.pool));
+    }
+
+    if screening::is_screening_enabled(&pool.screening_program) && request.screening_accounts.is_empty() {
+        return Err(RelayerError::InvalidRequest(
+            "pool requires screening policy accounts".into(),
+        ));
+    }
+
+    let tree = fetch::<MerkleTree>(chain, &pool.tree)
+        .await?
+        .ok_or_else(|| RelayerError::Misconfigured(format!("merkle tree {} not found", pool.tree)))?;
+    if !tree.is_known_root(&request.root) {
+        return Err(RelayerError::StaleRoot);
+    }
+
+    let nullifier = fetch::<Nullifier>(chain, &pda::nullifier(&request.nullifier_hash, &request.pool)).await?;
+    if nullifier.is_some_and(|nullifier| nullifier.is_spent) {
+        return Err(RelayerError::NullifierSpent);
+    }
+
+    // Same deposit-age bound as `withdraw`
+    let checkpoints = fetch::<DepositCheckpoints>(chain, &pda::checkpoints(&request.pool))
+        .await?
+        .ok_or_else(|| RelayerError::Misconfigured("deposit checkpoints not found".into()))?;
+    let cutoff_slot = chain.get_slot().await?.saturating_sub(pool.min_deposit_age_slots);
+    if request.leaf_index_bound > checkpoints.leaves_inserted_before(cutoff_slot, tree.num_leaves) {
+        return Err(RelayerError::InvalidRequest("deposit is too recent to withdraw".into()));
+    }
+
+    fees::check_fee(chain, relayer, &pool, request.fee).await?;
+
+    let verifying_key = fetch::<VerifyingKeyAccount>(
+        chain,
+        &pda::verifying_key(&request.pool, VerifierCircuit::Withdraw),
+    )
+    .await?
+    .ok_or(RelayerError::MissingVerifyingKey(request.pool))?;
+
+    // `withdraw` only binds a relayer when there is a fee to pay it
+    let bound_relayer = if request.fee > 0 { *relayer } else { Pubkey::default() };
+    let public_inputs = WithdrawPublicInputs::new(request, bound_relayer, pool.denomination)
+        .field_elements()?;
+    verify_groth16_proof(&request.proof, &verifying_key.data, &public_inputs)
+        .map_err(|_| RelayerError::InvalidProof)?;
+
+    Ok(pool)
+}
@@ -0,0 +1,507 @@
+//! `solana-program-test` harness: a bank running the SolanaVeil program with a
+//! native pool and a registered relayer, and Groth16 keys for stand-in circuits
+//! that only bind the withdraw and batch update public inputs.
+
+// Each test binary uses a different part of the fixture
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anchor_lang::{AccountSerialize, InstructionData, ToAccountMetas};
+use ark_bn254::{Bn254, Fr, G1Affine, G2Affine};
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Groth16, ProvingKey, VerifyingKey};
+use ark_relations::lc;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::rand::rngs::StdRng;
+use ark_std::rand::SeedableRng;
+use async_trait::async_trait;
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::account::Account;
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::keccak;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use solana_veil::state::{
+    CommitmentBatch, Config, DepositCheckpoint, DepositCheckpoints, MerkleTree, Nullifier, Pool, Relayer,
+    VerifierCircuit, VerifyingKeyAccount, MAX_DEPOSIT_CHECKPOINTS, MAX_SEALED_BATCHES,
+    ROOT_HISTORY_SIZE,
+};
+use solana_veil::verifier::field_element_from_u64;
+use tokio::sync::Mutex;
+
+use veil_relayer::chain::{fetch, Chain};
+use veil_relayer::error::{RelayerError, Result};
+use veil_relayer::pda;
+use veil_relayer::request::{WithdrawPublicInputs, WithdrawRequest};
+use veil_relayer::service::{JobView, RelayerService, ServiceSettings};
+use veil_relayer::transaction;
+use veil_relayer_queue::{JobQueue, RetryPolicy};
+
+pub const DENOMINATION: u64 = LAMPORTS_PER_SOL;
+
+/// Relayer's flat fee in basis points
+pub const RELAYER_FEE_BASIS_POINTS: u16 = 100;
+
+pub const PROTOCOL_FEE_BASIS_POINTS: u16 = 10;
+
+pub const ROOT: [u8; 32] = [7; 32];
+
+/// Hash chain of the tree's pending batch
+pub const PENDING_HASH_CHAIN: [u8; 32] = [8; 32];
+
+/// Number of public signals of `withdraw.circom`
+const PUBLIC_SIGNALS: usize = 9;
+
+/// Number of public signals of the batch update circuit
+const BATCH_PUBLIC_SIGNALS: usize = 5;
+
+/// Stand-in for the pool's circuits that only constrains each public input to itself,
+/// so a proof is valid for exactly the inputs it was made for
+#[derive(Clone)]
+struct PublicInputsCircuit {
+    inputs: Vec<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for PublicInputsCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> std::result::Result<(), SynthesisError> {
+        for value in self.inputs {
+            let input = cs.new_input_variable(|| Ok(value))?;
+            let square = cs.new_witness_variable(|| Ok(value * value))?;
+            cs.enforce_constraint(lc!() + input, lc!() + input, lc!() + square)?;
+        }
+        Ok(())
+    }
+}
+
+/// Uncompressed big-endian point, as the alt_bn128 syscalls take it
+fn g1_bytes(point: &G1Affine) -> Vec<u8> {
+    [point.x, point.y].iter().flat_map(|coordinate| coordinate.into_bigint().to_bytes_be()).collect()
+}
+
+/// Uncompressed big-endian point with each coordinate's c1 first, as the alt_bn128 syscalls take it
+fn g2_bytes(point: &G2Affine) -> Vec<u8> {
+    [point.x.c1, point.x.c0, point.y.c1, point.y.c0]
+        .iter()
+        .flat_map(|coordinate| coordinate.into_bigint().to_bytes_be())
+        .collect()
+}
+
+/// `VerifyingKeyAccount` encoding: alpha, then beta, gamma and delta negated, then IC
+fn verifying_key_bytes(verifying_key: &VerifyingKey<Bn254>) -> Vec<u8> {
+    let mut bytes = g1_bytes(&verifying_key.alpha_g1);
+    for point in [verifying_key.beta_g2, verifying_key.gamma_g2, verifying_key.delta_g2] {
+        bytes.extend(g2_bytes(&-point));
+    }
+    for point in &verifying_key.gamma_abc_g1 {
+        bytes.extend(g1_bytes(point));
+    }
+    bytes
+}
+
+/// Proof of the stand-in circuit for `inputs`, in the program's encoding
+fn groth16_proof(proving_key: &ProvingKey<Bn254>, inputs: &[[u8; 32]]) -> Vec<u8> {
+    let inputs = inputs.iter().map(|bytes| Fr::from_be_bytes_mod_order(bytes)).collect();
+    let mut rng = StdRng::seed_from_u64(7);
+    let proof = Groth16::<Bn254>::create_random_proof_with_reduction(
+        PublicInputsCircuit { inputs },
+        proving_key,
+        &mut rng,
+    )
+    .unwrap();
+
+    [g1_bytes(&proof.a), g2_bytes(&proof.b), g1_bytes(&proof.c)].concat()
+}
+
+/// Anchor's generated entrypoint wants the account slice to outlive the call
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    solana_veil::entry(program_id, accounts, data)
+}
+
+fn anchor_account<T: AccountSerialize>(account: &T) -> Account {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: solana_veil::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn verifying_key_account(pool: Pubkey, circuit: VerifierCircuit, proving_key: &ProvingKey<Bn254>) -> Account {
+    let data = verifying_key_bytes(&proving_key.vk);
+    anchor_account(&VerifyingKeyAccount {
+        pool,
+        circuit,
+        key_hash: keccak::hash(&data).to_bytes(),
+        data,
+        updated_at: 0,
+        bump: find(&[b"verifying_key", pool.as_ref(), &[circuit as u8]]).1,
+    })
+}
+
+fn system_account(lamports: u64) -> Account {
+    Account::new(lamports, 0, &system_program::ID)
+}
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &solana_veil::ID)
+}
+
+/// [`Chain`] over a `solana-program-test` bank. Transactions are processed when
+/// sent; failures surface from `send_transaction` like an RPC preflight failure.
+pub struct BanksChain {
+    client: Mutex<BanksClient>,
+    statuses: Mutex<HashMap<Signature, std::result::Result<(), TransactionError>>>,
+}
+
+#[async_trait]
+impl Chain for BanksChain {
+    async fn get_account(&self, address: &Pubkey) -> Result<Option<Account>> {
+        let mut client = self.client.lock().await;
+        client.get_account(*address).await.map_err(|error| RelayerError::Rpc(error.to_string()))
+    }
+
+    async fn get_slot(&self) -> Result<u64> {
+        let mut client = self.client.lock().await;
+        client.get_root_slot().await.map_err(|error| RelayerError::Rpc(error.to_string()))
+    }
+
+    async fn get_latest_blockhash(&self) -> Result<(Hash, u64)> {
+        let mut client = self.client.lock().await;
+        let blockhash = client
+            .get_latest_blockhash()
+            .await
+            .map_err(|error| RelayerError::Rpc(error.to_string()))?;
+
+        // Banks transactions settle synchronously, so blockhashes never expire here
+        // (the queue stores heights as SQLite integers)
+        Ok((blockhash, i64::MAX as u64))
+    }
+
+    async fn get_block_height(&self) -> Result<u64> {
+        Ok(0)
+    }
+
+    async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let signature = transaction.signatures[0];
+        let mut client = self.client.lock().await;
+        client
+            .process_transaction(transaction.clone())
+            .await
+            .map_err(|error| RelayerError::Rpc(error.to_string()))?;
+
+        self.statuses.lock().await.insert(signature, Ok(()));
+        Ok(signature)
+    }
+
+    async fn get_signature_status(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<std::result::Result<(), TransactionError>>> {
+        Ok(self.statuses.lock().await.get(signature).cloned())
+    }
+}
+
+pub struct Fixture {
+    pub chain: Arc<BanksChain>,
+    pub service: Arc<RelayerService>,
+    pub relayer: Pubkey,
+    pub pool: Pubkey,
+    pub tree: Pubkey,
+    relayer_keypair: Keypair,
+    proving_key: ProvingKey<Bn254>,
+    batch_proving_key: ProvingKey<Bn254>,
+}
+
+impl Fixture {
+    /// Start a bank with one native pool, its verifying key and an active relayer.
+    /// `spent_nullifiers` are recorded as already withdrawn.
+    pub async fn start(spent_nullifiers: &[[u8; 32]]) -> Self {
+        let mut rng = StdRng::seed_from_u64(42);
+        let proving_key = Groth16::<Bn254>::generate_random_parameters_with_reduction(
+            PublicInputsCircuit { inputs: vec![Fr::from(0u64); PUBLIC_SIGNALS] },
+            &mut rng,
+        )
+        .unwrap();
+        let batch_proving_key = Groth16::<Bn254>::generate_random_parameters_with_reduction(
+            PublicInputsCircuit { inputs: vec![Fr::from(0u64); BATCH_PUBLIC_SIGNALS] },
+            &mut rng,
+        )
+        .unwrap();
+
+        let mut program_test = ProgramTest::new("solana_veil", solana_veil::ID, processor!(process_instruction));
+
+        let authority = Pubkey::new_unique();
+        let relayer = Keypair::new();
+        let tree = Pubkey::new_unique();
+        let (pool, pool_bump) = find(&[b"pool", &DENOMINATION.to_le_bytes(), Pubkey::default().as_ref()]);
+        let sol_vault = pda::sol_vault(&pool);
+        let (_, vault_bump) = find(&[b"sol_vault", pool.as_ref()]);
+
+        program_test.add_account(relayer.pubkey(), system_account(10 * LAMPORTS_PER_SOL));
+        program_test.add_account(sol_vault, system_account(10 * DENOMINATION));
+        program_test.add_account(pda::treasury_vault(&pool), system_account(Rent::default().minimum_balance(0)));
+
+        program_test.add_account(pda::config(), anchor_account(&Config {
+            authority,
+            protocol_fee_basis_points: PROTOCOL_FEE_BASIS_POINTS,
+            treasury: authority,
+            pools_enabled: true,
+            admin_timelock_seconds: 0,
+            bump: find(&[b"config"]).1,
+        }));
+
+        program_test.add_account(pool, anchor_account(&Pool {
+            authority,
+            pending_authority: Pubkey::default(),
+            denomination: DENOMINATION,
+            merkle_root: ROOT,
+            max_depth: 20,
+            tree,
+            mint: Pubkey::default(),
+            vault: sol_vault,
+            vault_bump,
+            is_spl_token: false,
+            max_fee_basis_points: 500,
+            min_withdrawal_amount: 0,
+            pause_flags: 0,
+            pauser: Pubkey::default(),
+            total_deposited: 10 * DENOMINATION,
+            total_withdrawn: 0,
+            total_protocol_fees: 0,
+            protocol_fees_claimed: 0,
+            screening_program: Pubkey::default(),
+            min_deposit_age_slots: 0,
+            registry_index: 0,
+            max_total_deposits: 0,
+            withdrawal_limit_amount: 0,
+            withdrawal_limit_window_slots: 0,
+            withdrawal_window_start_slot: 0,
+            withdrawal_window_start_total: 0,
+            withdrawal_previous_window_amount: 0,
+            bump: pool_bump,
+        }));
+
+        program_test.add_account(tree, anchor_account(&MerkleTree {
+            authority,
+            pending_authority: Pubkey::default(),
+            max_depth: 20,
+            num_leaves: 12,
+            root: ROOT,
+            root_history: [[0; 32]; ROOT_HISTORY_SIZE],
+            root_history_head: 0,
+            pool,
+            open_batch: CommitmentBatch { hash_chain: PENDING_HASH_CHAIN, start_index: 10, count: 2 },
+            sealed_batches: [CommitmentBatch::default(); MAX_SEALED_BATCHES],
+            sealed_head: 0,
+            sealed_count: 0,
+            bump: 0,
+        }));
+
+        program_test.add_account(pda::checkpoints(&pool), anchor_account(&DepositCheckpoints {
+            pool,
+            head: 0,
+            count: 0,
+            last_deposit_slot: 0,
+            evicted_next_index: 0,
+            evicted_slot: 0,
+            checkpoints: [DepositCheckpoint::default(); MAX_DEPOSIT_CHECKPOINTS],
+            bump: find(&[b"checkpoints", pool.as_ref()]).1,
+        }));
+
+        for (circuit, proving_key) in [
+            (VerifierCircuit::Withdraw, &proving_key),
+            (VerifierCircuit::BatchUpdate, &batch_proving_key),
+        ] {
+            program_test.add_account(
+                pda::verifying_key(&pool, circuit),
+                verifying_key_account(pool, circuit, proving_key),
+            );
+        }
+
+        program_test.add_account(pda::relayer(&relayer.pubkey()), anchor_account(&Relayer {
+            authority: relayer.pubkey(),
+            is_active: true,
+            fee_basis_points: RELAYER_FEE_BASIS_POINTS,
+            total_relayed: 0,
+            total_fees: 0,
+            successful_withdrawals: 0,
+            last_active_slot: 0,
+            stake_amount: LAMPORTS_PER_SOL,
+            unstake_requested_slot: 0,
+            last_slashed_slot: 0,
+            slash_count: 0,
+            quote_signer: relayer.pubkey(),
+            bump: find(&[b"relayer", relayer.pubkey().as_ref()]).1,
+        }));
+
+        for nullifier_hash in spent_nullifiers {
+            program_test.add_account(pda::nullifier(nullifier_hash, &pool), anchor_account(&Nullifier {
+                is_spent: true,
+                nullifier_hash: *nullifier_hash,
+                pool,
+                spent_at: 0,
+                recipient: Pubkey::default(),
+            }));
+        }
+
+        let (client, _payer, _blockhash) = program_test.start().await;
+        let chain = Arc::new(BanksChain {
+            client: Mutex::new(client),
+            statuses: Mutex::new(HashMap::new()),
+        });
+
+        let relayer_key = relayer.pubkey();
+        let relayer_keypair = Keypair::from_bytes(&relayer.to_bytes()).unwrap();
+        let settings = ServiceSettings {
+            retry: RetryPolicy {
+                max_attempts: 3,
+                poll_interval: Duration::from_millis(10),
+                backoff: Duration::from_millis(10),
+            },
+            ..ServiceSettings::default()
+        };
+        let queue = JobQueue::open_in_memory().unwrap();
+        let service = RelayerService::new(chain.clone(), relayer, queue, settings);
+
+        Self {
+            chain,
+            service,
+            relayer: relayer_key,
+            pool,
+            tree,
+            relayer_keypair,
+            proving_key,
+            batch_proving_key,
+        }
+    }
+
+    /// The relayer's fee for one withdrawal
+    pub fn fee(&self) -> u64 {
+        DENOMINATION * RELAYER_FEE_BASIS_POINTS as u64 / 10_000
+    }
+
+    /// Request paying `recipient` with the relayer's fee, without a proof
+    pub fn request(&self, nullifier_hash: [u8; 32], recipient: Pubkey) -> WithdrawRequest {
+        WithdrawRequest {
+            pool: self.pool,
+            proof: Vec::new(),
+            root: ROOT,
+            nullifier_hash,
+            recipient,
+            fee: self.fee(),
+            leaf_index_bound: 1,
+            screening_accounts: Vec::new(),
+        }
+    }
+
+    /// Proof for the public inputs of `request`, as relayed by this relayer
+    pub fn prove(&self, request: &WithdrawRequest) -> Vec<u8> {
+        let inputs = WithdrawPublicInputs::new(request, self.relayer, DENOMINATION)
+            .field_elements()
+            .unwrap();
+        groth16_proof(&self.proving_key, &inputs)
+    }
+
+    /// Proven request paying `recipient`
+    pub fn proven_request(&self, nullifier_hash: [u8; 32], recipient: Pubkey) -> WithdrawRequest {
+        let mut request = self.request(nullifier_hash, recipient);
+        request.proof = self.prove(&request);
+        request
+    }
+
+    /// The relayer's instructions for `request`
+    pub async fn withdraw_instructions(&self, request: &WithdrawRequest) -> Result<Vec<Instruction>> {
+        let pool = fetch::<Pool>(self.chain.as_ref(), &self.pool).await?.unwrap();
+        let quote = transaction::quote(request, self.chain.get_slot().await?, 100, 0);
+        Ok(transaction::withdraw_instructions(&self.relayer_keypair, &pool, request, &quote, None))
+    }
+
+    /// Proven `batch_update_root` applying the tree's pending batch to reach `new_root`
+    pub fn batch_update_instruction(&self, new_root: [u8; 32]) -> Instruction {
+        // Public inputs in circuit order: oldRoot, newRoot, hashChain, startIndex, batchSize
+        let inputs = [
+            ROOT,
+            new_root,
+            PENDING_HASH_CHAIN,
+            field_element_from_u64(10),
+            field_element_from_u64(2),
+        ];
+        Instruction {
+            program_id: solana_veil::ID,
+            accounts: solana_veil::accounts::BatchUpdateRoot {
+                updater: self.relayer,
+                pool: self.pool,
+                tree: self.tree,
+                verifying_key: pda::verifying_key(&self.pool, VerifierCircuit::BatchUpdate),
+            }
+            .to_account_metas(None),
+            data: solana_veil::instruction::BatchUpdateRoot {
+                proof_data: groth16_proof(&self.batch_proving_key, &inputs),
+                new_root,
+            }
+            .data(),
+        }
+    }
+
+    /// Compute units `instructions` consume when the relayer sends them; panics if they fail
+    pub async fn compute_units(&self, instructions: &[Instruction]) -> u64 {
+        let (blockhash, _) = self.chain.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.relayer),
+            &[&self.relayer_keypair],
+            blockhash,
+        );
+        let simulation = self.chain.client.lock().await.simulate_transaction(transaction).await.unwrap();
+        let details = simulation.simulation_details.unwrap();
+        assert_eq!(simulation.result, Some(Ok(())), "{:#?}", details.logs);
+        details.units_consumed
+    }
+
+    /// Send `request` straight to the program as the relayer, skipping pre-verification
+    pub async fn send_unverified(&self, request: &WithdrawRequest) -> Result<Signature> {
+        let instructions = self.withdraw_instructions(request).await?;
+        let (blockhash, _) = self.chain.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&self.relayer),
+            &[&self.relayer_keypair],
+            blockhash,
+        );
+        self.chain.send_transaction(&transaction).await
+    }
+
+    pub async fn balance(&self, address: &Pubkey) -> u64 {
+        self.chain
+            .get_account(address)
+            .await
+            .unwrap()
+            .map_or(0, |account| account.lamports)
+    }
+
+    /// Wait until job `id` reaches a final status
+    pub async fn wait_for(&self, id: &str) -> JobView {
+        for _ in 0..500 {
+            let job = self.service.job_status(id).await.unwrap();
+            if job.status.is_terminal() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {id} did not finish");
+    }
+}
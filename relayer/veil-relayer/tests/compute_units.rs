@@ -0,0 +1,32 @@
+//! Compute units of the instructions that verify a Groth16 proof. The program only
+//! runs metered against its SBF build (`cargo test-sbf`, which sets `SBF_OUT_DIR`);
+//! under `cargo test` it runs natively and these only check that the instructions succeed.
+
+mod common;
+
+use solana_sdk::pubkey::Pubkey;
+
+use common::Fixture;
+
+/// Default compute budget of one instruction
+const INSTRUCTION_COMPUTE_LIMIT: u64 = 200_000;
+
+#[tokio::test]
+async fn withdraw_fits_the_default_compute_budget() {
+    let fixture = Fixture::start(&[]).await;
+    let request = fixture.proven_request([11; 32], Pubkey::new_unique());
+
+    let instructions = fixture.withdraw_instructions(&request).await.unwrap();
+    let units = fixture.compute_units(&instructions).await;
+    println!("withdraw: {units} compute units");
+    assert!(units <= INSTRUCTION_COMPUTE_LIMIT, "withdraw used {units} compute units");
+}
+
+#[tokio::test]
+async fn batch_update_root_fits_the_default_compute_budget() {
+    let fixture = Fixture::start(&[]).await;
+
+    let units = fixture.compute_units(&[fixture.batch_update_instruction([9; 32])]).await;
+    println!("batch_update_root: {units} compute units");
+    assert!(units <= INSTRUCTION_COMPUTE_LIMIT, "batch_update_root used {units} compute units");
+}
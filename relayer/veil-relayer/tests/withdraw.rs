@@ -0,0 +1,130 @@
+mod common;
+
+use anchor_lang::AccountDeserialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_veil::state::Nullifier;
+
+use common::{Fixture, DENOMINATION, PROTOCOL_FEE_BASIS_POINTS};
+use veil_relayer::chain::Chain;
+use veil_relayer::error::RelayerError;
+use veil_relayer::pda;
+use veil_relayer_queue::JobState;
+
+#[tokio::test]
+async fn relays_a_verified_withdrawal() {
+    let fixture = Fixture::start(&[]).await;
+    let recipient = Pubkey::new_unique();
+    let nullifier_hash = [1; 32];
+    let relayer_before = fixture.balance(&fixture.relayer).await;
+
+    let request = fixture.proven_request(nullifier_hash, recipient);
+    let id = fixture.service.submit(request.clone()).await.unwrap();
+    let job = fixture.wait_for(&id).await;
+    assert_eq!(job.status, JobState::Confirmed, "{job:?}");
+    assert_eq!(job.attempts, 1);
+
+    let protocol_fee = DENOMINATION * PROTOCOL_FEE_BASIS_POINTS as u64 / 10_000;
+    assert_eq!(
+        fixture.balance(&recipient).await,
+        DENOMINATION - fixture.fee() - protocol_fee
+    );
+
+    // The fee covers the relayer's transaction costs and the rent it pays
+    assert!(fixture.balance(&fixture.relayer).await > relayer_before);
+
+    let nullifier = fixture
+        .chain
+        .get_account(&pda::nullifier(&nullifier_hash, &fixture.pool))
+        .await
+        .unwrap()
+        .unwrap();
+    let nullifier = Nullifier::try_deserialize(&mut nullifier.data.as_slice()).unwrap();
+    assert!(nullifier.is_spent);
+    assert_eq!(nullifier.recipient, recipient);
+
+    // Resubmitting the same withdrawal returns the existing job
+    assert_eq!(fixture.service.submit(request).await.unwrap(), id);
+    assert_eq!(fixture.service.job_status(&id).await.unwrap(), job);
+}
+
+#[tokio::test]
+async fn rejects_fees_below_the_relayer_fee() {
+    let fixture = Fixture::start(&[]).await;
+
+    let mut request = fixture.request([2; 32], Pubkey::new_unique());
+    request.fee = fixture.fee() - 1;
+    request.proof = fixture.prove(&request);
+
+    let error = fixture.service.submit(request).await.unwrap_err();
+    assert!(
+        matches!(error, RelayerError::FeeTooLow { required, .. } if required == fixture.fee()),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn rejects_proofs_for_other_inputs() {
+    let fixture = Fixture::start(&[]).await;
+
+    // Proof made for one recipient, submitted for another
+    let mut request = fixture.proven_request([3; 32], Pubkey::new_unique());
+    request.recipient = Pubkey::new_unique();
+
+    let error = fixture.service.submit(request).await.unwrap_err();
+    assert!(matches!(error, RelayerError::InvalidProof), "{error:?}");
+}
+
+#[tokio::test]
+async fn program_rejects_proofs_for_other_inputs() {
+    let fixture = Fixture::start(&[]).await;
+    let nullifier_hash = [7; 32];
+
+    // Proof made for one recipient, sent to the program for another
+    let mut request = fixture.proven_request(nullifier_hash, Pubkey::new_unique());
+    request.recipient = Pubkey::new_unique();
+
+    fixture.send_unverified(&request).await.unwrap_err();
+    assert_eq!(fixture.balance(&request.recipient).await, 0);
+    let nullifier = fixture
+        .chain
+        .get_account(&pda::nullifier(&nullifier_hash, &fixture.pool))
+        .await
+        .unwrap();
+    assert!(nullifier.is_none());
+
+    // The same proof goes through for the recipient it was made for
+    let request = fixture.proven_request(nullifier_hash, request.recipient);
+    fixture.send_unverified(&request).await.unwrap();
+    assert!(fixture.balance(&request.recipient).await > 0);
+}
+
+#[tokio::test]
+async fn rejects_stale_roots() {
+    let fixture = Fixture::start(&[]).await;
+
+    let mut request = fixture.request([4; 32], Pubkey::new_unique());
+    request.root = [8; 32];
+    request.proof = fixture.prove(&request);
+
+    let error = fixture.service.submit(request).await.unwrap_err();
+    assert!(matches!(error, RelayerError::StaleRoot), "{error:?}");
+}
+
+#[tokio::test]
+async fn rejects_spent_nullifiers() {
+    let nullifier_hash = [5; 32];
+    let fixture = Fixture::start(&[nullifier_hash]).await;
+
+    let request = fixture.proven_request(nullifier_hash, Pubkey::new_unique());
+
+    let error = fixture.service.submit(request).await.unwrap_err();
+    assert!(matches!(error, RelayerError::NullifierSpent), "{error:?}");
+}
+
+#[tokio::test]
+async fn reports_unknown_jobs() {
+    let fixture = Fixture::start(&[]).await;
+
+    let error = fixture.service.job_status(&hex::encode([6; 32])).await.unwrap_err();
+    assert!(matches!(error, RelayerError::UnknownJob(_)), "{error:?}");
+}